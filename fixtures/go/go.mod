@@ -0,0 +1,16 @@
+module example.com/fixture
+
+go 1.22
+
+require (
+	github.com/spf13/cobra v1.8.0
+	golang.org/x/text v0.14.0 // indirect
+	example.com/forked v1.0.0
+	example.com/local v0.1.0
+)
+
+require github.com/google/uuid v1.6.0
+
+replace example.com/forked => github.com/fixture/forked v1.0.1
+
+replace example.com/local => ../local
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Component, Path};
+
+use anyhow::Context;
+use flate2::read::GzDecoder;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+    /// A RubyGems `.gem`: a plain tar whose `data.tar.gz` member holds the gem files.
+    Gem,
+    /// A Hex package tarball: a plain tar whose `contents.tar.gz` member holds the
+    /// package files, next to `VERSION`, `CHECKSUM`, and `metadata.config`.
+    HexTarball,
+}
+
+/// Reads every regular file in a package archive, keyed by its `/`-separated path.
+///
+/// `strip_components` leading path components are dropped, like `tar --strip-components`:
+/// one for the directory npm tarballs (`package/`), sdists and `.crate` files
+/// (`<name>-<version>/`) wrap their contents in, and one per module path element for
+/// Go module zips (`<module>@<version>/`).
+pub fn read_package_files(
+    bytes: &[u8],
+    format: ArchiveFormat,
+    strip_components: usize,
+) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    match format {
+        ArchiveFormat::TarGz => read_tar_gz_files(bytes, strip_components),
+        ArchiveFormat::Zip => read_zip_files(bytes, strip_components),
+        ArchiveFormat::Gem => read_nested_tar_gz_files(bytes, "data.tar.gz", strip_components),
+        ArchiveFormat::HexTarball => {
+            read_nested_tar_gz_files(bytes, "contents.tar.gz", strip_components)
+        }
+    }
+}
+
+/// Writes the files of a package archive under `destination`.
+pub fn unpack_package_files(
+    bytes: &[u8],
+    format: ArchiveFormat,
+    strip_components: usize,
+    destination: &Path,
+) -> anyhow::Result<()> {
+    let files = read_package_files(bytes, format, strip_components)?;
+    fs::create_dir_all(destination)
+        .with_context(|| format!("failed to create directory {}", destination.display()))?;
+    for (relative_path, contents) in &files {
+        let path = destination.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory {}", parent.display()))?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn read_tar_gz_files(
+    bytes: &[u8],
+    strip_components: usize,
+) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+    for entry in archive
+        .entries()
+        .context("failed to read archive entries")?
+    {
+        let mut entry = entry.context("failed to read archive entry")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .context("archive entry has an invalid path")?
+            .into_owned();
+        let Some(relative_path) = package_relative_path(&path, strip_components)? else {
+            continue;
+        };
+
+        let mut contents = Vec::new();
+        entry
+            .read_to_end(&mut contents)
+            .with_context(|| format!("failed to read archive entry {}", relative_path))?;
+        files.insert(relative_path, contents);
+    }
+
+    Ok(files)
+}
+
+fn read_zip_files(
+    bytes: &[u8],
+    strip_components: usize,
+) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("failed to read zip archive")?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .context("failed to read zip archive entry")?;
+        if !entry.is_file() {
+            continue;
+        }
+        let Some(path) = entry.enclosed_name() else {
+            anyhow::bail!(
+                "zip archive entry escapes the archive root: {}",
+                entry.name()
+            );
+        };
+        let Some(relative_path) = package_relative_path(&path, strip_components)? else {
+            continue;
+        };
+
+        let mut contents = Vec::new();
+        entry
+            .read_to_end(&mut contents)
+            .with_context(|| format!("failed to read zip archive entry {}", relative_path))?;
+        files.insert(relative_path, contents);
+    }
+
+    Ok(files)
+}
+
+/// Reads the files of the gzipped tar stored as `member` inside a plain tar.
+fn read_nested_tar_gz_files(
+    bytes: &[u8],
+    member: &str,
+    strip_components: usize,
+) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    let mut archive = tar::Archive::new(bytes);
+    for entry in archive
+        .entries()
+        .context("failed to read archive entries")?
+    {
+        let mut entry = entry.context("failed to read archive entry")?;
+        if entry
+            .path()
+            .context("archive entry has an invalid path")?
+            .as_ref()
+            != Path::new(member)
+        {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("failed to read {} from archive", member))?;
+        return read_tar_gz_files(&data, strip_components);
+    }
+
+    anyhow::bail!("archive does not contain {}", member)
+}
+
+fn package_relative_path(path: &Path, strip_components: usize) -> anyhow::Result<Option<String>> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(component) => components.push(component.to_string_lossy()),
+            Component::CurDir => {}
+            _ => anyhow::bail!("archive entry escapes the archive root: {}", path.display()),
+        }
+    }
+
+    let components = components.get(strip_components..).unwrap_or_default();
+    if components.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(components.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    fn tar_gz(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .expect("append tar entry");
+        }
+        builder
+            .into_inner()
+            .expect("finish tar")
+            .finish()
+            .expect("finish gzip")
+    }
+
+    #[test]
+    fn tarball_files_drop_the_top_level_directory() {
+        let bytes = tar_gz(&[
+            ("package/package.json", "{}"),
+            ("package/lib/index.js", "module.exports = 1;\n"),
+        ]);
+
+        let files = read_package_files(&bytes, ArchiveFormat::TarGz, 1).expect("read");
+
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["lib/index.js", "package.json"]
+        );
+        assert_eq!(files["lib/index.js"], b"module.exports = 1;\n");
+    }
+
+    #[test]
+    fn unpacks_package_files_into_destination() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bytes = tar_gz(&[("demo-1.0/src/demo/__init__.py", "x = 1\n")]);
+
+        unpack_package_files(&bytes, ArchiveFormat::TarGz, 1, &temp.path().join("out"))
+            .expect("unpack");
+
+        assert_eq!(
+            fs::read_to_string(temp.path().join("out/src/demo/__init__.py")).expect("read"),
+            "x = 1\n"
+        );
+    }
+
+    #[test]
+    fn zip_files_keep_their_paths_without_stripping() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("demo/__init__.py", zip::write::SimpleFileOptions::default())
+            .expect("start file");
+        writer.write_all(b"VERSION = 1\n").expect("write");
+        let bytes = writer.finish().expect("finish zip").into_inner();
+
+        let files = read_package_files(&bytes, ArchiveFormat::Zip, 0).expect("read");
+
+        assert_eq!(files["demo/__init__.py"], b"VERSION = 1\n");
+    }
+
+    #[test]
+    fn gem_files_come_from_the_nested_data_archive() {
+        let data = tar_gz(&[("lib/demo.rb", "module Demo; end\n")]);
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, contents) in [
+            ("metadata.gz", &b"not read"[..]),
+            ("data.tar.gz", &data[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents)
+                .expect("append gem entry");
+        }
+        let bytes = builder.into_inner().expect("finish gem");
+
+        let files = read_package_files(&bytes, ArchiveFormat::Gem, 0).expect("read");
+
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["lib/demo.rb"]
+        );
+    }
+
+    #[test]
+    fn hex_tarball_files_come_from_the_nested_contents_archive() {
+        let contents = tar_gz(&[("lib/demo.ex", "defmodule Demo do\nend\n")]);
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, data) in [
+            ("VERSION", &b"3"[..]),
+            ("metadata.config", &b"not read"[..]),
+            ("contents.tar.gz", &contents[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, data)
+                .expect("append hex tarball entry");
+        }
+        let bytes = builder.into_inner().expect("finish hex tarball");
+
+        let files = read_package_files(&bytes, ArchiveFormat::HexTarball, 0).expect("read");
+
+        assert_eq!(files["lib/demo.ex"], b"defmodule Demo do\nend\n");
+    }
+
+    #[test]
+    fn go_module_zip_drops_every_module_path_component() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                "example.com/demo@v1.0.0/pkg/demo.go",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .expect("start file");
+        writer.write_all(b"package pkg\n").expect("write");
+        let bytes = writer.finish().expect("finish zip").into_inner();
+
+        let files = read_package_files(&bytes, ArchiveFormat::Zip, 2).expect("read");
+
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["pkg/demo.go"]
+        );
+    }
+}
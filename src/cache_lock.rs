@@ -0,0 +1,123 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use tracing::{debug, info};
+
+use crate::error::PkgrepError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Advisory lock on a cache entry, released when dropped.
+#[derive(Debug)]
+pub struct CacheLock {
+    _file: File,
+}
+
+/// Returns the lock file guarding `cached_path`, mirrored under `<cache>/locks`.
+pub fn lock_path_for(cache_root: &Path, cached_path: &Path) -> PathBuf {
+    let relative = cached_path.strip_prefix(cache_root).unwrap_or(cached_path);
+    let mut lock_path = cache_root.join("locks").join(relative).into_os_string();
+    lock_path.push(".lock");
+    PathBuf::from(lock_path)
+}
+
+/// Takes the advisory lock for `cached_path`, waiting up to `timeout` while another
+/// process holds it.
+pub fn acquire(
+    cache_root: &Path,
+    cached_path: &Path,
+    description: &str,
+    timeout: Duration,
+) -> anyhow::Result<CacheLock> {
+    let lock_path = lock_path_for(cache_root, cached_path);
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create lock directory {}", parent.display()))?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("failed to open lock file {}", lock_path.display()))?;
+
+    let started = Instant::now();
+    let mut reported_wait = false;
+    loop {
+        match file.try_lock() {
+            Ok(()) => {
+                debug!(lock_path = %lock_path.display(), "acquired cache lock");
+                return Ok(CacheLock { _file: file });
+            }
+            Err(TryLockError::WouldBlock) => {}
+            Err(TryLockError::Error(err)) => {
+                return Err(err).with_context(|| format!("failed to lock {}", lock_path.display()));
+            }
+        }
+
+        let waited = started.elapsed();
+        if waited >= timeout {
+            return Err(PkgrepError::LockTimeout {
+                description: description.to_string(),
+                path: lock_path,
+                timeout_secs: timeout.as_secs(),
+            }
+            .into());
+        }
+        if !reported_wait {
+            info!(
+                lock_path = %lock_path.display(),
+                timeout_secs = timeout.as_secs(),
+                "waiting for cache lock held by another process"
+            );
+            reported_wait = true;
+        }
+        std::thread::sleep(POLL_INTERVAL.min(timeout - waited));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_path_mirrors_cache_layout_under_locks_dir() {
+        let cache_root = Path::new("/cache");
+        assert_eq!(
+            lock_path_for(cache_root, &cache_root.join("repos/git/b64_abc.git")),
+            PathBuf::from("/cache/locks/repos/git/b64_abc.git.lock")
+        );
+        assert_eq!(
+            lock_path_for(cache_root, &cache_root.join("index")),
+            PathBuf::from("/cache/locks/index.lock")
+        );
+    }
+
+    #[test]
+    fn held_lock_times_out_with_lock_error_and_is_released_on_drop() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let cached_path = temp.path().join("index");
+
+        let held = acquire(temp.path(), &cached_path, "global index", Duration::ZERO)
+            .expect("first acquire");
+        let err = acquire(
+            temp.path(),
+            &cached_path,
+            "global index",
+            Duration::from_millis(150),
+        )
+        .expect_err("second acquire should time out");
+        let lock_err = err
+            .downcast_ref::<PkgrepError>()
+            .expect("lock timeout error");
+        assert!(matches!(lock_err, PkgrepError::LockTimeout { .. }));
+        assert!(err.to_string().contains("global index"));
+
+        drop(held);
+        acquire(temp.path(), &cached_path, "global index", Duration::ZERO)
+            .expect("acquire after release");
+    }
+}
@@ -13,6 +13,11 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Resolve only from cached registry metadata, existing git mirrors, and
+    /// manifest aliases, without touching the network.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -26,6 +31,11 @@ pub enum Command {
         #[arg(long)]
         fallback_repo_head: bool,
 
+        /// When pulling from project lockfiles, also resolve entries without git
+        /// source hints through their package registries at the locked version.
+        #[arg(long, conflicts_with = "dep_specs")]
+        resolve_registry: bool,
+
         /// Dependency spec(s), for example:
         /// git:https://github.com/org/repo.git
         /// git:https://github.com/org/repo.git@<rev>
@@ -61,6 +71,33 @@ pub enum Command {
         json: bool,
     },
 
+    /// Search linked dependency sources with a regular expression.
+    Grep {
+        /// Regular expression to search for.
+        pattern: String,
+
+        /// Limit the search to these linked dependency specs (defaults to all).
+        dep_specs: Vec<String>,
+
+        /// Match case-insensitively.
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
+        /// Output matches as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare a linked registry dependency with its published package artifact.
+    Verify {
+        /// Linked registry dependency spec (npm, pypi, or crates).
+        dep_spec: String,
+
+        /// Output the verification report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Initialize project-local pkgrep integration files.
     Init,
 
@@ -157,6 +194,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_grep_with_pattern_dep_specs_and_flags() {
+        let cli = Cli::try_parse_from([
+            "pkgrep",
+            "grep",
+            "-i",
+            "fn parse",
+            "npm:zod",
+            "crates:serde",
+            "--json",
+        ])
+        .expect("parse");
+        match cli.command {
+            Command::Grep {
+                pattern,
+                dep_specs,
+                ignore_case,
+                json,
+            } => {
+                assert_eq!(pattern, "fn parse");
+                assert_eq!(
+                    dep_specs,
+                    vec![String::from("npm:zod"), String::from("crates:serde")]
+                );
+                assert!(ignore_case);
+                assert!(json);
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn grep_requires_pattern() {
+        let result = Cli::try_parse_from(["pkgrep", "grep"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_verify_with_json_flag() {
+        let cli =
+            Cli::try_parse_from(["pkgrep", "verify", "npm:zod@3.23.8", "--json"]).expect("parse");
+        match cli.command {
+            Command::Verify { dep_spec, json } => {
+                assert_eq!(dep_spec, "npm:zod@3.23.8");
+                assert!(json);
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
     #[test]
     fn remove_requires_dep_spec() {
         let result = Cli::try_parse_from(["pkgrep", "remove", "--yes"]);
@@ -169,6 +256,13 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parses_global_offline_flag_after_subcommand() {
+        let cli =
+            Cli::try_parse_from(["pkgrep", "pull", "--offline", "npm:zod@3.23.8"]).expect("parse");
+        assert!(cli.offline);
+    }
+
     #[test]
     fn parses_verbose_flag() {
         let cli = Cli::try_parse_from(["pkgrep", "--verbose", "pull"]).expect("parse");
@@ -182,15 +276,39 @@ mod tests {
         match cli.command {
             Command::Pull {
                 fallback_repo_head,
+                resolve_registry,
                 dep_specs,
             } => {
                 assert!(fallback_repo_head);
+                assert!(!resolve_registry);
                 assert_eq!(dep_specs, vec![String::from("npm:zod")]);
             }
             _ => panic!("unexpected command"),
         }
     }
 
+    #[test]
+    fn parses_pull_with_resolve_registry_flag() {
+        let cli = Cli::try_parse_from(["pkgrep", "pull", "--resolve-registry"]).expect("parse");
+        match cli.command {
+            Command::Pull {
+                resolve_registry,
+                dep_specs,
+                ..
+            } => {
+                assert!(resolve_registry);
+                assert!(dep_specs.is_empty());
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn rejects_resolve_registry_with_explicit_dep_specs() {
+        let result = Cli::try_parse_from(["pkgrep", "pull", "--resolve-registry", "npm:zod"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn parses_path_command() {
         let cli = Cli::try_parse_from(["pkgrep", "path", "git:https://example.com/repo.git@v1"])
@@ -1,12 +1,15 @@
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use tracing::{info, warn};
 
+use crate::cache_lock;
 use crate::config::Config;
 use crate::depspec;
 use crate::index;
+use crate::source;
 
 pub(super) fn run_cache_clean(cwd: &Path, config: &Config, yes: bool) -> anyhow::Result<()> {
     let cache_dir = if config.cache_dir.is_absolute() {
@@ -66,25 +69,36 @@ pub(super) fn run_cache_prune(cwd: &Path, config: &Config, yes: bool) -> anyhow:
 
     info!(cache_dir = %cache_root.display(), dry_run = !yes, "cache prune requested");
 
-    let reconcile = index::reconcile_global_index(&cache_root).with_context(|| {
-        format!(
-            "failed to reconcile global ref index under {}",
-            cache_root.display()
-        )
-    })?;
+    let reconcile = index::reconcile_global_index(&cache_root, config.lock_timeout())
+        .with_context(|| {
+            format!(
+                "failed to reconcile global ref index under {}",
+                cache_root.display()
+            )
+        })?;
 
     let checkout_candidates =
         collect_prunable_checkouts(&cache_root.join("sources"), &reconcile.live_cache_keys)?;
-    let mirror_candidates =
+    let mut mirror_candidates =
         collect_prunable_mirrors(&cache_root.join("repos"), &reconcile.live_mirror_refs)?;
 
+    // Checkouts borrow objects from their mirror, so a mirror stays while any
+    // checkout that survives this prune still points at it.
+    let shared_mirrors =
+        collect_mirrors_shared_with_checkouts(&cache_root.join("sources"), &checkout_candidates)?;
+    let before_shared_filter = mirror_candidates.len();
+    mirror_candidates
+        .retain(|candidate| !shared_mirrors.contains(&canonical_path(&candidate.path)));
+    let shared_mirrors_retained = before_shared_filter - mirror_candidates.len();
+
     println!(
-        "Prune scan: stale_project_refs_removed={} stale_index_entries_removed={} index_updated={} checkout_candidates={} mirror_candidates={}",
+        "Prune scan: stale_project_refs_removed={} stale_index_entries_removed={} index_updated={} checkout_candidates={} mirror_candidates={} shared_mirrors_retained={}",
         reconcile.stale_project_references_removed,
         reconcile.empty_entries_removed,
         reconcile.index_updated,
         checkout_candidates.len(),
-        mirror_candidates.len()
+        mirror_candidates.len(),
+        shared_mirrors_retained
     );
 
     for candidate in &checkout_candidates {
@@ -112,6 +126,12 @@ pub(super) fn run_cache_prune(cwd: &Path, config: &Config, yes: bool) -> anyhow:
 
     let mut removed_checkouts = 0usize;
     for candidate in &checkout_candidates {
+        let _checkout_lock = cache_lock::acquire(
+            &cache_root,
+            &candidate.path,
+            "cache checkout",
+            config.lock_timeout(),
+        )?;
         match fs::remove_dir_all(&candidate.path) {
             Ok(()) => removed_checkouts += 1,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
@@ -128,6 +148,12 @@ pub(super) fn run_cache_prune(cwd: &Path, config: &Config, yes: bool) -> anyhow:
 
     let mut removed_mirrors = 0usize;
     for candidate in &mirror_candidates {
+        let _mirror_lock = cache_lock::acquire(
+            &cache_root,
+            &candidate.path,
+            "git mirror",
+            config.lock_timeout(),
+        )?;
         match fs::remove_dir_all(&candidate.path) {
             Ok(()) => removed_mirrors += 1,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
@@ -155,7 +181,7 @@ pub(super) fn run_cache_prune(cwd: &Path, config: &Config, yes: bool) -> anyhow:
 
 fn collect_prunable_checkouts(
     sources_root: &Path,
-    live_cache_keys: &std::collections::BTreeSet<String>,
+    live_cache_keys: &BTreeSet<String>,
 ) -> anyhow::Result<Vec<PrunableCheckout>> {
     let mut checkout_paths = Vec::new();
     collect_checkout_dirs(sources_root, &mut checkout_paths)?;
@@ -196,7 +222,7 @@ fn collect_checkout_dirs(root: &Path, out: &mut Vec<PathBuf>) -> anyhow::Result<
             continue;
         }
 
-        if path.join(".git").exists() {
+        if path.join(".git").exists() || source::is_archive_checkout(&path) {
             out.push(path);
             continue;
         }
@@ -207,6 +233,54 @@ fn collect_checkout_dirs(root: &Path, out: &mut Vec<PathBuf>) -> anyhow::Result<
     Ok(())
 }
 
+fn collect_mirrors_shared_with_checkouts(
+    sources_root: &Path,
+    checkout_candidates: &[PrunableCheckout],
+) -> anyhow::Result<BTreeSet<PathBuf>> {
+    let mut checkout_paths = Vec::new();
+    collect_checkout_dirs(sources_root, &mut checkout_paths)?;
+    let pruned_checkouts = checkout_candidates
+        .iter()
+        .map(|candidate| candidate.path.as_path())
+        .collect::<BTreeSet<_>>();
+
+    let mut shared_mirrors = BTreeSet::new();
+    for checkout_path in checkout_paths {
+        if pruned_checkouts.contains(checkout_path.as_path()) {
+            continue;
+        }
+
+        let alternates_path = source::checkout_alternates_path(&checkout_path);
+        let raw = match fs::read_to_string(&alternates_path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!(
+                        "failed to read object alternates file {}",
+                        alternates_path.display()
+                    )
+                });
+            }
+        };
+
+        for line in raw.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(mirror_path) = Path::new(line).parent() {
+                shared_mirrors.insert(canonical_path(mirror_path));
+            }
+        }
+    }
+
+    Ok(shared_mirrors)
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
 fn checkout_path_to_cache_key(sources_root: &Path, checkout_path: &Path) -> Option<String> {
     let relative = checkout_path.strip_prefix(sources_root).ok()?;
     let mut parts = Vec::new();
@@ -221,7 +295,7 @@ fn checkout_path_to_cache_key(sources_root: &Path, checkout_path: &Path) -> Opti
 
 fn collect_prunable_mirrors(
     repos_root: &Path,
-    live_mirror_refs: &std::collections::BTreeSet<index::MirrorRef>,
+    live_mirror_refs: &BTreeSet<index::MirrorRef>,
 ) -> anyhow::Result<Vec<PrunableMirror>> {
     let mut candidates = Vec::new();
     if !repos_root.exists() {
@@ -329,3 +403,65 @@ fn describe_mirror_candidate(candidate: &PrunableMirror) -> String {
         .unwrap_or_else(|| candidate.normalized_locator.clone());
     format!("{}:{}", candidate.ecosystem, locator)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_checkout_with_alternates(checkout_path: &Path, mirror_path: &Path) {
+        let alternates_path = source::checkout_alternates_path(checkout_path);
+        fs::create_dir_all(alternates_path.parent().expect("alternates parent"))
+            .expect("create object info dir");
+        fs::write(
+            &alternates_path,
+            format!("{}\n", mirror_path.join("objects").display()),
+        )
+        .expect("write alternates");
+    }
+
+    #[test]
+    fn mirrors_used_by_retained_checkouts_are_shared() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let sources_root = temp.path().join("sources");
+        let kept_mirror = temp.path().join("repos/npm/b64_kept.git");
+        let pruned_mirror = temp.path().join("repos/npm/b64_pruned.git");
+        fs::create_dir_all(&kept_mirror).expect("create kept mirror");
+        fs::create_dir_all(&pruned_mirror).expect("create pruned mirror");
+
+        let kept_checkout = sources_root.join("npm/b64_kept/1.0.0/abc");
+        let pruned_checkout = sources_root.join("npm/b64_pruned/1.0.0/def");
+        write_checkout_with_alternates(&kept_checkout, &kept_mirror);
+        write_checkout_with_alternates(&pruned_checkout, &pruned_mirror);
+
+        let shared = collect_mirrors_shared_with_checkouts(
+            &sources_root,
+            &[PrunableCheckout {
+                path: pruned_checkout,
+                cache_key: String::from("npm/b64_pruned/1.0.0/def"),
+            }],
+        )
+        .expect("collect shared mirrors");
+
+        assert!(shared.contains(&canonical_path(&kept_mirror)));
+        assert!(!shared.contains(&canonical_path(&pruned_mirror)));
+    }
+
+    #[test]
+    fn unpacked_archives_are_prune_candidates_without_descending_into_them() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let sources_root = temp.path().join("sources");
+        let archive = sources_root.join("npm/b64_bGVmdC1wYWQ/1.3.0/archive-sha256-abc");
+        fs::create_dir_all(archive.join("lib")).expect("create archive dir");
+        fs::write(archive.join("lib").join("index.js"), "").expect("write");
+
+        let candidates =
+            collect_prunable_checkouts(&sources_root, &BTreeSet::new()).expect("collect");
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, archive);
+        assert_eq!(
+            candidates[0].cache_key,
+            "npm/b64_bGVmdC1wYWQ/1.3.0/archive-sha256-abc"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use ignore::WalkBuilder;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::depspec::{self, SourceKind};
+use crate::index::{self, ProjectLinkSummary};
+use crate::worker_pool;
+
+// Minified bundles can put a whole package on one line; print a window around
+// the match instead of the full line.
+const MAX_LINE_CHARS: usize = 400;
+const LINE_CONTEXT_BEFORE_MATCH: usize = 80;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct GrepMatch {
+    dep_spec: String,
+    path: String,
+    line_number: usize,
+    line: String,
+}
+
+pub(super) fn run_grep(
+    cwd: &Path,
+    config: &Config,
+    pattern: &str,
+    dep_specs: Vec<String>,
+    ignore_case: bool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .with_context(|| format!("invalid grep pattern '{}'", pattern))?;
+    let links = select_links(cwd, &dep_specs)?;
+
+    info!(
+        pattern = pattern,
+        dep_spec_count = dep_specs.len(),
+        searched_links = links.len(),
+        "grep requested"
+    );
+
+    let mut matches_by_dep = BTreeMap::new();
+    let mut first_error = None;
+    worker_pool::run_bounded(
+        links,
+        config.worker_pool_size,
+        |link| {
+            let result = search_link(&link, &regex);
+            (link.dep_spec, result)
+        },
+        |(dep_spec, result)| match result {
+            Ok(matches) => {
+                matches_by_dep.insert(dep_spec, matches);
+                true
+            }
+            Err(err) => {
+                first_error.get_or_insert(err);
+                false
+            }
+        },
+    );
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    let matches = matches_by_dep.into_values().flatten().collect::<Vec<_>>();
+
+    if json {
+        let payload = serde_json::json!({
+            "pattern": pattern,
+            "matches": matches,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload)
+                .map_err(|err| anyhow::anyhow!("failed to serialize grep output: {err}"))?
+        );
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!("No matches for '{}' in linked dependencies", pattern);
+        return Ok(());
+    }
+
+    for grep_match in matches {
+        println!(
+            "{}:{}:{}:{}",
+            grep_match.dep_spec, grep_match.path, grep_match.line_number, grep_match.line
+        );
+    }
+
+    Ok(())
+}
+
+fn select_links(cwd: &Path, dep_specs: &[String]) -> anyhow::Result<Vec<ProjectLinkSummary>> {
+    let links = index::list_project_links(cwd)?;
+    if links.is_empty() {
+        anyhow::bail!(
+            "no linked dependencies found in {}; run 'pkgrep pull' first",
+            cwd.display()
+        );
+    }
+    if dep_specs.is_empty() {
+        return Ok(links);
+    }
+
+    let parsed_specs = super::parse_dep_specs(dep_specs)?;
+    let mut selected = BTreeMap::new();
+    for (dep_spec, spec) in dep_specs.iter().zip(parsed_specs) {
+        let link_paths = match &spec.source_kind {
+            SourceKind::Git {
+                subdirectory: Some(_),
+                ..
+            } => index::find_dep_spec_link(cwd, dep_spec)?
+                .into_iter()
+                .map(|link_match| link_match.link_path)
+                .collect(),
+            SourceKind::Git {
+                url,
+                requested_revision: Some(requested_revision),
+                subdirectory: None,
+            } => vec![cwd.join(depspec::link_path(&spec.ecosystem, url, requested_revision))],
+            SourceKind::Git {
+                url,
+                requested_revision: None,
+                subdirectory: None,
+            } => index::find_git_link_matches(cwd, dep_spec, url)?
+                .into_iter()
+                .map(|link_match| link_match.link_path)
+                .collect(),
+            SourceKind::Registry => index::find_registry_link_matches(
+                cwd,
+                dep_spec,
+                &spec.ecosystem,
+                &spec.locator,
+                spec.version.as_deref(),
+            )?
+            .into_iter()
+            .map(|link_match| link_match.link_path)
+            .collect(),
+        };
+
+        let mut matched_any = false;
+        for link in &links {
+            if link_paths.contains(&link.link_path) {
+                matched_any = true;
+                selected
+                    .entry(link.dep_spec.clone())
+                    .or_insert_with(|| link.clone());
+            }
+        }
+        if !matched_any {
+            anyhow::bail!("dependency is not linked in this project: {}", dep_spec);
+        }
+    }
+
+    Ok(selected.into_values().collect())
+}
+
+fn search_link(link: &ProjectLinkSummary, regex: &Regex) -> anyhow::Result<Vec<GrepMatch>> {
+    let root = fs::canonicalize(&link.link_path).with_context(|| {
+        format!(
+            "failed to resolve linked checkout {}",
+            link.link_path.display()
+        )
+    })?;
+
+    let mut files = Vec::new();
+    // Only the dependency's own ignore files apply: archive checkouts have no `.git`,
+    // and the user's global excludes or ignore files above the cache say nothing
+    // about dependency sources.
+    let walker = WalkBuilder::new(&root)
+        .hidden(false)
+        .require_git(false)
+        .git_global(false)
+        .git_exclude(false)
+        .parents(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker {
+        let entry =
+            entry.with_context(|| format!("failed to walk linked checkout {}", root.display()))?;
+        if entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            files.push(entry.into_path());
+        }
+    }
+    files.sort();
+
+    let mut matches = Vec::new();
+    for file in files {
+        let bytes = match fs::read(&file) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!(path = %file.display(), error = %err, "skipping unreadable file");
+                continue;
+            }
+        };
+        if bytes.iter().take(BINARY_SNIFF_BYTES).any(|byte| *byte == 0) {
+            continue;
+        }
+
+        let relative_path = relative_display_path(&root, &file);
+        let text = String::from_utf8_lossy(&bytes);
+        for (index, line) in text.lines().enumerate() {
+            let Some(found) = regex.find(line) else {
+                continue;
+            };
+            matches.push(GrepMatch {
+                dep_spec: link.dep_spec.clone(),
+                path: relative_path.clone(),
+                line_number: index + 1,
+                line: display_line(line, found.start()),
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+fn relative_display_path(root: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn display_line(line: &str, match_start: usize) -> String {
+    if line.chars().count() <= MAX_LINE_CHARS {
+        return line.to_string();
+    }
+
+    let prefix_chars = line[..match_start].chars().count();
+    let window_start = prefix_chars.saturating_sub(LINE_CONTEXT_BEFORE_MATCH);
+    let window = line
+        .chars()
+        .skip(window_start)
+        .take(MAX_LINE_CHARS)
+        .collect::<String>();
+    let leading = if window_start > 0 { "..." } else { "" };
+    let trailing = if window_start + MAX_LINE_CHARS < line.chars().count() {
+        "..."
+    } else {
+        ""
+    };
+    format!("{leading}{window}{trailing}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(root: &Path) -> ProjectLinkSummary {
+        ProjectLinkSummary {
+            dep_spec: String::from("git:https://example.com/repo.git@abc"),
+            link_path: root.to_path_buf(),
+            cache_key: String::from("git/b64_x/abc/abc"),
+            aliases: Vec::new(),
+            subdirectory: None,
+            release_tag: None,
+            verification: None,
+        }
+    }
+
+    #[test]
+    fn search_applies_only_the_checkout_ignore_files_of_archive_checkouts() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        fs::write(temp.path().join(".gitignore"), "*.rs\n").expect("write parent gitignore");
+        let root = temp.path().join("archive-checkout");
+        fs::create_dir_all(root.join("src")).expect("create src");
+        fs::write(root.join(".gitignore"), "generated.js\n").expect("write gitignore");
+        fs::write(root.join("generated.js"), "needle in generated\n").expect("write");
+        fs::write(root.join("src").join("lib.rs"), "let needle = 1;\n").expect("write");
+
+        let regex = Regex::new("needle").expect("regex");
+        let matches = search_link(&summary(&root), &regex).expect("search");
+
+        assert_eq!(
+            matches
+                .iter()
+                .map(|found| found.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn search_skips_git_dir_gitignored_and_binary_files() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let root = temp.path().join("checkout");
+        fs::create_dir_all(root.join(".git")).expect("create .git");
+        fs::create_dir_all(root.join("src")).expect("create src");
+        fs::create_dir_all(root.join("dist")).expect("create dist");
+        fs::write(root.join(".git").join("config"), "needle in git dir\n").expect("write");
+        fs::write(root.join(".gitignore"), "dist/\n").expect("write gitignore");
+        fs::write(root.join("dist").join("bundle.js"), "needle in dist\n").expect("write");
+        fs::write(
+            root.join("src").join("lib.rs"),
+            "fn a() {}\nlet needle = 1;\n",
+        )
+        .expect("write");
+        fs::write(root.join("blob.bin"), b"needle\0binary").expect("write");
+
+        let regex = Regex::new("needle").expect("regex");
+        let matches = search_link(&summary(&root), &regex).expect("search");
+
+        assert_eq!(
+            matches,
+            vec![GrepMatch {
+                dep_spec: String::from("git:https://example.com/repo.git@abc"),
+                path: String::from("src/lib.rs"),
+                line_number: 2,
+                line: String::from("let needle = 1;"),
+            }]
+        );
+    }
+
+    #[test]
+    fn long_lines_are_windowed_around_the_match() {
+        let line = format!("{}needle{}", "a".repeat(1000), "b".repeat(1000));
+        let start = line.find("needle").expect("needle");
+
+        let shown = display_line(&line, start);
+
+        assert!(shown.starts_with("..."));
+        assert!(shown.ends_with("..."));
+        assert!(shown.contains("needle"));
+        assert_eq!(shown.chars().count(), MAX_LINE_CHARS + 6);
+    }
+}
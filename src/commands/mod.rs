@@ -1,4 +1,5 @@
 mod cache;
+mod grep;
 mod init;
 mod list;
 mod path;
@@ -6,6 +7,7 @@ mod pull;
 mod remove;
 mod self_update;
 mod skill;
+mod verify;
 
 use std::path::Path;
 
@@ -17,11 +19,19 @@ pub fn execute(cwd: &Path, config: &Config, command: Command) -> anyhow::Result<
     match command {
         Command::Pull {
             fallback_repo_head,
+            resolve_registry,
             dep_specs,
-        } => pull::run_pull(cwd, config, dep_specs, fallback_repo_head),
+        } => pull::run_pull(cwd, config, dep_specs, fallback_repo_head, resolve_registry),
         Command::Remove { dep_specs, yes } => remove::run_remove(cwd, config, dep_specs, yes),
         Command::Path { dep_spec } => path::run_path(cwd, dep_spec),
         Command::List { json } => list::run_list(cwd, json),
+        Command::Grep {
+            pattern,
+            dep_specs,
+            ignore_case,
+            json,
+        } => grep::run_grep(cwd, config, &pattern, dep_specs, ignore_case, json),
+        Command::Verify { dep_spec, json } => verify::run_verify(cwd, config, dep_spec, json),
         Command::Init => init::run_init(cwd),
         Command::Cache { command } => match command {
             CacheCommand::Clean { yes } => cache::run_cache_clean(cwd, config, yes),
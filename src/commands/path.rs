@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::depspec::{self, Ecosystem, SourceKind};
+use crate::depspec::{self, SourceKind};
 use crate::index;
 
 pub(super) fn run_path(cwd: &Path, dep_spec: String) -> anyhow::Result<()> {
@@ -10,14 +10,25 @@ pub(super) fn run_path(cwd: &Path, dep_spec: String) -> anyhow::Result<()> {
         .next()
         .ok_or_else(|| anyhow::anyhow!("missing dependency spec"))?;
 
-    let (locator, requested_revision) = match spec.source_kind {
+    let (locator, requested_revision, subdirectory) = match spec.source_kind {
         SourceKind::Git {
             url,
             requested_revision,
-        } => (url, requested_revision),
+            subdirectory,
+        } => (url, requested_revision, subdirectory),
         SourceKind::Registry => return resolve_registry_path(cwd, &dep_spec, &spec),
     };
 
+    // Monorepo packages link under their package name, so only the manifest knows
+    // where a `//<dir>` spec points.
+    if subdirectory.is_some() {
+        let Some(link_match) = index::find_dep_spec_link(cwd, &dep_spec)? else {
+            anyhow::bail!("dependency is not linked in this project: {}", dep_spec);
+        };
+        println!("{}", link_match.link_path.display());
+        return Ok(());
+    }
+
     let Some(requested_revision) = requested_revision else {
         return resolve_git_path_without_revision(cwd, &dep_spec, &locator);
     };
@@ -73,12 +84,10 @@ fn resolve_registry_path(
     dep_spec: &str,
     spec: &crate::depspec::DepSpec,
 ) -> anyhow::Result<()> {
-    if !matches!(
-        spec.ecosystem,
-        Ecosystem::Npm | Ecosystem::Pypi | Ecosystem::Crates
-    ) {
+    if !spec.ecosystem.is_registry() {
         anyhow::bail!(
-            "path supports npm/pypi/crates registry specs only; use 'npm:<name>[@<version>]', 'pypi:<name>[@<version>]', or 'crates:<name>[@<version>]'"
+            "path supports registry package specs only; use '<ecosystem>:<name>[@<version>]' with one of: {}",
+            crate::depspec::REGISTRY_SCHEMES.join(", ")
         );
     }
 
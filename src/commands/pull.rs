@@ -5,20 +5,25 @@ use anyhow::Context;
 use tracing::{info, warn};
 
 use crate::config::Config;
-use crate::depspec::{self, Ecosystem, SourceKind};
+use crate::depspec::{self, Ecosystem, SourceKind, VersionRequest};
+use crate::error::PkgrepError;
 use crate::index;
 use crate::installed_version;
 use crate::providers;
 use crate::registry_resolver;
 use crate::registry_resolver::RequestedRevisionSource;
 use crate::source;
+use crate::worker_pool;
 
 #[derive(Clone, Debug)]
 pub(super) struct PullTargetResolution {
-    pub target: source::GitPullTarget,
+    pub target: source::PullTarget,
     pub aliases: BTreeSet<String>,
     pub registry_refs: BTreeSet<index::RegistrySpecRef>,
+    /// Published artifact to unpack when a registry package's git revision cannot be fetched.
+    pub archive_fallback: Option<source::ArchivePullTarget>,
     pub repo_head_fallback_context: Option<RepoHeadFallbackContext>,
+    pub lockfile_registry_specs: BTreeSet<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -27,6 +32,35 @@ pub(super) struct PullResolution {
     pub discovered_lockfiles: usize,
     pub discovered_dependencies: usize,
     pub skipped_non_git_dependencies: usize,
+    pub registry_failures: Vec<RegistryPullReportEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct RegistryPullReportEntry {
+    pub dep_spec: String,
+    pub outcome: RegistryPullOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum RegistryPullOutcome {
+    Resolved { source: String },
+    FellBack { source: String },
+    FromArchive { source: String },
+    Failed { reason: String },
+}
+
+/// An explicit spec in command-line order: either already resolved locally, or a
+/// registry spec waiting for its lookup on the worker pool.
+enum PendingPullTarget {
+    Resolved(PullTargetResolution),
+    Registry(PendingRegistrySpec),
+}
+
+struct PendingRegistrySpec {
+    original_spec: String,
+    spec: depspec::DepSpec,
+    spec_label: String,
+    version_request: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -37,16 +71,19 @@ pub(super) struct RepoHeadFallbackContext {
 
 pub(super) fn resolve_pull_resolution(
     cwd: &Path,
+    config: &Config,
     dep_specs: &[String],
+    resolve_registry: bool,
 ) -> anyhow::Result<PullResolution> {
     if dep_specs.is_empty() {
-        resolve_pull_targets_from_project(cwd)
+        resolve_pull_targets_from_project(cwd, config, resolve_registry)
     } else {
         Ok(PullResolution {
-            targets: resolve_pull_targets_from_specs(cwd, dep_specs)?,
+            targets: resolve_pull_targets_from_specs(cwd, config, dep_specs)?,
             discovered_lockfiles: 0,
             discovered_dependencies: 0,
             skipped_non_git_dependencies: 0,
+            registry_failures: Vec::new(),
         })
     }
 }
@@ -56,8 +93,12 @@ pub(super) fn run_pull(
     config: &Config,
     dep_specs: Vec<String>,
     fallback_repo_head: bool,
+    resolve_registry: bool,
 ) -> anyhow::Result<()> {
-    let resolved = resolve_pull_resolution(cwd, &dep_specs)?;
+    let resolved = resolve_pull_resolution(cwd, config, &dep_specs, resolve_registry)?;
+    let report_registry_outcomes = dep_specs.is_empty() && resolve_registry;
+    let mut registry_report = resolved.registry_failures.clone();
+    let mut explicit_archive_fallbacks = 0usize;
 
     if dep_specs.is_empty() {
         if resolved.discovered_lockfiles == 0 {
@@ -79,30 +120,38 @@ pub(super) fn run_pull(
                 skipped_non_git_dependencies = resolved.skipped_non_git_dependencies,
                 "supported lockfiles were found, but no git-backed dependencies were available"
             );
-            println!(
-                "No-op: detected {} dependency entries from {} lockfile(s), but none had git source hints (git-only mode).",
-                resolved.discovered_dependencies, resolved.discovered_lockfiles
-            );
+            if report_registry_outcomes {
+                print_registry_report(&registry_report);
+                println!(
+                    "No-op: detected {} dependency entries from {} lockfile(s), but none could be resolved to a git source.",
+                    resolved.discovered_dependencies, resolved.discovered_lockfiles
+                );
+            } else {
+                println!(
+                    "No-op: detected {} dependency entries from {} lockfile(s), but none had git source hints (git-only mode; pass --resolve-registry to resolve registry entries).",
+                    resolved.discovered_dependencies, resolved.discovered_lockfiles
+                );
+            }
             return Ok(());
         }
     }
 
     if let Some(first) = resolved.targets.first() {
-        let version_for_key = first.target.requested_revision.as_str();
+        let version_for_key = first.target.revision();
         let preview_cache_key = depspec::cache_key(
-            &first.target.ecosystem,
-            &first.target.locator,
+            first.target.ecosystem(),
+            first.target.locator(),
             version_for_key,
             "source-fingerprint-pending",
         );
         let preview_link_path = depspec::link_path(
-            &first.target.ecosystem,
-            &first.target.locator,
+            first.target.ecosystem(),
+            first.target.locator(),
             version_for_key,
         );
         info!(
-            first_dep_ecosystem = first.target.ecosystem.as_str(),
-            first_dep_locator = %first.target.locator,
+            first_dep_ecosystem = first.target.ecosystem().as_str(),
+            first_dep_locator = %first.target.locator(),
             first_dep_version = version_for_key,
             first_dep_cache_key_preview = %preview_cache_key,
             first_dep_link_path_preview = %preview_link_path.display(),
@@ -116,6 +165,7 @@ pub(super) fn run_pull(
         discovered_lockfiles = resolved.discovered_lockfiles,
         discovered_dependencies = resolved.discovered_dependencies,
         skipped_non_git_dependencies = resolved.skipped_non_git_dependencies,
+        worker_pool_size = config.worker_pool_size,
         "pull requested"
     );
 
@@ -123,53 +173,141 @@ pub(super) fn run_pull(
 
     let mut fetched_from_git = 0usize;
     let total_targets = resolved.targets.len();
+    let mut completed_targets = 0usize;
+    let mut first_error = None;
+
+    // Targets that share a mirror run sequentially on one worker so concurrent
+    // fetches never race on the same bare repository.
+    worker_pool::run_bounded(
+        group_targets_by_mirror(&resolved.targets),
+        config.worker_pool_size,
+        |group| {
+            group
+                .into_iter()
+                .map(|index| {
+                    let mut log = Vec::new();
+                    let result = resolve_materialized_pull_target(
+                        cwd,
+                        config,
+                        &resolved.targets[index],
+                        fallback_repo_head,
+                        &mut log,
+                    );
+                    TargetPullOutcome { index, log, result }
+                })
+                .collect::<Vec<_>>()
+        },
+        |outcomes| {
+            for outcome in outcomes {
+                completed_targets += 1;
+                let target_resolution = &resolved.targets[outcome.index];
+                let target = &target_resolution.target;
+                println!(
+                    "[{}/{}] pull {}",
+                    completed_targets,
+                    total_targets,
+                    target.describe()
+                );
+                for line in &outcome.log {
+                    println!("{line}");
+                }
 
-    for (index, target_resolution) in resolved.targets.iter().enumerate() {
-        let target = &target_resolution.target;
-        println!(
-            "[{}/{}] pull {}@{}",
-            index + 1,
-            total_targets,
-            target.git_url,
-            target.requested_revision
-        );
+                let (effective_target, materialized) = match outcome.result {
+                    Ok(result) => result,
+                    Err(err) if !target_resolution.lockfile_registry_specs.is_empty() => {
+                        println!("  -> failed: {err:#}");
+                        record_registry_outcomes(
+                            &mut registry_report,
+                            &target_resolution.lockfile_registry_specs,
+                            RegistryPullOutcome::Failed {
+                                reason: format!("{err:#}"),
+                            },
+                        );
+                        continue;
+                    }
+                    Err(err) => {
+                        first_error.get_or_insert(err);
+                        continue;
+                    }
+                };
+                if materialized.git_fetch_performed {
+                    fetched_from_git += 1;
+                }
+                if !target_resolution.lockfile_registry_specs.is_empty() {
+                    let source = effective_target.describe();
+                    let outcome = match &effective_target {
+                        source::PullTarget::Archive(_) => {
+                            RegistryPullOutcome::FromArchive { source }
+                        }
+                        source::PullTarget::Git(_)
+                            if effective_target.revision() == target.revision() =>
+                        {
+                            RegistryPullOutcome::Resolved { source }
+                        }
+                        source::PullTarget::Git(_) => RegistryPullOutcome::FellBack { source },
+                    };
+                    record_registry_outcomes(
+                        &mut registry_report,
+                        &target_resolution.lockfile_registry_specs,
+                        outcome,
+                    );
+                } else if let (source::PullTarget::Git(_), source::PullTarget::Archive(archive)) =
+                    (target, &effective_target)
+                {
+                    registry_report.push(RegistryPullReportEntry {
+                        dep_spec: format!(
+                            "{}:{}@{}",
+                            archive.ecosystem.as_str(),
+                            archive.package_name,
+                            archive.package_version
+                        ),
+                        outcome: RegistryPullOutcome::FromArchive {
+                            source: effective_target.describe(),
+                        },
+                    });
+                    explicit_archive_fallbacks += 1;
+                }
 
-        let (effective_target, materialized) =
-            resolve_materialized_pull_target(cwd, config, target_resolution, fallback_repo_head)?;
-        if materialized.git_fetch_performed {
-            fetched_from_git += 1;
-        }
+                let link_metadata = index::LinkRecordMetadata {
+                    aliases: target_resolution.aliases.clone(),
+                    registry_refs: target_resolution.registry_refs.clone(),
+                };
 
-        let link_metadata = index::LinkRecordMetadata {
-            aliases: target_resolution.aliases.clone(),
-            registry_refs: target_resolution.registry_refs.clone(),
-        };
+                if let Err(err) = index::record_link_with_metadata(
+                    cwd,
+                    &cache_root,
+                    &effective_target,
+                    &materialized,
+                    &link_metadata,
+                    config.lock_timeout(),
+                ) {
+                    warn!(
+                        source = %effective_target.describe(),
+                        error = %err,
+                        "failed to update local index files after link"
+                    );
+                }
+                println!("  -> linked {}", materialized.project_link_path.display());
+
+                info!(
+                    source = %effective_target.describe(),
+                    source_fingerprint = %materialized.source_fingerprint,
+                    cache_key = %materialized.cache_key,
+                    checkout_path = %materialized.checkout_path.display(),
+                    link_path = %materialized.project_link_path.display(),
+                    "materialized source and linked into project"
+                );
+            }
+            first_error.is_none()
+        },
+    );
 
-        if let Err(err) = index::record_link_with_metadata(
-            cwd,
-            &cache_root,
-            &effective_target,
-            &materialized,
-            &link_metadata,
-        ) {
-            warn!(
-                git_url = %effective_target.git_url,
-                requested_revision = %effective_target.requested_revision,
-                error = %err,
-                "failed to update local index files after link"
-            );
-        }
-        println!("  -> linked {}", materialized.project_link_path.display());
+    if let Some(err) = first_error {
+        return Err(err);
+    }
 
-        info!(
-            git_url = %effective_target.git_url,
-            requested_revision = %effective_target.requested_revision,
-            source_fingerprint = %materialized.source_fingerprint,
-            cache_key = %materialized.cache_key,
-            checkout_path = %materialized.checkout_path.display(),
-            link_path = %materialized.project_link_path.display(),
-            "materialized git source and linked into project"
-        );
+    if report_registry_outcomes || explicit_archive_fallbacks > 0 {
+        print_registry_report(&registry_report);
     }
 
     println!(
@@ -181,28 +319,136 @@ pub(super) fn run_pull(
     Ok(())
 }
 
+struct TargetPullOutcome {
+    index: usize,
+    log: Vec<String>,
+    result: anyhow::Result<(source::PullTarget, source::MaterializedSource)>,
+}
+
+fn group_targets_by_mirror(targets: &[PullTargetResolution]) -> Vec<Vec<usize>> {
+    let mut group_by_mirror: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+
+    for (index, target_resolution) in targets.iter().enumerate() {
+        let key = match &target_resolution.target {
+            source::PullTarget::Git(target) => (target.ecosystem.as_str(), target.git_url.as_str()),
+            source::PullTarget::Archive(target) => {
+                (target.ecosystem.as_str(), target.package_name.as_str())
+            }
+        };
+        match group_by_mirror.get(&key) {
+            Some(group_index) => groups[*group_index].push(index),
+            None => {
+                group_by_mirror.insert(key, groups.len());
+                groups.push(vec![index]);
+            }
+        }
+    }
+
+    groups
+}
+
+fn record_registry_outcomes(
+    report: &mut Vec<RegistryPullReportEntry>,
+    dep_specs: &BTreeSet<String>,
+    outcome: RegistryPullOutcome,
+) {
+    for dep_spec in dep_specs {
+        report.push(RegistryPullReportEntry {
+            dep_spec: dep_spec.clone(),
+            outcome: outcome.clone(),
+        });
+    }
+}
+
+fn print_registry_report(report: &[RegistryPullReportEntry]) {
+    let mut entries = report.iter().collect::<Vec<_>>();
+    entries.sort_by(|lhs, rhs| lhs.dep_spec.cmp(&rhs.dep_spec));
+
+    let mut resolved = 0usize;
+    let mut fell_back = 0usize;
+    let mut from_archive = 0usize;
+    let mut failed = 0usize;
+
+    println!("Registry resolution report:");
+    for entry in entries {
+        match &entry.outcome {
+            RegistryPullOutcome::Resolved { source } => {
+                resolved += 1;
+                println!("  resolved {} -> {}", entry.dep_spec, source);
+            }
+            RegistryPullOutcome::FellBack { source } => {
+                fell_back += 1;
+                println!(
+                    "  fell back {} -> {} (repository default branch)",
+                    entry.dep_spec, source
+                );
+            }
+            RegistryPullOutcome::FromArchive { source } => {
+                from_archive += 1;
+                println!("  archive {} -> {}", entry.dep_spec, source);
+            }
+            RegistryPullOutcome::Failed { reason } => {
+                failed += 1;
+                println!("  failed {}: {}", entry.dep_spec, reason);
+            }
+        }
+    }
+    println!(
+        "Registry resolution: resolved={} fell_back={} failed={} from_archive={}",
+        resolved, fell_back, failed, from_archive
+    );
+}
+
 fn resolve_pull_targets_from_specs(
     cwd: &Path,
+    config: &Config,
     dep_specs: &[String],
 ) -> anyhow::Result<Vec<PullTargetResolution>> {
     let normalized_specs = normalize_explicit_dep_specs_for_pull(cwd, dep_specs)?;
     let parsed_specs = super::parse_dep_specs(&normalized_specs)?;
-    let mut targets = Vec::new();
+    let mut pending = Vec::new();
 
-    for (original_spec, spec) in normalized_specs.into_iter().zip(parsed_specs.into_iter()) {
+    for (original_spec, spec) in normalized_specs.into_iter().zip(parsed_specs) {
         match spec.source_kind {
             SourceKind::Git {
                 url,
                 requested_revision,
+                subdirectory,
             } => {
+                if requested_revision.is_none()
+                    && config.offline
+                    && let Some(linked) = offline_manifest_link(
+                        &original_spec,
+                        index::find_git_link_matches(cwd, &original_spec, &url)?,
+                    )
+                {
+                    println!("{linked}");
+                    continue;
+                }
+
+                let subdirectory = match subdirectory {
+                    Some(raw) => Some(
+                        registry_resolver::normalize_subdirectory(&raw).with_context(|| {
+                            format!("invalid git subdirectory '{raw}' in {original_spec}")
+                        })?,
+                    ),
+                    None => None,
+                };
+                // A `//<dir>` package links beside the repository, not over its root link.
+                let locator = match &subdirectory {
+                    Some(subdirectory) => format!("{url}//{subdirectory}"),
+                    None => url.clone(),
+                };
+
                 let mut aliases = BTreeSet::new();
                 aliases.insert(original_spec);
 
                 let requested_revision = match requested_revision {
                     Some(requested_revision) => requested_revision,
                     None => {
-                        let resolved =
-                            source::resolve_default_remote_revision(&url).with_context(|| {
+                        let resolved = source::resolve_default_remote_revision(config, &url)
+                            .with_context(|| {
                                 format!("failed to resolve default revision for {}", url)
                             })?;
                         println!(
@@ -213,70 +459,38 @@ fn resolve_pull_targets_from_specs(
                     }
                 };
 
-                targets.push(PullTargetResolution {
-                    target: source::GitPullTarget {
+                pending.push(PendingPullTarget::Resolved(PullTargetResolution {
+                    target: source::PullTarget::Git(source::GitPullTarget {
                         ecosystem: spec.ecosystem,
-                        locator: url.clone(),
+                        locator,
                         git_url: url,
                         requested_revision,
-                    },
+                        subdirectory,
+                        release_tag_package: None,
+                    }),
                     aliases,
                     registry_refs: BTreeSet::new(),
+                    archive_fallback: None,
                     repo_head_fallback_context: None,
-                });
+                    lockfile_registry_specs: BTreeSet::new(),
+                }));
+            }
+            SourceKind::Registry if spec.ecosystem == Ecosystem::Swift => {
+                pending.push(PendingPullTarget::Resolved(resolve_swift_pin_target(
+                    cwd,
+                    original_spec,
+                    &spec,
+                )?));
             }
             SourceKind::Registry => {
                 let mut spec = spec;
-                if spec.version.is_none() {
-                    match spec.ecosystem {
-                        Ecosystem::Npm => {
-                            if let Some(detected) =
-                                installed_version::detect_installed_npm_version(cwd, &spec.locator)?
-                            {
-                                println!(
-                                    "detected installed npm version for {}: {} (from {})",
-                                    spec.locator,
-                                    detected.version,
-                                    detected.source.as_str()
-                                );
-                                spec.version = Some(detected.version);
-                            } else {
-                                println!(
-                                    "no installed npm version detected for {}; falling back to registry latest",
-                                    spec.locator
-                                );
-                            }
-                        }
-                        Ecosystem::Crates => {
-                            if let Some(detected) =
-                                installed_version::detect_installed_crates_version(
-                                    cwd,
-                                    &spec.locator,
-                                )?
-                            {
-                                println!(
-                                    "detected installed crates version for {}: {} (from {})",
-                                    spec.locator,
-                                    detected.version,
-                                    detected.source.as_str()
-                                );
-                                spec.version = Some(detected.version);
-                            } else {
-                                println!(
-                                    "no installed crates version detected for {}; falling back to registry latest",
-                                    spec.locator
-                                );
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                if spec.ecosystem == Ecosystem::Pypi && spec.version.is_none() {
-                    if let Some(detected) =
-                        installed_version::detect_installed_pypi_version(cwd, &spec.locator)?
-                    {
+                if spec.version.is_none()
+                    && let Some((detect, fallback)) = installed_version_detector(&spec.ecosystem)
+                {
+                    if let Some(detected) = detect(cwd, &spec.locator)? {
                         println!(
-                            "detected installed pypi version for {}: {} (from {})",
+                            "detected installed {} version for {}: {} (from {})",
+                            spec.ecosystem.as_str(),
                             spec.locator,
                             detected.version,
                             detected.source.as_str()
@@ -284,8 +498,10 @@ fn resolve_pull_targets_from_specs(
                         spec.version = Some(detected.version);
                     } else {
                         println!(
-                            "no installed pypi version detected for {}; falling back to registry latest",
-                            spec.locator
+                            "no installed {} version detected for {}; falling back to {}",
+                            spec.ecosystem.as_str(),
+                            spec.locator,
+                            fallback
                         );
                     }
                 }
@@ -296,52 +512,227 @@ fn resolve_pull_targets_from_specs(
                     }
                     None => format!("{}:{}", spec.ecosystem.as_str(), spec.locator),
                 };
-                println!("resolving package metadata for {}", spec_label);
-                let resolved = registry_resolver::resolve_registry_spec(spec)?;
-                println!(
-                    "  -> resolved to {}@{} (package version {})",
-                    resolved.target.git_url,
-                    resolved.target.requested_revision,
-                    resolved.package_version
-                );
-
-                let mut aliases = BTreeSet::new();
-                aliases.insert(original_spec);
-                aliases.insert(format!(
-                    "{}:{}",
-                    resolved.target.ecosystem.as_str(),
-                    resolved.target.locator
-                ));
-                aliases.insert(format!(
-                    "{}:{}@{}",
-                    resolved.target.ecosystem.as_str(),
-                    resolved.target.locator,
-                    resolved.package_version
-                ));
-
-                let mut registry_refs = BTreeSet::new();
-                if let Some(registry_ref) = registry_ref(
-                    &resolved.target.ecosystem,
-                    &resolved.target.locator,
-                    Some(resolved.package_version.clone()),
-                ) {
-                    registry_refs.insert(registry_ref);
+                if config.offline
+                    && let Some(linked) = offline_manifest_link(
+                        &original_spec,
+                        index::find_registry_link_matches(
+                            cwd,
+                            &original_spec,
+                            &spec.ecosystem,
+                            &spec.locator,
+                            spec.version.as_deref(),
+                        )?,
+                    )
+                {
+                    println!("{linked}");
+                    continue;
                 }
-                let repo_head_fallback_context = repo_head_fallback_context(&resolved);
+                let version_request = match spec.version_request() {
+                    Some(VersionRequest::Range(request) | VersionRequest::DistTag(request)) => {
+                        Some(request)
+                    }
+                    Some(VersionRequest::Exact(_)) | None => None,
+                };
+                pending.push(PendingPullTarget::Registry(PendingRegistrySpec {
+                    original_spec,
+                    spec,
+                    spec_label,
+                    version_request,
+                }));
+            }
+        }
+    }
 
-                targets.push(PullTargetResolution {
-                    target: resolved.target,
-                    aliases,
-                    registry_refs,
-                    repo_head_fallback_context,
+    // Registry lookups run on the worker pool; their output and targets follow the
+    // order the specs were given in.
+    let registry_specs = pending
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pending)| match pending {
+            PendingPullTarget::Registry(registry) => Some((index, registry.spec.clone())),
+            PendingPullTarget::Resolved(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let mut registry_results = BTreeMap::new();
+    worker_pool::run_bounded(
+        registry_specs,
+        config.worker_pool_size,
+        |(index, spec)| {
+            (
+                index,
+                registry_resolver::resolve_registry_spec(cwd, config, spec),
+            )
+        },
+        |(index, result)| {
+            registry_results.insert(index, result);
+            true
+        },
+    );
+
+    let mut targets = Vec::new();
+    for (index, pending) in pending.into_iter().enumerate() {
+        let registry = match pending {
+            PendingPullTarget::Resolved(target) => {
+                targets.push(target);
+                continue;
+            }
+            PendingPullTarget::Registry(registry) => registry,
+        };
+        println!("resolving package metadata for {}", registry.spec_label);
+        let result = registry_results.remove(&index).with_context(|| {
+            format!(
+                "registry resolution for {} did not complete",
+                registry.spec_label
+            )
+        })?;
+        let resolved = match result {
+            Err(err) if config.offline => {
+                return Err(err).with_context(|| {
+                    format!(
+                        "offline mode: cannot resolve {} from cached registry metadata",
+                        registry.spec_label
+                    )
                 });
             }
-        }
+            resolved => resolved?,
+        };
+        println!(
+            "  -> resolved to {} (package version {})",
+            resolved.target.describe(),
+            resolved.package_version
+        );
+
+        targets.push(registry_target_resolution(
+            registry.original_spec,
+            resolved,
+            registry.version_request,
+        ));
     }
 
     Ok(deduplicate_pull_targets(targets))
 }
 
+type InstalledVersionDetector =
+    fn(&Path, &str) -> anyhow::Result<Option<installed_version::InstalledVersion>>;
+
+/// How a registry spec without a version finds the one the project installed, and
+/// what the registry picks when nothing is installed.
+fn installed_version_detector(
+    ecosystem: &Ecosystem,
+) -> Option<(InstalledVersionDetector, &'static str)> {
+    let detector: (InstalledVersionDetector, &'static str) = match ecosystem {
+        Ecosystem::Npm => (
+            installed_version::detect_installed_npm_version,
+            "registry latest",
+        ),
+        Ecosystem::Pypi => (
+            installed_version::detect_installed_pypi_version,
+            "registry latest",
+        ),
+        Ecosystem::Crates => (
+            installed_version::detect_installed_crates_version,
+            "registry latest",
+        ),
+        Ecosystem::Go => (
+            installed_version::detect_installed_go_version,
+            "the proxy latest",
+        ),
+        Ecosystem::Rubygems => (
+            installed_version::detect_installed_rubygems_version,
+            "registry latest",
+        ),
+        Ecosystem::Maven => (
+            installed_version::detect_installed_maven_version,
+            "the repository release version",
+        ),
+        Ecosystem::Packagist => (
+            installed_version::detect_installed_packagist_version,
+            "the latest stable release",
+        ),
+        Ecosystem::Nuget => (
+            installed_version::detect_installed_nuget_version,
+            "the latest stable release",
+        ),
+        Ecosystem::Hex => (
+            installed_version::detect_installed_hex_version,
+            "the latest stable release",
+        ),
+        Ecosystem::Pub => (
+            installed_version::detect_installed_pub_version,
+            "the latest version",
+        ),
+        Ecosystem::Swift | Ecosystem::Git | Ecosystem::Other(_) => return None,
+    };
+    Some(detector)
+}
+
+// Offline, a spec the project manifest already links needs no lookup at all.
+fn offline_manifest_link(original_spec: &str, matches: Vec<index::LinkMatch>) -> Option<String> {
+    let linked = matches.into_iter().next()?;
+    Some(format!(
+        "offline: {} already linked at {} (manifest entry {}); skipping resolution",
+        original_spec,
+        linked.link_path.display(),
+        linked.dep_spec
+    ))
+}
+
+/// `version_request` is the range or dist-tag the spec asked for, recorded next to
+/// the package version it resolved to.
+fn registry_target_resolution(
+    original_spec: String,
+    resolved: registry_resolver::RegistryResolution,
+    version_request: Option<String>,
+) -> PullTargetResolution {
+    let ecosystem = resolved.target.ecosystem().clone();
+    let package_name = resolved.target.locator().to_string();
+    let mut aliases = BTreeSet::new();
+    aliases.insert(original_spec);
+    aliases.insert(format!("{}:{}", ecosystem.as_str(), package_name));
+    aliases.insert(format!(
+        "{}:{}@{}",
+        ecosystem.as_str(),
+        package_name,
+        resolved.package_version
+    ));
+
+    let mut registry_refs = BTreeSet::new();
+    if let Some(registry_ref) = registry_ref(
+        &ecosystem,
+        &package_name,
+        Some(resolved.package_version.clone()),
+    ) {
+        registry_refs.insert(index::RegistrySpecRef {
+            version_request,
+            ..registry_ref
+        });
+    }
+    let repo_head_fallback_context = repo_head_fallback_context(&resolved);
+    let mut target = resolved.target;
+    if resolved.requested_revision_source == RequestedRevisionSource::VersionDerived
+        && let source::PullTarget::Git(git_target) = &mut target
+    {
+        git_target.release_tag_package = Some(package_name.clone());
+    }
+    let archive_fallback = match &target {
+        source::PullTarget::Git(_) => Some(source::ArchivePullTarget {
+            ecosystem,
+            package_name,
+            package_version: resolved.package_version.clone(),
+        }),
+        source::PullTarget::Archive(_) => None,
+    };
+
+    PullTargetResolution {
+        target,
+        aliases,
+        registry_refs,
+        archive_fallback,
+        repo_head_fallback_context,
+        lockfile_registry_specs: BTreeSet::new(),
+    }
+}
+
 fn normalize_explicit_dep_specs_for_pull(
     cwd: &Path,
     dep_specs: &[String],
@@ -408,6 +799,14 @@ fn infer_default_registry_ecosystem(cwd: &Path) -> anyhow::Result<Ecosystem> {
         Some("npm") => Ok(Ecosystem::Npm),
         Some("pypi") => Ok(Ecosystem::Pypi),
         Some("crates") => Ok(Ecosystem::Crates),
+        Some("go") => Ok(Ecosystem::Go),
+        Some("rubygems") => Ok(Ecosystem::Rubygems),
+        Some("maven") => Ok(Ecosystem::Maven),
+        Some("packagist") => Ok(Ecosystem::Packagist),
+        Some("nuget") => Ok(Ecosystem::Nuget),
+        Some("hex") => Ok(Ecosystem::Hex),
+        Some("pub") => Ok(Ecosystem::Pub),
+        Some("swift") => Ok(Ecosystem::Swift),
         Some(other) => Err(anyhow::anyhow!(
             "unsupported inferred shorthand dependency ecosystem '{other}'"
         )),
@@ -417,7 +816,11 @@ fn infer_default_registry_ecosystem(cwd: &Path) -> anyhow::Result<Ecosystem> {
     }
 }
 
-fn resolve_pull_targets_from_project(cwd: &Path) -> anyhow::Result<PullResolution> {
+fn resolve_pull_targets_from_project(
+    cwd: &Path,
+    config: &Config,
+    resolve_registry: bool,
+) -> anyhow::Result<PullResolution> {
     let inputs = providers::detect_supported_project_files(cwd);
     let discovered_lockfiles = inputs.len();
     if inputs.is_empty() {
@@ -426,12 +829,14 @@ fn resolve_pull_targets_from_project(cwd: &Path) -> anyhow::Result<PullResolutio
             discovered_lockfiles: 0,
             discovered_dependencies: 0,
             skipped_non_git_dependencies: 0,
+            registry_failures: Vec::new(),
         });
     }
 
     let mut targets = Vec::new();
     let mut discovered_dependencies = 0usize;
     let mut skipped_non_git_dependencies = 0usize;
+    let mut registry_dep_specs = BTreeSet::new();
 
     for input in inputs {
         let deps = providers::parse_provider_input(&input).map_err(|err| {
@@ -440,41 +845,63 @@ fn resolve_pull_targets_from_project(cwd: &Path) -> anyhow::Result<PullResolutio
                 input.path.display()
             )
         })?;
-        for dep in deps {
+        for mut dep in deps {
             discovered_dependencies += 1;
-            let Some(git_hint) = dep.git_hint else {
-                skipped_non_git_dependencies += 1;
+            let ecosystem = ecosystem_from_provider(&dep.ecosystem);
+            let Some(git_hint) = dep.git_hint.take() else {
+                if resolve_registry && is_locked_registry_version(&dep.version) {
+                    registry_dep_specs.insert(format!(
+                        "{}:{}@{}",
+                        ecosystem.as_str(),
+                        dep.name,
+                        dep.version
+                    ));
+                } else {
+                    skipped_non_git_dependencies += 1;
+                }
                 continue;
             };
+            targets.push(git_hint_target_resolution(ecosystem, &dep, git_hint));
+        }
+    }
 
-            let ecosystem = ecosystem_from_provider(&dep.ecosystem);
-            let mut aliases = BTreeSet::new();
-            aliases.insert(format!("{}:{}", ecosystem.as_str(), dep.name));
-            aliases.insert(format!(
-                "{}:{}@{}",
-                ecosystem.as_str(),
-                dep.name,
-                dep.version
-            ));
-
-            let mut registry_refs = BTreeSet::new();
-            if let Some(registry_ref) =
-                registry_ref(&ecosystem, &dep.name, Some(dep.version.clone()))
-            {
-                registry_refs.insert(registry_ref);
-            }
+    let mut registry_results = BTreeMap::new();
+    worker_pool::run_bounded(
+        registry_dep_specs.into_iter().collect(),
+        config.worker_pool_size,
+        |dep_spec: String| {
+            let result = resolve_lockfile_registry_dependency(cwd, config, &dep_spec);
+            (dep_spec, result)
+        },
+        |(dep_spec, result)| {
+            registry_results.insert(dep_spec, result);
+            true
+        },
+    );
 
-            targets.push(PullTargetResolution {
-                target: source::GitPullTarget {
-                    ecosystem,
-                    locator: git_hint.url.clone(),
-                    git_url: git_hint.url,
-                    requested_revision: git_hint.requested_revision,
-                },
-                aliases,
-                registry_refs,
-                repo_head_fallback_context: None,
-            });
+    let mut registry_failures = Vec::new();
+    for (dep_spec, result) in registry_results {
+        println!("resolving package metadata for {}", dep_spec);
+        match result {
+            Ok(resolved) => {
+                println!(
+                    "  -> resolved to {} (package version {})",
+                    resolved.target.describe(),
+                    resolved.package_version
+                );
+                let mut target = registry_target_resolution(dep_spec.clone(), resolved, None);
+                target.lockfile_registry_specs.insert(dep_spec);
+                targets.push(target);
+            }
+            Err(err) => {
+                println!("  -> failed to resolve {}: {err:#}", dep_spec);
+                registry_failures.push(RegistryPullReportEntry {
+                    dep_spec,
+                    outcome: RegistryPullOutcome::Failed {
+                        reason: format!("{err:#}"),
+                    },
+                });
+            }
         }
     }
 
@@ -483,7 +910,136 @@ fn resolve_pull_targets_from_project(cwd: &Path) -> anyhow::Result<PullResolutio
         discovered_lockfiles,
         discovered_dependencies,
         skipped_non_git_dependencies,
+        registry_failures,
+    })
+}
+
+fn git_hint_target_resolution(
+    ecosystem: Ecosystem,
+    dep: &providers::NormalizedDependency,
+    git_hint: providers::GitSourceHint,
+) -> PullTargetResolution {
+    let mut aliases = BTreeSet::new();
+    aliases.insert(format!("{}:{}", ecosystem.as_str(), dep.name));
+    aliases.insert(format!(
+        "{}:{}@{}",
+        ecosystem.as_str(),
+        dep.name,
+        dep.version
+    ));
+
+    let mut registry_refs = BTreeSet::new();
+    if let Some(registry_ref) = registry_ref(&ecosystem, &dep.name, Some(dep.version.clone())) {
+        registry_refs.insert(registry_ref);
+    }
+
+    // Packages from one monorepo share the repository URL, so a hint that
+    // names the package directory links under the package name instead.
+    let subdirectory = git_hint
+        .subdirectory
+        .as_deref()
+        .and_then(registry_resolver::normalize_subdirectory);
+    let locator = match subdirectory {
+        Some(_) => dep.name.clone(),
+        None => git_hint.url.clone(),
+    };
+    PullTargetResolution {
+        target: source::PullTarget::Git(source::GitPullTarget {
+            ecosystem,
+            locator,
+            git_url: git_hint.url,
+            requested_revision: git_hint.requested_revision,
+            subdirectory,
+            release_tag_package: None,
+        }),
+        aliases,
+        registry_refs,
+        archive_fallback: None,
+        repo_head_fallback_context: None,
+        lockfile_registry_specs: BTreeSet::new(),
+    }
+}
+
+/// SwiftPM has no central registry to query, so `swift:<identity>` pulls the pin
+/// recorded in the project's `Package.resolved`.
+fn resolve_swift_pin_target(
+    cwd: &Path,
+    original_spec: String,
+    spec: &depspec::DepSpec,
+) -> anyhow::Result<PullTargetResolution> {
+    let resolved_path = cwd.join("Package.resolved");
+    if !resolved_path.exists() {
+        anyhow::bail!(
+            "cannot resolve '{}': no Package.resolved in {}; run 'swift package resolve' first",
+            original_spec,
+            cwd.display()
+        );
+    }
+    let deps = providers::parse_provider_input(&providers::ProviderInputMatch {
+        provider: providers::ProviderKind::PackageResolved,
+        path: resolved_path,
     })
+    .map_err(|err| anyhow::anyhow!("failed to parse Package.resolved: {err}"))?;
+
+    // Identities are case-insensitive; `Package.resolved` records them lowercased.
+    let identity = spec.locator.to_ascii_lowercase();
+    let mut dep = deps
+        .into_iter()
+        .find(|dep| dep.name == identity)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "swift package '{}' is not pinned in Package.resolved",
+                spec.locator
+            )
+        })?;
+    let git_hint = dep.git_hint.take().ok_or_else(|| {
+        anyhow::anyhow!(
+            "swift package '{}' has no source control pin in Package.resolved",
+            spec.locator
+        )
+    })?;
+    if let Some(version) = &spec.version
+        && version != &dep.version
+        && version != &git_hint.requested_revision
+    {
+        anyhow::bail!(
+            "swift package '{}' is pinned at {} in Package.resolved, not {}",
+            spec.locator,
+            dep.version,
+            version
+        );
+    }
+
+    println!(
+        "resolved swift:{} from Package.resolved -> {} at {}",
+        dep.name, git_hint.url, git_hint.requested_revision
+    );
+    let mut target = git_hint_target_resolution(Ecosystem::Swift, &dep, git_hint);
+    target.aliases.insert(original_spec);
+    Ok(target)
+}
+
+fn resolve_lockfile_registry_dependency(
+    cwd: &Path,
+    config: &Config,
+    dep_spec: &str,
+) -> anyhow::Result<registry_resolver::RegistryResolution> {
+    let spec = depspec::parse(dep_spec)
+        .map_err(|err| anyhow::anyhow!("invalid lockfile dependency '{}': {err}", dep_spec))?;
+    registry_resolver::resolve_registry_spec(cwd, config, spec)
+}
+
+// Lockfiles can carry declared ranges or protocol references (for example the
+// root `package.json` ranges in package-lock.json); only exact versions can be
+// resolved against registry metadata.
+fn is_locked_registry_version(version: &str) -> bool {
+    !version.is_empty()
+        && !version.chars().any(|ch| {
+            matches!(
+                ch,
+                '^' | '~' | '>' | '<' | '=' | '*' | '|' | ' ' | ':' | '/'
+            )
+        })
 }
 
 fn deduplicate_pull_targets(targets: Vec<PullTargetResolution>) -> Vec<PullTargetResolution> {
@@ -491,17 +1047,32 @@ fn deduplicate_pull_targets(targets: Vec<PullTargetResolution>) -> Vec<PullTarge
     let mut deduped: Vec<PullTargetResolution> = Vec::new();
 
     for target in targets {
-        let key = format!(
-            "{}||{}||{}",
-            target.target.ecosystem.as_str(),
-            target.target.git_url,
-            target.target.requested_revision
-        );
+        let key = match &target.target {
+            source::PullTarget::Git(git_target) => format!(
+                "{}||{}||{}||{}",
+                git_target.ecosystem.as_str(),
+                git_target.git_url,
+                git_target.requested_revision,
+                git_target.subdirectory.as_deref().unwrap_or_default()
+            ),
+            source::PullTarget::Archive(archive_target) => format!(
+                "{}||archive||{}||{}",
+                archive_target.ecosystem.as_str(),
+                archive_target.package_name,
+                archive_target.package_version
+            ),
+        };
 
         if let Some(existing_index) = seen.get(&key).copied() {
             if let Some(existing_target) = deduped.get_mut(existing_index) {
                 existing_target.aliases.extend(target.aliases);
                 existing_target.registry_refs.extend(target.registry_refs);
+                existing_target
+                    .lockfile_registry_specs
+                    .extend(target.lockfile_registry_specs);
+                if existing_target.archive_fallback.is_none() {
+                    existing_target.archive_fallback = target.archive_fallback;
+                }
                 if existing_target.repo_head_fallback_context.is_none() {
                     existing_target.repo_head_fallback_context = target.repo_head_fallback_context;
                 }
@@ -519,14 +1090,14 @@ fn deduplicate_pull_targets(targets: Vec<PullTargetResolution>) -> Vec<PullTarge
 fn repo_head_fallback_context(
     resolved: &registry_resolver::RegistryResolution,
 ) -> Option<RepoHeadFallbackContext> {
-    if resolved.requested_revision_source == RequestedRevisionSource::ExactMetadata {
+    if resolved.requested_revision_source != RequestedRevisionSource::VersionDerived {
         return None;
     }
 
     let spec = format!(
         "{}:{}@{}",
-        resolved.target.ecosystem.as_str(),
-        resolved.target.locator,
+        resolved.target.ecosystem().as_str(),
+        resolved.target.locator(),
         resolved.package_version
     );
 
@@ -541,16 +1112,35 @@ fn resolve_materialized_pull_target(
     config: &Config,
     target_resolution: &PullTargetResolution,
     fallback_repo_head: bool,
-) -> anyhow::Result<(source::GitPullTarget, source::MaterializedSource)> {
-    println!("  -> resolving via local git mirror");
+    log: &mut Vec<String>,
+) -> anyhow::Result<(source::PullTarget, source::MaterializedSource)> {
+    match &target_resolution.target {
+        source::PullTarget::Git(_) => log.push("  -> resolving via local git mirror".to_string()),
+        source::PullTarget::Archive(_) => {
+            log.push("  -> resolving via published package archive".to_string())
+        }
+    }
 
     let (effective_target, materialized) =
-        materialize_pull_target(cwd, config, target_resolution, fallback_repo_head)?;
+        materialize_pull_target(cwd, config, target_resolution, fallback_repo_head, log)?;
 
-    if materialized.git_fetch_performed {
-        println!("  -> fetched requested revision from origin");
+    if let Some(release_tag) = &materialized.release_tag {
+        log.push(format!(
+            "  -> mapped version to release tag {} ({})",
+            release_tag.name, release_tag.convention
+        ));
+    }
+
+    if let source::PullTarget::Archive(_) = &effective_target {
+        if materialized.archive_download_performed {
+            log.push("  -> downloaded and unpacked published archive".to_string());
+        } else {
+            log.push("  -> reused unpacked archive from local cache".to_string());
+        }
+    } else if materialized.git_fetch_performed {
+        log.push("  -> fetched requested revision from origin".to_string());
     } else {
-        println!("  -> reused requested revision from local mirror");
+        log.push("  -> reused requested revision from local mirror".to_string());
     }
 
     Ok((effective_target, materialized))
@@ -561,22 +1151,86 @@ fn materialize_pull_target(
     config: &Config,
     target_resolution: &PullTargetResolution,
     fallback_repo_head: bool,
-) -> anyhow::Result<(source::GitPullTarget, source::MaterializedSource)> {
-    let target = &target_resolution.target;
+    log: &mut Vec<String>,
+) -> anyhow::Result<(source::PullTarget, source::MaterializedSource)> {
+    let target = match &target_resolution.target {
+        source::PullTarget::Git(target) => target,
+        source::PullTarget::Archive(target) => {
+            let materialized = source::materialize_archive_source(cwd, config, target)?;
+            return Ok((target_resolution.target.clone(), materialized));
+        }
+    };
     match source::materialize_git_source(cwd, config, target) {
-        Ok(materialized) => Ok((target.clone(), materialized)),
-        Err(err) => try_repo_head_fallback(cwd, config, target_resolution, fallback_repo_head, err),
+        Ok(materialized) => Ok((target_resolution.target.clone(), materialized)),
+        // The fallback would wait on the same mirror lock, so surface it directly.
+        Err(err) if is_lock_timeout(&err) => Err(err),
+        Err(err) => {
+            // Only a repository or revision that does not exist falls back; an
+            // unreachable remote is reported rather than papered over.
+            if let Some(archive_target) = &target_resolution.archive_fallback
+                && source::is_missing_source(&err)
+            {
+                match source::materialize_archive_source(cwd, config, archive_target) {
+                    Ok(materialized) => {
+                        warn!(
+                            git_url = %target.git_url,
+                            requested_revision = %target.requested_revision,
+                            error = %format!("{err:#}"),
+                            "git source unavailable; using the published package archive"
+                        );
+                        log.push(format!(
+                            "  -> git revision {} unavailable ({err:#}); using the published package archive instead",
+                            target.requested_revision
+                        ));
+                        return Ok((
+                            source::PullTarget::Archive(archive_target.clone()),
+                            materialized,
+                        ));
+                    }
+                    Err(archive_err) => {
+                        log.push(format!(
+                            "  -> published archive fallback failed: {archive_err:#}"
+                        ));
+                    }
+                }
+            }
+            try_repo_head_fallback(
+                cwd,
+                config,
+                target_resolution,
+                target,
+                fallback_repo_head,
+                err,
+                log,
+            )
+        }
     }
 }
 
+fn is_lock_timeout(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<PkgrepError>(),
+        Some(PkgrepError::LockTimeout { .. })
+    )
+}
+
 fn try_repo_head_fallback(
     cwd: &Path,
     config: &Config,
     target_resolution: &PullTargetResolution,
+    target: &source::GitPullTarget,
     fallback_repo_head: bool,
     source_error: anyhow::Error,
-) -> anyhow::Result<(source::GitPullTarget, source::MaterializedSource)> {
-    let target = &target_resolution.target;
+    log: &mut Vec<String>,
+) -> anyhow::Result<(source::PullTarget, source::MaterializedSource)> {
+    if config.offline {
+        return Err(source_error).with_context(|| {
+            format!(
+                "offline mode: {}@{} is not in the local git mirror or source cache",
+                target.git_url, target.requested_revision
+            )
+        });
+    }
     let Some(context) = &target_resolution.repo_head_fallback_context else {
         return Err(source_error).with_context(|| {
             format!(
@@ -590,21 +1244,24 @@ fn try_repo_head_fallback(
         return Err(source_error).context(repo_head_fallback_guidance(context, target));
     }
 
-    println!(
+    log.push(
         "  -> exact source revision unavailable; falling back to repository default branch because --fallback-repo-head was set"
+            .to_string(),
     );
-    let resolved = source::resolve_default_remote_revision(&target.git_url)
+    let resolved = source::resolve_default_remote_revision(config, &target.git_url)
         .with_context(|| format!("failed to resolve default branch for {}", target.git_url))?;
-    println!(
+    log.push(format!(
         "  -> resolved {} default branch {} -> {}",
         target.git_url, resolved.default_branch_ref, resolved.commit_id
-    );
+    ));
 
     let fallback_target = source::GitPullTarget {
         ecosystem: target.ecosystem.clone(),
         locator: target.locator.clone(),
         git_url: target.git_url.clone(),
         requested_revision: resolved.commit_id,
+        subdirectory: target.subdirectory.clone(),
+        release_tag_package: None,
     };
     let materialized =
         source::materialize_git_source(cwd, config, &fallback_target).with_context(|| {
@@ -613,7 +1270,7 @@ fn try_repo_head_fallback(
                 context.package_label
             )
         })?;
-    Ok((fallback_target, materialized))
+    Ok((source::PullTarget::Git(fallback_target), materialized))
 }
 
 fn repo_head_fallback_guidance(
@@ -636,6 +1293,7 @@ fn registry_ref(
         ecosystem,
         name: name.to_string(),
         package_version,
+        version_request: None,
     })
 }
 
@@ -644,6 +1302,14 @@ fn ecosystem_from_provider(ecosystem: &providers::ProviderEcosystem) -> Ecosyste
         providers::ProviderEcosystem::Npm => Ecosystem::Npm,
         providers::ProviderEcosystem::Pypi => Ecosystem::Pypi,
         providers::ProviderEcosystem::Crates => Ecosystem::Crates,
+        providers::ProviderEcosystem::Go => Ecosystem::Go,
+        providers::ProviderEcosystem::Rubygems => Ecosystem::Rubygems,
+        providers::ProviderEcosystem::Maven => Ecosystem::Maven,
+        providers::ProviderEcosystem::Packagist => Ecosystem::Packagist,
+        providers::ProviderEcosystem::Nuget => Ecosystem::Nuget,
+        providers::ProviderEcosystem::Hex => Ecosystem::Hex,
+        providers::ProviderEcosystem::Pub => Ecosystem::Pub,
+        providers::ProviderEcosystem::Swift => Ecosystem::Swift,
     }
 }
 
@@ -651,16 +1317,31 @@ fn ecosystem_from_provider_kind(kind: &providers::ProviderKind) -> &'static str
     match kind {
         providers::ProviderKind::Package
         | providers::ProviderKind::Pnpm
-        | providers::ProviderKind::Yarn => "npm",
+        | providers::ProviderKind::Yarn
+        | providers::ProviderKind::Bun => "npm",
         providers::ProviderKind::Cargo => "crates",
-        providers::ProviderKind::Uv => "pypi",
+        providers::ProviderKind::Uv
+        | providers::ProviderKind::Poetry
+        | providers::ProviderKind::Pdm
+        | providers::ProviderKind::Pipfile => "pypi",
+        providers::ProviderKind::GoMod | providers::ProviderKind::GoSum => "go",
+        providers::ProviderKind::Bundler => "rubygems",
+        providers::ProviderKind::GradleLockfile
+        | providers::ProviderKind::GradleVersionCatalog
+        | providers::ProviderKind::Pom => "maven",
+        providers::ProviderKind::Composer => "packagist",
+        providers::ProviderKind::NugetPackagesLock
+        | providers::ProviderKind::DirectoryPackagesProps => "nuget",
+        providers::ProviderKind::Mix => "hex",
+        providers::ProviderKind::PubspecLock => "pub",
+        providers::ProviderKind::PackageResolved => "swift",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::source::GitPullTarget;
+    use crate::source::{GitPullTarget, PullTarget};
 
     #[test]
     fn explicit_scheme_detection() {
@@ -731,6 +1412,17 @@ mod tests {
         assert_eq!(normalized, vec![String::from("npm:zod@3.23.8")]);
     }
 
+    #[test]
+    fn shorthand_inference_rewrites_with_single_bun_lockfile() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("bun.lock"), "{}").expect("write bun lock");
+
+        let normalized =
+            normalize_explicit_dep_specs_for_pull(temp.path(), &[String::from("zod@3.23.8")])
+                .expect("normalize shorthand");
+        assert_eq!(normalized, vec![String::from("npm:zod@3.23.8")]);
+    }
+
     #[test]
     fn shorthand_inference_rewrites_with_single_cargo_lockfile() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -742,6 +1434,143 @@ mod tests {
         assert_eq!(normalized, vec![String::from("crates:serde")]);
     }
 
+    #[test]
+    fn shorthand_inference_rewrites_with_go_module_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("go.mod"), "module example.com/app\n")
+            .expect("write go.mod");
+        std::fs::write(temp.path().join("go.sum"), "").expect("write go.sum");
+
+        let normalized = normalize_explicit_dep_specs_for_pull(
+            temp.path(),
+            &[String::from("github.com/spf13/cobra@v1.8.0")],
+        )
+        .expect("normalize shorthand");
+        assert_eq!(
+            normalized,
+            vec![String::from("go:github.com/spf13/cobra@v1.8.0")]
+        );
+    }
+
+    #[test]
+    fn shorthand_inference_treats_python_lockfiles_as_one_ecosystem() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("poetry.lock"), "").expect("write poetry lock");
+        std::fs::write(temp.path().join("Pipfile.lock"), "{}").expect("write Pipfile lock");
+
+        let normalized =
+            normalize_explicit_dep_specs_for_pull(temp.path(), &[String::from("requests")])
+                .expect("normalize shorthand");
+        assert_eq!(normalized, vec![String::from("pypi:requests")]);
+    }
+
+    #[test]
+    fn locked_registry_version_rejects_ranges_and_protocols() {
+        assert!(is_locked_registry_version("18.3.1"));
+        assert!(is_locked_registry_version("2.32.3"));
+        assert!(is_locked_registry_version("1.0.0-beta.1+build.5"));
+        assert!(!is_locked_registry_version(""));
+        assert!(!is_locked_registry_version("^18.3.1"));
+        assert!(!is_locked_registry_version(">=1.0, <2"));
+        assert!(!is_locked_registry_version("workspace:*"));
+        assert!(!is_locked_registry_version("file:../local"));
+    }
+
+    #[test]
+    fn deduplicated_targets_merge_lockfile_registry_specs() {
+        let target = |spec: &str| PullTargetResolution {
+            target: PullTarget::Git(GitPullTarget {
+                ecosystem: Ecosystem::Npm,
+                locator: String::from("@babel/core"),
+                git_url: String::from("https://github.com/babel/babel.git"),
+                requested_revision: String::from("deadbeef"),
+                subdirectory: None,
+                release_tag_package: None,
+            }),
+            aliases: BTreeSet::new(),
+            registry_refs: BTreeSet::new(),
+            archive_fallback: None,
+            repo_head_fallback_context: None,
+            lockfile_registry_specs: BTreeSet::from([String::from(spec)]),
+        };
+
+        let deduped = deduplicate_pull_targets(vec![
+            target("npm:@babel/core@7.26.0"),
+            target("npm:@babel/core@7.26.0-alias"),
+        ]);
+
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].lockfile_registry_specs.len(), 2);
+    }
+
+    #[test]
+    fn deduplication_keeps_monorepo_packages_with_distinct_subdirectories() {
+        let target = |locator: &str, subdirectory: &str| PullTargetResolution {
+            target: PullTarget::Git(GitPullTarget {
+                ecosystem: Ecosystem::Npm,
+                locator: String::from(locator),
+                git_url: String::from("https://github.com/babel/babel.git"),
+                requested_revision: String::from("deadbeef"),
+                subdirectory: Some(String::from(subdirectory)),
+                release_tag_package: None,
+            }),
+            aliases: BTreeSet::new(),
+            registry_refs: BTreeSet::new(),
+            archive_fallback: None,
+            repo_head_fallback_context: None,
+            lockfile_registry_specs: BTreeSet::new(),
+        };
+
+        let deduped = deduplicate_pull_targets(vec![
+            target("@babel/core", "packages/babel-core"),
+            target("@babel/parser", "packages/babel-parser"),
+            target("@babel/core", "packages/babel-core"),
+        ]);
+
+        assert_eq!(deduped.len(), 2);
+    }
+
+    #[test]
+    fn targets_sharing_a_mirror_are_grouped_in_order() {
+        let target = |ecosystem: Ecosystem, git_url: &str, revision: &str| PullTargetResolution {
+            target: PullTarget::Git(GitPullTarget {
+                ecosystem,
+                locator: String::from(git_url),
+                git_url: String::from(git_url),
+                requested_revision: String::from(revision),
+                subdirectory: None,
+                release_tag_package: None,
+            }),
+            aliases: BTreeSet::new(),
+            registry_refs: BTreeSet::new(),
+            archive_fallback: None,
+            repo_head_fallback_context: None,
+            lockfile_registry_specs: BTreeSet::new(),
+        };
+
+        let groups = group_targets_by_mirror(&[
+            target(Ecosystem::Git, "https://example.com/a.git", "v1"),
+            target(Ecosystem::Git, "https://example.com/b.git", "v1"),
+            target(Ecosystem::Git, "https://example.com/a.git", "v2"),
+            target(Ecosystem::Npm, "https://example.com/a.git", "v3"),
+        ]);
+
+        assert_eq!(groups, vec![vec![0, 2], vec![1], vec![3]]);
+    }
+
+    #[test]
+    fn every_registry_scheme_but_swift_detects_installed_versions() {
+        for scheme in depspec::REGISTRY_SCHEMES {
+            let spec = depspec::parse(&format!("{scheme}:demo")).expect("parse spec");
+            assert_eq!(
+                installed_version_detector(&spec.ecosystem).is_some(),
+                spec.ecosystem != Ecosystem::Swift,
+                "{scheme}"
+            );
+        }
+        assert!(installed_version_detector(&Ecosystem::Git).is_none());
+    }
+
     #[test]
     fn repo_head_fallback_guidance_mentions_flag_and_spec() {
         let context = RepoHeadFallbackContext {
@@ -753,6 +1582,8 @@ mod tests {
             locator: String::from("@types/node"),
             git_url: String::from("https://github.com/DefinitelyTyped/DefinitelyTyped.git"),
             requested_revision: String::from("25.6.0"),
+            subdirectory: None,
+            release_tag_package: None,
         };
 
         let guidance = repo_head_fallback_guidance(&context, &target);
@@ -36,8 +36,19 @@ pub(super) fn run_remove(
     let mut not_found = 0usize;
     let mut skipped = 0usize;
 
-    for spec in parsed_specs {
-        let candidate_paths = if let Some(version) = spec.version {
+    for (dep_spec, spec) in dep_specs.iter().zip(parsed_specs) {
+        let candidate_paths = if matches!(
+            spec.source_kind,
+            depspec::SourceKind::Git {
+                subdirectory: Some(_),
+                ..
+            }
+        ) {
+            index::find_dep_spec_link(cwd, dep_spec)?
+                .into_iter()
+                .map(|link_match| link_match.link_path)
+                .collect()
+        } else if let Some(version) = spec.version {
             vec![cwd.join(depspec::link_path(&spec.ecosystem, &spec.locator, &version))]
         } else {
             let locator_prefix_path =
@@ -68,6 +79,7 @@ pub(super) fn run_remove(
                         &cache_root,
                         &candidate,
                         symlink_target.as_deref(),
+                        config.lock_timeout(),
                     ) {
                         warn!(candidate = %candidate.display(), error = %err, "failed to update local index files after remove");
                     }
@@ -0,0 +1,397 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use ignore::WalkBuilder;
+use serde::Serialize;
+use tracing::info;
+
+use crate::archive;
+use crate::config::Config;
+use crate::depspec::{self, DepSpec, SourceKind};
+use crate::index::{self, LinkMatch, VerificationRecord};
+use crate::registry_resolver::{self, ArtifactKind};
+
+const MAX_LISTED_FILES: usize = 20;
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct FileComparison {
+    identical_files: usize,
+    missing: Vec<String>,
+    different: Vec<String>,
+    extra: Vec<String>,
+}
+
+impl FileComparison {
+    fn published_file_count(&self) -> usize {
+        self.identical_files + self.missing.len() + self.different.len()
+    }
+
+    fn match_score(&self) -> f64 {
+        match self.published_file_count() {
+            0 => 0.0,
+            total => self.identical_files as f64 / total as f64,
+        }
+    }
+}
+
+pub(super) fn run_verify(
+    cwd: &Path,
+    config: &Config,
+    dep_spec: String,
+    json: bool,
+) -> anyhow::Result<()> {
+    let parsed_specs = super::parse_dep_specs(std::slice::from_ref(&dep_spec))?;
+    let spec = parsed_specs
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("missing dependency spec"))?;
+    if matches!(spec.source_kind, SourceKind::Git { .. }) || !spec.ecosystem.is_registry() {
+        anyhow::bail!(
+            "verify supports registry package specs only; use '<ecosystem>:<name>[@<version>]' with one of: {}",
+            depspec::REGISTRY_SCHEMES.join(", ")
+        );
+    }
+
+    let link_match = select_link(cwd, &dep_spec, &spec)?;
+    let package_version = link_match.package_version.clone().ok_or_else(|| {
+        anyhow::anyhow!(
+            "could not determine the linked version of '{}'; pass an explicit version (for example '{}:{}@<version>')",
+            dep_spec,
+            spec.ecosystem.as_str(),
+            spec.locator
+        )
+    })?;
+
+    let artifact = registry_resolver::locate_published_artifact(
+        cwd,
+        config,
+        &spec.ecosystem,
+        &spec.locator,
+        &package_version,
+    )?;
+    info!(
+        dep_spec = %dep_spec,
+        package_version = %package_version,
+        artifact_url = %artifact.url,
+        "verifying linked checkout against published artifact"
+    );
+    let bytes = registry_resolver::download_published_artifact(cwd, config, &artifact)?;
+    let published_files =
+        archive::read_package_files(&bytes, artifact.format, artifact.strip_components)
+            .with_context(|| format!("failed to unpack published artifact {}", artifact.url))?;
+    let published_files = comparable_published_files(artifact.kind, published_files);
+    let comparison = compare_with_checkout(&link_match.link_path, &published_files, artifact.kind)?;
+
+    let verification = VerificationRecord {
+        package_version: package_version.clone(),
+        artifact_url: artifact.url.to_string(),
+        match_score: comparison.match_score(),
+        identical_files: comparison.identical_files,
+        missing_files: comparison.missing.len(),
+        different_files: comparison.different.len(),
+        extra_files: comparison.extra.len(),
+    };
+    index::record_verification(cwd, &link_match.dep_spec, verification.clone())?;
+
+    if json {
+        let payload = serde_json::json!({
+            "dep_spec": dep_spec,
+            "linked_dep_spec": link_match.dep_spec,
+            "package_version": package_version,
+            "artifact_url": verification.artifact_url,
+            "match_score": verification.match_score,
+            "identical_files": comparison.identical_files,
+            "missing": comparison.missing,
+            "different": comparison.different,
+            "extra": comparison.extra,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload)
+                .map_err(|err| anyhow::anyhow!("failed to serialize verify output: {err}"))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}:{}@{} -> {}",
+        spec.ecosystem.as_str(),
+        spec.locator,
+        package_version,
+        link_match.dep_spec
+    );
+    println!("artifact: {}", verification.artifact_url);
+    println!(
+        "match score: {:.1}% ({} of {} published files identical)",
+        verification.match_score * 100.0,
+        comparison.identical_files,
+        comparison.published_file_count()
+    );
+    print_file_list("missing", &comparison.missing);
+    print_file_list("different", &comparison.different);
+    print_file_list("extra", &comparison.extra);
+
+    Ok(())
+}
+
+fn select_link(cwd: &Path, dep_spec: &str, spec: &DepSpec) -> anyhow::Result<LinkMatch> {
+    let mut matches = index::find_registry_link_matches(
+        cwd,
+        dep_spec,
+        &spec.ecosystem,
+        &spec.locator,
+        spec.version.as_deref(),
+    )?;
+
+    match matches.len() {
+        0 => anyhow::bail!("dependency is not linked in this project: {}", dep_spec),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let candidates = matches
+                .iter()
+                .map(|link_match| link_match.dep_spec.as_str())
+                .collect::<Vec<_>>();
+            anyhow::bail!(
+                "multiple linked dependencies match '{}': {}. Use a versioned dependency spec.",
+                dep_spec,
+                candidates.join(", ")
+            );
+        }
+    }
+}
+
+fn print_file_list(label: &str, paths: &[String]) {
+    if paths.is_empty() {
+        return;
+    }
+
+    println!("{} ({}):", label, paths.len());
+    for path in paths.iter().take(MAX_LISTED_FILES) {
+        println!("  {}", path);
+    }
+    if paths.len() > MAX_LISTED_FILES {
+        println!(
+            "  ... and {} more (use --json for the full list)",
+            paths.len() - MAX_LISTED_FILES
+        );
+    }
+}
+
+/// Drops files the packaging tool generates at publish time, so they are not
+/// reported as missing from the source checkout.
+fn comparable_published_files(
+    kind: ArtifactKind,
+    mut files: BTreeMap<String, Vec<u8>>,
+) -> BTreeMap<String, Vec<u8>> {
+    match kind {
+        ArtifactKind::NpmTarball
+        | ArtifactKind::GoModuleZip
+        | ArtifactKind::Gem
+        | ArtifactKind::ComposerDist
+        | ArtifactKind::HexTarball
+        | ArtifactKind::PubArchive => {}
+        ArtifactKind::Crate => {
+            files.remove(".cargo_vcs_info.json");
+            files.remove("Cargo.lock");
+            // `cargo publish` rewrites Cargo.toml and keeps the original alongside it.
+            if let Some(original_manifest) = files.remove("Cargo.toml.orig") {
+                files.insert(String::from("Cargo.toml"), original_manifest);
+            }
+        }
+        ArtifactKind::PypiSdist => {
+            files.retain(|path, _| {
+                path != "PKG-INFO"
+                    && !path
+                        .split('/')
+                        .any(|component| component.ends_with(".egg-info"))
+            });
+        }
+        ArtifactKind::MavenSourcesJar => {
+            files.retain(|path, _| !path.starts_with("META-INF/"));
+        }
+        ArtifactKind::Nupkg => {
+            // OPC packaging parts, the signature, and the generated root `.nuspec`.
+            files.retain(|path, _| {
+                let root_nuspec = !path.contains('/') && path.ends_with(".nuspec");
+                !root_nuspec
+                    && path != "[Content_Types].xml"
+                    && path != ".signature.p7s"
+                    && !path.starts_with("_rels/")
+                    && !path.starts_with("package/")
+            });
+        }
+        ArtifactKind::PypiWheel => {
+            files.retain(|path, _| {
+                let top_level = path.split('/').next().unwrap_or_default();
+                !top_level.ends_with(".dist-info") && !top_level.ends_with(".data")
+            });
+        }
+    }
+    files
+}
+
+fn compare_with_checkout(
+    link_path: &Path,
+    published_files: &BTreeMap<String, Vec<u8>>,
+    kind: ArtifactKind,
+) -> anyhow::Result<FileComparison> {
+    let root = fs::canonicalize(link_path)
+        .with_context(|| format!("failed to resolve linked checkout {}", link_path.display()))?;
+    let checkout_files = collect_checkout_files(&root)?;
+
+    let mut comparison = FileComparison::default();
+    let mut matched_checkout_files = BTreeSet::new();
+    for (path, published_contents) in published_files {
+        // Wheels install the import package at the top level, while many projects
+        // keep it under `src/`; sources jars are rooted at the Java package.
+        let candidates = match kind {
+            ArtifactKind::PypiWheel => vec![path.clone(), format!("src/{path}")],
+            ArtifactKind::MavenSourcesJar => vec![
+                path.clone(),
+                format!("src/main/java/{path}"),
+                format!("src/main/kotlin/{path}"),
+                format!("src/main/resources/{path}"),
+            ],
+            _ => vec![path.clone()],
+        };
+        let Some(checkout_path) = candidates
+            .into_iter()
+            .find(|candidate| checkout_files.contains(candidate))
+        else {
+            comparison.missing.push(path.clone());
+            continue;
+        };
+
+        let checkout_contents = fs::read(root.join(&checkout_path))
+            .with_context(|| format!("failed to read {}", root.join(&checkout_path).display()))?;
+        if &checkout_contents == published_contents {
+            comparison.identical_files += 1;
+        } else {
+            comparison.different.push(path.clone());
+        }
+        matched_checkout_files.insert(checkout_path);
+    }
+
+    comparison.extra = checkout_files
+        .difference(&matched_checkout_files)
+        .cloned()
+        .collect();
+    Ok(comparison)
+}
+
+fn collect_checkout_files(root: &Path) -> anyhow::Result<BTreeSet<String>> {
+    let mut files = BTreeSet::new();
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker {
+        let entry =
+            entry.with_context(|| format!("failed to walk linked checkout {}", root.display()))?;
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        files.insert(
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        );
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn published(entries: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
+        entries
+            .iter()
+            .map(|(path, contents)| (path.to_string(), contents.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn comparison_reports_missing_different_and_extra_files() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).expect("create .git");
+        fs::create_dir_all(root.join("lib")).expect("create lib");
+        fs::write(root.join(".git").join("HEAD"), "ref\n").expect("write");
+        fs::write(root.join("package.json"), "{}").expect("write");
+        fs::write(root.join("lib").join("index.js"), "local\n").expect("write");
+        fs::write(root.join("README.md"), "readme\n").expect("write");
+
+        let comparison = compare_with_checkout(
+            root,
+            &published(&[
+                ("package.json", "{}"),
+                ("lib/index.js", "published\n"),
+                ("dist/index.js", "built\n"),
+            ]),
+            ArtifactKind::NpmTarball,
+        )
+        .expect("compare");
+
+        assert_eq!(
+            comparison,
+            FileComparison {
+                identical_files: 1,
+                missing: vec![String::from("dist/index.js")],
+                different: vec![String::from("lib/index.js")],
+                extra: vec![String::from("README.md")],
+            }
+        );
+        assert!((comparison.match_score() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn generated_crate_files_are_ignored_and_original_manifest_is_compared() {
+        let files = comparable_published_files(
+            ArtifactKind::Crate,
+            published(&[
+                (".cargo_vcs_info.json", "{}"),
+                ("Cargo.lock", "# generated"),
+                ("Cargo.toml", "# normalized"),
+                ("Cargo.toml.orig", "[package]\n"),
+                ("src/lib.rs", ""),
+            ]),
+        );
+
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["Cargo.toml", "src/lib.rs"]
+        );
+        assert_eq!(files["Cargo.toml"], b"[package]\n");
+    }
+
+    #[test]
+    fn wheel_files_match_src_layout_and_skip_dist_info() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let root = temp.path();
+        fs::create_dir_all(root.join("src").join("demo")).expect("create src");
+        fs::write(root.join("src").join("demo").join("__init__.py"), "x = 1\n").expect("write");
+
+        let files = comparable_published_files(
+            ArtifactKind::PypiWheel,
+            published(&[
+                ("demo/__init__.py", "x = 1\n"),
+                ("demo-1.0.dist-info/METADATA", "Name: demo\n"),
+            ]),
+        );
+        let comparison =
+            compare_with_checkout(root, &files, ArtifactKind::PypiWheel).expect("compare");
+
+        assert_eq!(comparison.identical_files, 1);
+        assert!(comparison.missing.is_empty());
+        assert!(comparison.extra.is_empty());
+    }
+}
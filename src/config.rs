@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
@@ -8,12 +9,62 @@ use crate::error::PkgrepError;
 pub struct Config {
     pub cache_dir: PathBuf,
     pub worker_pool_size: usize,
+    pub lock_timeout_secs: u64,
+    pub checkout_strategy: CheckoutStrategy,
+    /// Release tag templates keyed by `<ecosystem>:<package>`, for repositories whose
+    /// tags follow none of the built-in conventions.
+    pub tag_formats: BTreeMap<String, String>,
+    /// Private key files offered to SSH git hosts after the ssh-agent.
+    pub git_ssh_keys: Vec<PathBuf>,
+    /// Git URL prefixes mapped to the prefix to fetch from instead, like git's
+    /// `url.<base>.insteadOf`.
+    pub git_url_rewrites: BTreeMap<String, String>,
+    /// Resolve only from cached registry metadata, existing git mirrors, and manifest
+    /// aliases, never touching the network.
+    pub offline: bool,
+}
+
+/// How much of a repository is written into each cache checkout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckoutStrategy {
+    /// Check out the whole tree.
+    #[default]
+    Full,
+    /// Check out only the package subdirectory plus files at the repository root,
+    /// for targets that live inside a monorepo.
+    Sparse,
+}
+
+impl CheckoutStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Sparse => "sparse",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "full" => Some(Self::Full),
+            "sparse" => Some(Self::Sparse),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
 struct PartialConfig {
     cache_dir: Option<PathBuf>,
     worker_pool_size: Option<usize>,
+    lock_timeout_secs: Option<u64>,
+    checkout_strategy: Option<CheckoutStrategy>,
+    #[serde(default)]
+    tag_formats: BTreeMap<String, String>,
+    git_ssh_keys: Option<Vec<PathBuf>>,
+    #[serde(default)]
+    git_url_rewrites: BTreeMap<String, String>,
+    offline: Option<bool>,
 }
 
 pub fn load(cwd: &Path) -> Result<Config, PkgrepError> {
@@ -53,6 +104,14 @@ fn default_worker_pool_size() -> usize {
     }
 }
 
+const DEFAULT_LOCK_TIMEOUT_SECS: u64 = 300;
+
+impl Config {
+    pub fn lock_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.lock_timeout_secs)
+    }
+}
+
 fn load_partial_if_exists(path: &Path) -> Result<PartialConfig, PkgrepError> {
     if !path.exists() {
         return Ok(PartialConfig::default());
@@ -77,9 +136,34 @@ fn partial_from_env() -> Result<PartialConfig, PkgrepError> {
         Err(_) => None,
     };
 
+    let lock_timeout_secs = match std::env::var("PKGREP_LOCK_TIMEOUT_SECS") {
+        Ok(value) => value.parse::<u64>().ok(),
+        Err(_) => None,
+    };
+
+    let checkout_strategy = match std::env::var("PKGREP_CHECKOUT_STRATEGY") {
+        Ok(value) => Some(
+            CheckoutStrategy::parse(&value).ok_or(PkgrepError::InvalidCheckoutStrategy(value))?,
+        ),
+        Err(_) => None,
+    };
+
+    let git_ssh_keys = std::env::var_os("PKGREP_GIT_SSH_KEY").map(|key| vec![PathBuf::from(key)]);
+
+    let offline = match std::env::var("PKGREP_OFFLINE") {
+        Ok(value) => parse_bool(&value),
+        Err(_) => None,
+    };
+
     Ok(PartialConfig {
         cache_dir,
         worker_pool_size,
+        lock_timeout_secs,
+        checkout_strategy,
+        tag_formats: BTreeMap::new(),
+        git_ssh_keys,
+        git_url_rewrites: BTreeMap::new(),
+        offline,
     })
 }
 
@@ -105,12 +189,67 @@ fn merge_config(
         return Err(PkgrepError::InvalidWorkerPoolSize(worker_pool_size));
     }
 
+    let lock_timeout_secs = env
+        .lock_timeout_secs
+        .or(project.lock_timeout_secs)
+        .or(global.lock_timeout_secs)
+        .unwrap_or(DEFAULT_LOCK_TIMEOUT_SECS);
+
+    let checkout_strategy = env
+        .checkout_strategy
+        .or(project.checkout_strategy)
+        .or(global.checkout_strategy)
+        .unwrap_or_default();
+
+    // Per-package entries merge, with project entries replacing global ones.
+    let mut tag_formats = global.tag_formats;
+    tag_formats.extend(project.tag_formats);
+
+    let git_ssh_keys = env
+        .git_ssh_keys
+        .or(project.git_ssh_keys)
+        .or(global.git_ssh_keys)
+        .unwrap_or_default()
+        .into_iter()
+        .map(expand_home)
+        .collect();
+
+    let mut git_url_rewrites = global.git_url_rewrites;
+    git_url_rewrites.extend(project.git_url_rewrites);
+
+    let offline = env
+        .offline
+        .or(project.offline)
+        .or(global.offline)
+        .unwrap_or(false);
+
     Ok(Config {
         cache_dir,
         worker_pool_size,
+        lock_timeout_secs,
+        checkout_strategy,
+        tag_formats,
+        git_ssh_keys,
+        git_url_rewrites,
+        offline,
     })
 }
 
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +258,12 @@ mod tests {
         PartialConfig {
             cache_dir,
             worker_pool_size,
+            lock_timeout_secs: None,
+            checkout_strategy: None,
+            tag_formats: BTreeMap::new(),
+            git_ssh_keys: None,
+            git_url_rewrites: BTreeMap::new(),
+            offline: None,
         }
     }
 
@@ -144,6 +289,101 @@ mod tests {
 
         assert!(!cfg.cache_dir.as_os_str().is_empty());
         assert!(cfg.worker_pool_size >= 1);
+        assert_eq!(cfg.lock_timeout_secs, DEFAULT_LOCK_TIMEOUT_SECS);
+    }
+
+    #[test]
+    fn lock_timeout_follows_config_precedence() {
+        let global = PartialConfig {
+            lock_timeout_secs: Some(60),
+            ..PartialConfig::default()
+        };
+        let project = PartialConfig {
+            lock_timeout_secs: Some(5),
+            ..PartialConfig::default()
+        };
+
+        let cfg = merge_config(global, project, PartialConfig::default()).expect("merge");
+        assert_eq!(cfg.lock_timeout_secs, 5);
+    }
+
+    #[test]
+    fn checkout_strategy_defaults_to_full_and_parses_from_toml() {
+        let cfg = merge_config(
+            PartialConfig::default(),
+            PartialConfig::default(),
+            PartialConfig::default(),
+        )
+        .expect("merge");
+        assert_eq!(cfg.checkout_strategy, CheckoutStrategy::Full);
+
+        let project: PartialConfig =
+            toml::from_str("checkout_strategy = \"sparse\"").expect("parse project config");
+        let cfg = merge_config(PartialConfig::default(), project, PartialConfig::default())
+            .expect("merge");
+        assert_eq!(cfg.checkout_strategy, CheckoutStrategy::Sparse);
+
+        assert!(toml::from_str::<PartialConfig>("checkout_strategy = \"shallow\"").is_err());
+    }
+
+    #[test]
+    fn tag_formats_merge_per_package_with_project_precedence() {
+        let global: PartialConfig = toml::from_str(
+            "[tag_formats]\n\"npm:@acme/widgets\" = \"widgets-{version}\"\n\"crates:demo\" = \"demo-{version}\"\n",
+        )
+        .expect("parse global config");
+        let project: PartialConfig = toml::from_str(
+            "[tag_formats]\n\"npm:@acme/widgets\" = \"release/widgets/{version}\"\n",
+        )
+        .expect("parse project config");
+
+        let cfg = merge_config(global, project, PartialConfig::default()).expect("merge");
+        assert_eq!(
+            cfg.tag_formats,
+            BTreeMap::from([
+                (String::from("crates:demo"), String::from("demo-{version}")),
+                (
+                    String::from("npm:@acme/widgets"),
+                    String::from("release/widgets/{version}")
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn git_ssh_keys_replace_as_a_list_and_expand_home() {
+        let global: PartialConfig =
+            toml::from_str("git_ssh_keys = [\"~/.ssh/global\"]\n").expect("parse global config");
+        let project: PartialConfig = toml::from_str(
+            "git_ssh_keys = [\"~/.ssh/deploy\", \"/keys/ci\"]\n\n[git_url_rewrites]\n\"https://gitlab.internal/\" = \"ssh://git@gitlab.internal/\"\n",
+        )
+        .expect("parse project config");
+
+        let cfg = merge_config(global, project, PartialConfig::default()).expect("merge");
+        let home = dirs::home_dir().expect("home dir");
+        assert_eq!(
+            cfg.git_ssh_keys,
+            vec![home.join(".ssh/deploy"), PathBuf::from("/keys/ci")]
+        );
+        assert_eq!(
+            cfg.git_url_rewrites.get("https://gitlab.internal/"),
+            Some(&String::from("ssh://git@gitlab.internal/"))
+        );
+    }
+
+    #[test]
+    fn offline_follows_config_precedence_and_env_spellings() {
+        let project: PartialConfig =
+            toml::from_str("offline = true").expect("parse project config");
+        let env = PartialConfig {
+            offline: parse_bool("0"),
+            ..PartialConfig::default()
+        };
+        let cfg = merge_config(PartialConfig::default(), project, env).expect("merge");
+        assert!(!cfg.offline);
+
+        assert_eq!(parse_bool(" YES "), Some(true));
+        assert_eq!(parse_bool("sometimes"), None);
     }
 
     #[test]
@@ -3,11 +3,35 @@ use std::path::PathBuf;
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 
+/// Schemes of package specs: the package registries they are resolved against, plus
+/// `swift`, which SwiftPM resolves from source control and pkgrep from `Package.resolved`.
+pub const REGISTRY_SCHEMES: [&str; 11] = [
+    "npm",
+    "pypi",
+    "crates",
+    "go",
+    "rubygems",
+    "maven",
+    "packagist",
+    "nuget",
+    "hex",
+    "pub",
+    "swift",
+];
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Ecosystem {
     Npm,
     Pypi,
     Crates,
+    Go,
+    Rubygems,
+    Maven,
+    Packagist,
+    Nuget,
+    Hex,
+    Pub,
+    Swift,
     Git,
     Other(String),
 }
@@ -18,10 +42,22 @@ impl Ecosystem {
             Self::Npm => "npm",
             Self::Pypi => "pypi",
             Self::Crates => "crates",
+            Self::Go => "go",
+            Self::Rubygems => "rubygems",
+            Self::Maven => "maven",
+            Self::Packagist => "packagist",
+            Self::Nuget => "nuget",
+            Self::Hex => "hex",
+            Self::Pub => "pub",
+            Self::Swift => "swift",
             Self::Git => "git",
             Self::Other(scheme) => scheme.as_str(),
         }
     }
+
+    pub fn is_registry(&self) -> bool {
+        !matches!(self, Self::Git | Self::Other(_))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -30,6 +66,8 @@ pub enum SourceKind {
     Git {
         url: String,
         requested_revision: Option<String>,
+        /// Monorepo package directory named by a `//<dir>` suffix after the revision.
+        subdirectory: Option<String>,
     },
 }
 
@@ -41,6 +79,61 @@ pub struct DepSpec {
     pub source_kind: SourceKind,
 }
 
+/// How the version part of a registry dep spec selects a published version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VersionRequest {
+    Exact(String),
+    /// npm or Cargo semver range, or a PEP 440 specifier set for PyPI; resolved to
+    /// the highest published version it matches.
+    Range(String),
+    /// npm dist-tag such as `next` or `beta`.
+    DistTag(String),
+}
+
+impl DepSpec {
+    pub fn version_request(&self) -> Option<VersionRequest> {
+        let version = self.version.as_deref()?;
+        Some(classify_version(&self.ecosystem, version))
+    }
+}
+
+fn classify_version(ecosystem: &Ecosystem, version: &str) -> VersionRequest {
+    let request = version.to_string();
+    match ecosystem {
+        // Registries publish `18.3.1`, never `v18.3.1`, so exact versions drop the prefix.
+        Ecosystem::Npm | Ecosystem::Crates if is_exact_semver(version) => {
+            VersionRequest::Exact(version.strip_prefix('v').unwrap_or(version).to_string())
+        }
+        Ecosystem::Npm if looks_like_npm_range(version) => VersionRequest::Range(request),
+        Ecosystem::Npm => VersionRequest::DistTag(request),
+        // Cargo reads a bare `1.0` as `^1.0`, so anything short of a full version is a range.
+        Ecosystem::Crates => VersionRequest::Range(request),
+        Ecosystem::Pypi
+            if version.starts_with(['<', '>', '=', '!', '~']) || version.contains(',') =>
+        {
+            VersionRequest::Range(request)
+        }
+        // A bare `4.2.*` is shorthand for the `==4.2.*` prefix match.
+        Ecosystem::Pypi if version.contains('*') => VersionRequest::Range(format!("=={version}")),
+        _ => VersionRequest::Exact(request),
+    }
+}
+
+fn is_exact_semver(version: &str) -> bool {
+    semver::Version::parse(version.strip_prefix('v').unwrap_or(version)).is_ok()
+}
+
+// Dist-tags cannot be valid semver ranges, so anything that starts like a version,
+// comparator, or wildcard is treated as a range.
+fn looks_like_npm_range(version: &str) -> bool {
+    let unprefixed = version.strip_prefix('v').unwrap_or(version);
+    version.starts_with(['^', '~', '<', '>', '=', '*'])
+        || unprefixed.starts_with(|ch: char| ch.is_ascii_digit())
+        || matches!(version, "x" | "X")
+        || version.contains("||")
+        || version.contains(char::is_whitespace)
+}
+
 pub fn parse(input: &str) -> Result<DepSpec, String> {
     let (scheme, rest) = input
         .split_once(':')
@@ -66,6 +159,14 @@ pub fn parse(input: &str) -> Result<DepSpec, String> {
         "npm" => Ecosystem::Npm,
         "pypi" => Ecosystem::Pypi,
         "crates" => Ecosystem::Crates,
+        "go" => Ecosystem::Go,
+        "rubygems" => Ecosystem::Rubygems,
+        "maven" => Ecosystem::Maven,
+        "packagist" => Ecosystem::Packagist,
+        "nuget" => Ecosystem::Nuget,
+        "hex" => Ecosystem::Hex,
+        "pub" => Ecosystem::Pub,
+        "swift" => Ecosystem::Swift,
         other => Ecosystem::Other(other.to_string()),
     };
 
@@ -97,6 +198,13 @@ fn parse_git(input: &str, rest: &str) -> Result<DepSpec, String> {
     }
 
     let (url, requested_revision) = split_git_locator_and_revision(rest);
+    let (requested_revision, subdirectory) = match requested_revision {
+        Some(revision) => match split_revision_and_subdirectory(revision) {
+            Some((revision, subdirectory)) => (Some(revision), Some(subdirectory)),
+            None => (Some(revision), None),
+        },
+        None => (None, None),
+    };
 
     if url.is_empty() {
         return Err(format!(
@@ -108,6 +216,11 @@ fn parse_git(input: &str, rest: &str) -> Result<DepSpec, String> {
             "invalid dep spec '{input}': git revision must not be empty"
         ));
     }
+    if matches!(subdirectory, Some(subdirectory) if subdirectory.is_empty()) {
+        return Err(format!(
+            "invalid dep spec '{input}': git subdirectory after '//' must not be empty"
+        ));
+    }
 
     Ok(DepSpec {
         ecosystem: Ecosystem::Git,
@@ -116,10 +229,18 @@ fn parse_git(input: &str, rest: &str) -> Result<DepSpec, String> {
         source_kind: SourceKind::Git {
             url: url.to_string(),
             requested_revision: requested_revision.map(ToString::to_string),
+            subdirectory: subdirectory.map(ToString::to_string),
         },
     })
 }
 
+// Git refs cannot contain `//`, so the first one after the revision starts the
+// package subdirectory (`git:<url>@<rev>//<dir>`).
+fn split_revision_and_subdirectory(revision: &str) -> Option<(&str, &str)> {
+    let (revision, subdirectory) = revision.split_once("//")?;
+    Some((revision, subdirectory.trim_matches('/')))
+}
+
 fn has_explicit_empty_git_revision(rest: &str) -> bool {
     rest.ends_with('@') || rest.ends_with('#')
 }
@@ -179,7 +300,7 @@ pub fn cache_key(
 }
 
 pub fn link_path(ecosystem: &Ecosystem, locator: &str, version: &str) -> PathBuf {
-    let (parent_components, leaf_component) = split_locator_for_link(locator);
+    let (parent_components, leaf_component) = split_locator_for_link(ecosystem, locator);
     let mut path = PathBuf::from(".pkgrep")
         .join("deps")
         .join(ecosystem.as_str());
@@ -194,7 +315,7 @@ pub fn link_path(ecosystem: &Ecosystem, locator: &str, version: &str) -> PathBuf
 }
 
 pub fn link_path_prefix(ecosystem: &Ecosystem, locator: &str) -> PathBuf {
-    let (parent_components, leaf_component) = split_locator_for_link(locator);
+    let (parent_components, leaf_component) = split_locator_for_link(ecosystem, locator);
     let mut path = PathBuf::from(".pkgrep")
         .join("deps")
         .join(ecosystem.as_str());
@@ -204,8 +325,12 @@ pub fn link_path_prefix(ecosystem: &Ecosystem, locator: &str) -> PathBuf {
     path.join(format!("{leaf_component}@"))
 }
 
-fn split_locator_for_link(locator: &str) -> (Vec<String>, String) {
-    let mut components = locator_path_components(locator);
+fn split_locator_for_link(ecosystem: &Ecosystem, locator: &str) -> (Vec<String>, String) {
+    // Maven coordinates `<groupId>:<artifactId>` link as `<groupId>/<artifactId>`.
+    let mut components = match ecosystem {
+        Ecosystem::Maven => locator_path_components(&locator.replacen(':', "/", 1)),
+        _ => locator_path_components(locator),
+    };
     if components.is_empty() {
         return (Vec::new(), "_".to_string());
     }
@@ -287,6 +412,65 @@ mod tests {
         assert!(matches!(spec.source_kind, SourceKind::Registry));
     }
 
+    #[test]
+    fn classifies_registry_version_requests() {
+        let request = |spec: &str| parse(spec).expect("parse").version_request();
+
+        assert_eq!(
+            request("npm:react@18.3.1"),
+            Some(VersionRequest::Exact(String::from("18.3.1")))
+        );
+        assert_eq!(
+            request("npm:react@v18.3.1"),
+            Some(VersionRequest::Exact(String::from("18.3.1")))
+        );
+        assert_eq!(
+            request("npm:react@^18"),
+            Some(VersionRequest::Range(String::from("^18")))
+        );
+        assert_eq!(
+            request("npm:react@18.x"),
+            Some(VersionRequest::Range(String::from("18.x")))
+        );
+        assert_eq!(
+            request("npm:@types/node@>=20 <22"),
+            Some(VersionRequest::Range(String::from(">=20 <22")))
+        );
+        assert_eq!(
+            request("npm:typescript@next"),
+            Some(VersionRequest::DistTag(String::from("next")))
+        );
+        assert_eq!(
+            request("crates:serde@1.0.228"),
+            Some(VersionRequest::Exact(String::from("1.0.228")))
+        );
+        assert_eq!(
+            request("crates:serde@~1.0"),
+            Some(VersionRequest::Range(String::from("~1.0")))
+        );
+        assert_eq!(
+            request("crates:serde@1.0"),
+            Some(VersionRequest::Range(String::from("1.0")))
+        );
+        assert_eq!(
+            request("pypi:django@>=4.2,<5"),
+            Some(VersionRequest::Range(String::from(">=4.2,<5")))
+        );
+        assert_eq!(
+            request("pypi:django@4.2"),
+            Some(VersionRequest::Exact(String::from("4.2")))
+        );
+        assert_eq!(
+            request("pypi:django@4.2.*"),
+            Some(VersionRequest::Range(String::from("==4.2.*")))
+        );
+        assert_eq!(
+            request("go:golang.org/x/text@v0.14.0"),
+            Some(VersionRequest::Exact(String::from("v0.14.0")))
+        );
+        assert_eq!(request("npm:react"), None);
+    }
+
     #[test]
     fn parse_git_spec_without_revision() {
         let spec = parse("git:https://github.com/org/repo.git").expect("parse");
@@ -297,7 +481,8 @@ mod tests {
             spec.source_kind,
             SourceKind::Git {
                 ref url,
-                requested_revision: None
+                requested_revision: None,
+                subdirectory: None
             } if url == "https://github.com/org/repo.git"
         ));
     }
@@ -312,7 +497,8 @@ mod tests {
             spec.source_kind,
             SourceKind::Git {
                 ref url,
-                requested_revision: Some(ref requested_revision)
+                requested_revision: Some(ref requested_revision),
+                subdirectory: None
             } if url == "https://github.com/org/repo.git" && requested_revision == "a1b2c3"
         ));
     }
@@ -327,7 +513,8 @@ mod tests {
             spec.source_kind,
             SourceKind::Git {
                 ref url,
-                requested_revision: Some(ref requested_revision)
+                requested_revision: Some(ref requested_revision),
+                subdirectory: None
             } if url == "https://github.com/org/repo.git" && requested_revision == "release@2026.02"
         ));
     }
@@ -347,12 +534,47 @@ mod tests {
             spec.source_kind,
             SourceKind::Git {
                 ref url,
-                requested_revision: Some(ref requested_revision)
+                requested_revision: Some(ref requested_revision),
+                subdirectory: None
             } if url == "https://github.com/openworkflowdev/openworkflow.git"
                 && requested_revision == "openworkflow@0.7.3"
         ));
     }
 
+    #[test]
+    fn parse_git_spec_with_subdirectory_round_trips() {
+        let key = "git:https://github.com/babel/babel.git@deadbeef//packages/babel-core";
+        let spec = parse(key).expect("parse");
+        assert_eq!(spec.locator, "https://github.com/babel/babel.git");
+        assert_eq!(spec.version.as_deref(), Some("deadbeef"));
+        let SourceKind::Git {
+            url,
+            requested_revision: Some(requested_revision),
+            subdirectory: Some(subdirectory),
+        } = spec.source_kind
+        else {
+            panic!("expected git spec with subdirectory");
+        };
+        assert_eq!(
+            format!("git:{url}@{requested_revision}//{subdirectory}"),
+            key
+        );
+
+        let spec = parse("git:https://github.com/org/repo.git#release@2026.02//crates/cli/")
+            .expect("parse");
+        assert!(matches!(
+            spec.source_kind,
+            SourceKind::Git {
+                requested_revision: Some(ref requested_revision),
+                subdirectory: Some(ref subdirectory),
+                ..
+            } if requested_revision == "release@2026.02" && subdirectory == "crates/cli"
+        ));
+
+        let err = parse("git:https://github.com/org/repo.git@main//").expect_err("empty dir");
+        assert!(err.contains("subdirectory after '//' must not be empty"));
+    }
+
     #[test]
     fn parse_git_spec_with_empty_revision_fails() {
         let err = parse("git:https://github.com/org/repo.git@").expect_err("expected failure");
@@ -409,6 +631,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn link_path_for_maven_coordinates_nests_the_artifact_under_its_group() {
+        let path = link_path(&Ecosystem::Maven, "com.google.guava:guava", "33.0.0-jre");
+        assert_eq!(
+            path,
+            PathBuf::from(".pkgrep/deps/maven/com.google.guava/guava@33.0.0-jre")
+        );
+    }
+
     proptest! {
         #[test]
         fn normalize_is_deterministic(input in "[ -~]{0,128}") {
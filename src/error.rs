@@ -7,6 +7,9 @@ pub enum PkgrepError {
     #[error("invalid worker_pool_size: {0} (must be >= 1)")]
     InvalidWorkerPoolSize(usize),
 
+    #[error("invalid checkout_strategy: {0} (expected 'full' or 'sparse')")]
+    InvalidCheckoutStrategy(String),
+
     #[error("failed to read config file {path}: {source}")]
     ConfigRead {
         path: PathBuf,
@@ -21,4 +24,26 @@ pub enum PkgrepError {
 
     #[error("unable to derive a cache directory from the current environment")]
     MissingCacheDirectory,
+
+    #[error(
+        "timed out after {timeout_secs}s waiting for the {description} lock at {}; another pkgrep process is using it (retry later or raise lock_timeout_secs / PKGREP_LOCK_TIMEOUT_SECS)",
+        path.display()
+    )]
+    LockTimeout {
+        description: String,
+        path: PathBuf,
+        timeout_secs: u64,
+    },
+
+    #[error("git repository {remote_url} does not exist")]
+    RepositoryNotFound { remote_url: String },
+
+    #[error(
+        "failed to fetch requested revision '{revision}' from {remote_url} via targeted refspecs [{attempts}]"
+    )]
+    RevisionNotFound {
+        revision: String,
+        remote_url: String,
+        attempts: String,
+    },
 }
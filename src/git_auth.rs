@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Context;
+use git2::{Cred, CredentialType, RemoteCallbacks};
+use tracing::debug;
+
+use crate::config::Config;
+
+// Offered after the configured keys, in the order `ssh` itself tries them.
+const DEFAULT_SSH_KEYS: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+const DEFAULT_TOKEN_USERNAME: &str = "oauth2";
+
+/// How git remotes are reached: URL rewrites applied before a remote is opened, the
+/// credentials offered when a host asks for them, and whether remotes may be
+/// contacted at all.
+#[derive(Clone, Debug, Default)]
+pub struct GitAccess {
+    offline: bool,
+    url_rewrites: BTreeMap<String, String>,
+    ssh_keys: Vec<PathBuf>,
+    ssh_key_passphrase: Option<String>,
+    token: Option<HttpToken>,
+    askpass: Option<PathBuf>,
+}
+
+/// Token from `PKGREP_GIT_TOKEN`, sent as the password for HTTPS remotes.
+#[derive(Clone, Debug)]
+struct HttpToken {
+    username: String,
+    token: String,
+}
+
+/// One way of answering a credential request; each is tried at most once per
+/// connection so a rejected credential moves on instead of looping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CredentialMethod {
+    Username(String),
+    SshAgent(String),
+    SshKey { username: String, path: PathBuf },
+    Token,
+    Askpass,
+    CredentialHelper,
+    Default,
+}
+
+impl CredentialMethod {
+    fn describe(&self) -> String {
+        match self {
+            Self::Username(username) => format!("username {username}"),
+            Self::SshAgent(_) => String::from("ssh-agent"),
+            Self::SshKey { path, .. } => format!("ssh key {}", path.display()),
+            Self::Token => String::from("PKGREP_GIT_TOKEN"),
+            Self::Askpass => String::from("GIT_ASKPASS"),
+            Self::CredentialHelper => String::from("git credential helper"),
+            Self::Default => String::from("default credentials"),
+        }
+    }
+}
+
+impl GitAccess {
+    pub fn from_config(config: &Config) -> Self {
+        Self::load(config, dirs::home_dir().as_deref(), &|name| {
+            std::env::var(name).ok()
+        })
+    }
+
+    fn load(config: &Config, home: Option<&Path>, env: &dyn Fn(&str) -> Option<String>) -> Self {
+        let mut ssh_keys = config.git_ssh_keys.clone();
+        if let Some(ssh_dir) = home.map(|home| home.join(".ssh")) {
+            ssh_keys.extend(
+                DEFAULT_SSH_KEYS
+                    .iter()
+                    .map(|name| ssh_dir.join(name))
+                    .filter(|path| path.is_file() && !config.git_ssh_keys.contains(path)),
+            );
+        }
+
+        let non_empty = |name: &str| env(name).filter(|value| !value.is_empty());
+        let token = non_empty("PKGREP_GIT_TOKEN").map(|token| HttpToken {
+            username: non_empty("PKGREP_GIT_USERNAME")
+                .unwrap_or_else(|| DEFAULT_TOKEN_USERNAME.to_string()),
+            token,
+        });
+
+        Self {
+            offline: config.offline,
+            url_rewrites: config.git_url_rewrites.clone(),
+            ssh_keys,
+            ssh_key_passphrase: non_empty("PKGREP_GIT_SSH_KEY_PASSPHRASE"),
+            token,
+            askpass: non_empty("GIT_ASKPASS").map(PathBuf::from),
+        }
+    }
+
+    /// Offline mode: only what is already in the local mirrors can be used.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// `url` with the longest matching rewrite prefix replaced, as git applies
+    /// `insteadOf`.
+    pub fn rewrite_url(&self, url: &str) -> String {
+        self.url_rewrites
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, replacement)| format!("{replacement}{}", &url[prefix.len()..]))
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    fn credential(
+        &self,
+        method: &CredentialMethod,
+        url: &str,
+        username_from_url: Option<&str>,
+    ) -> anyhow::Result<Cred> {
+        let cred = match method {
+            CredentialMethod::Username(username) => Cred::username(username)?,
+            CredentialMethod::SshAgent(username) => Cred::ssh_key_from_agent(username)?,
+            CredentialMethod::SshKey { username, path } => {
+                Cred::ssh_key(username, None, path, self.ssh_key_passphrase.as_deref())?
+            }
+            CredentialMethod::Token => {
+                let token = self.token.as_ref().context("PKGREP_GIT_TOKEN is not set")?;
+                Cred::userpass_plaintext(&token.username, &token.token)?
+            }
+            CredentialMethod::Askpass => {
+                let program = self.askpass.as_ref().context("GIT_ASKPASS is not set")?;
+                let username = match username_from_url {
+                    Some(username) => username.to_string(),
+                    None => run_askpass(program, &format!("Username for '{url}': "))?,
+                };
+                let password = run_askpass(program, &format!("Password for '{url}': "))?;
+                Cred::userpass_plaintext(&username, &password)?
+            }
+            CredentialMethod::CredentialHelper => {
+                let config =
+                    git2::Config::open_default().context("failed to open git configuration")?;
+                Cred::credential_helper(&config, url, username_from_url)?
+            }
+            CredentialMethod::Default => Cred::default()?,
+        };
+        Ok(cred)
+    }
+
+    /// Callbacks that answer credential requests for a single connection.
+    pub fn remote_callbacks(&self) -> RemoteCallbacks<'static> {
+        let mut callbacks = RemoteCallbacks::new();
+        let mut attempts = CredentialAttempts {
+            access: self.clone(),
+            tried: Vec::new(),
+        };
+        callbacks.credentials(move |url, username_from_url, allowed| {
+            attempts.next(url, username_from_url, allowed)
+        });
+        callbacks
+    }
+}
+
+struct CredentialAttempts {
+    access: GitAccess,
+    tried: Vec<CredentialMethod>,
+}
+
+impl CredentialAttempts {
+    fn next(
+        &mut self,
+        url: &str,
+        username_from_url: Option<&str>,
+        allowed: CredentialType,
+    ) -> Result<Cred, git2::Error> {
+        while let Some(method) = self.next_method(username_from_url, allowed) {
+            match self.access.credential(&method, url, username_from_url) {
+                Ok(cred) => {
+                    debug!(url = url, method = %method.describe(), "offering git credentials");
+                    return Ok(cred);
+                }
+                Err(err) => {
+                    debug!(
+                        url = url,
+                        method = %method.describe(),
+                        error = %format!("{err:#}"),
+                        "git credential source unavailable"
+                    );
+                }
+            }
+        }
+
+        let tried = self
+            .tried
+            .iter()
+            .map(CredentialMethod::describe)
+            .collect::<Vec<_>>();
+        Err(git2::Error::from_str(&format!(
+            "no accepted credentials for {url} (tried: {}); configure ssh-agent, `git_ssh_keys`, a git credential helper, GIT_ASKPASS, or PKGREP_GIT_TOKEN",
+            if tried.is_empty() {
+                String::from("none")
+            } else {
+                tried.join(", ")
+            }
+        )))
+    }
+
+    fn next_method(
+        &mut self,
+        username_from_url: Option<&str>,
+        allowed: CredentialType,
+    ) -> Option<CredentialMethod> {
+        let username = username_from_url.unwrap_or("git").to_string();
+        let mut candidates = Vec::new();
+        if allowed.contains(CredentialType::USERNAME) {
+            candidates.push(CredentialMethod::Username(username.clone()));
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            candidates.push(CredentialMethod::SshAgent(username.clone()));
+            candidates.extend(
+                self.access
+                    .ssh_keys
+                    .iter()
+                    .map(|path| CredentialMethod::SshKey {
+                        username: username.clone(),
+                        path: path.clone(),
+                    }),
+            );
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if self.access.token.is_some() {
+                candidates.push(CredentialMethod::Token);
+            }
+            if self.access.askpass.is_some() {
+                candidates.push(CredentialMethod::Askpass);
+            }
+            candidates.push(CredentialMethod::CredentialHelper);
+        }
+        if allowed.contains(CredentialType::DEFAULT) {
+            candidates.push(CredentialMethod::Default);
+        }
+
+        let method = candidates
+            .into_iter()
+            .find(|candidate| !self.tried.contains(candidate))?;
+        self.tried.push(method.clone());
+        Some(method)
+    }
+}
+
+/// Runs an askpass program the way git does: the prompt is the only argument and the
+/// answer is the first line of its output.
+fn run_askpass(program: &Path, prompt: &str) -> anyhow::Result<String> {
+    let output = Command::new(program)
+        .arg(prompt)
+        .output()
+        .with_context(|| format!("failed to run askpass program {}", program.display()))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "askpass program {} exited with {}",
+            program.display(),
+            output.status
+        );
+    }
+    let answer = String::from_utf8(output.stdout).context("askpass output is not UTF-8")?;
+    Ok(answer.lines().next().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access() -> GitAccess {
+        GitAccess {
+            offline: false,
+            url_rewrites: BTreeMap::from([
+                (
+                    String::from("https://gitlab.internal/"),
+                    String::from("ssh://git@gitlab.internal/"),
+                ),
+                (
+                    String::from("https://gitlab.internal/mirrors/"),
+                    String::from("https://mirror.internal/"),
+                ),
+            ]),
+            ssh_keys: vec![PathBuf::from("/keys/deploy"), PathBuf::from("/keys/ci")],
+            ssh_key_passphrase: None,
+            token: Some(HttpToken {
+                username: String::from(DEFAULT_TOKEN_USERNAME),
+                token: String::from("secret"),
+            }),
+            askpass: Some(PathBuf::from("/usr/bin/askpass")),
+        }
+    }
+
+    fn fresh_attempts(access: GitAccess) -> CredentialAttempts {
+        CredentialAttempts {
+            access,
+            tried: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rewrites_urls_with_the_longest_matching_prefix() {
+        let access = access();
+        assert_eq!(
+            access.rewrite_url("https://gitlab.internal/team/app.git"),
+            "ssh://git@gitlab.internal/team/app.git"
+        );
+        assert_eq!(
+            access.rewrite_url("https://gitlab.internal/mirrors/lib.git"),
+            "https://mirror.internal/lib.git"
+        );
+        assert_eq!(
+            access.rewrite_url("https://github.com/acme/app.git"),
+            "https://github.com/acme/app.git"
+        );
+    }
+
+    #[test]
+    fn ssh_requests_try_the_agent_then_each_key_once() {
+        let mut attempts = fresh_attempts(access());
+        let mut methods = Vec::new();
+        while let Some(method) = attempts.next_method(Some("deploy"), CredentialType::SSH_KEY) {
+            methods.push(method);
+        }
+
+        assert_eq!(
+            methods,
+            vec![
+                CredentialMethod::SshAgent(String::from("deploy")),
+                CredentialMethod::SshKey {
+                    username: String::from("deploy"),
+                    path: PathBuf::from("/keys/deploy"),
+                },
+                CredentialMethod::SshKey {
+                    username: String::from("deploy"),
+                    path: PathBuf::from("/keys/ci"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn https_requests_prefer_the_env_token_then_askpass_then_the_helper() {
+        let mut attempts = fresh_attempts(access());
+        let mut methods = Vec::new();
+        while let Some(method) = attempts.next_method(None, CredentialType::USER_PASS_PLAINTEXT) {
+            methods.push(method);
+        }
+        assert_eq!(
+            methods,
+            vec![
+                CredentialMethod::Token,
+                CredentialMethod::Askpass,
+                CredentialMethod::CredentialHelper,
+            ]
+        );
+
+        let mut attempts = fresh_attempts(GitAccess::default());
+        assert_eq!(
+            attempts.next_method(None, CredentialType::USER_PASS_PLAINTEXT),
+            Some(CredentialMethod::CredentialHelper)
+        );
+        assert_eq!(
+            attempts.next_method(None, CredentialType::USER_PASS_PLAINTEXT),
+            None
+        );
+    }
+
+    #[test]
+    fn loads_token_username_and_askpass_from_env() {
+        let config = Config {
+            cache_dir: PathBuf::from("/cache"),
+            worker_pool_size: 1,
+            lock_timeout_secs: 5,
+            checkout_strategy: crate::config::CheckoutStrategy::Full,
+            tag_formats: BTreeMap::new(),
+            git_ssh_keys: vec![PathBuf::from("/keys/deploy")],
+            git_url_rewrites: BTreeMap::new(),
+            offline: false,
+        };
+        let access = GitAccess::load(&config, None, &|name| match name {
+            "PKGREP_GIT_TOKEN" => Some(String::from("glpat-secret")),
+            "PKGREP_GIT_USERNAME" => Some(String::new()),
+            "GIT_ASKPASS" => Some(String::from("/usr/libexec/askpass")),
+            _ => None,
+        });
+
+        let token = access.token.expect("token");
+        assert_eq!(token.username, DEFAULT_TOKEN_USERNAME);
+        assert_eq!(token.token, "glpat-secret");
+        assert_eq!(access.askpass, Some(PathBuf::from("/usr/libexec/askpass")));
+        assert_eq!(access.ssh_keys, vec![PathBuf::from("/keys/deploy")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn askpass_answer_is_the_first_output_line() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let program = temp.path().join("askpass.sh");
+        std::fs::write(&program, "#!/bin/sh\necho \"answer to $1\"\necho ignored\n")
+            .expect("write askpass");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod askpass");
+
+        assert_eq!(
+            run_askpass(&program, "Password:").expect("run askpass"),
+            "answer to Password:"
+        );
+    }
+}
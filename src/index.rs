@@ -1,13 +1,16 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::Context;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::cache_lock;
 use crate::depspec::{self, Ecosystem};
-use crate::source::{GitPullTarget, MaterializedSource};
+use crate::release_tags::ReleaseTag;
+use crate::source::{MaterializedSource, PullTarget};
 
 const PROJECT_MANIFEST_SCHEMA_VERSION: u8 = 1;
 const GLOBAL_REF_INDEX_SCHEMA_VERSION: u8 = 1;
@@ -33,6 +36,14 @@ pub enum RegistrySpecEcosystem {
     Npm,
     Pypi,
     Crates,
+    Go,
+    Rubygems,
+    Maven,
+    Packagist,
+    Nuget,
+    Hex,
+    Pub,
+    Swift,
 }
 
 impl RegistrySpecEcosystem {
@@ -41,6 +52,14 @@ impl RegistrySpecEcosystem {
             Ecosystem::Npm => Some(Self::Npm),
             Ecosystem::Pypi => Some(Self::Pypi),
             Ecosystem::Crates => Some(Self::Crates),
+            Ecosystem::Go => Some(Self::Go),
+            Ecosystem::Rubygems => Some(Self::Rubygems),
+            Ecosystem::Maven => Some(Self::Maven),
+            Ecosystem::Packagist => Some(Self::Packagist),
+            Ecosystem::Nuget => Some(Self::Nuget),
+            Ecosystem::Hex => Some(Self::Hex),
+            Ecosystem::Pub => Some(Self::Pub),
+            Ecosystem::Swift => Some(Self::Swift),
             _ => None,
         }
     }
@@ -52,6 +71,9 @@ pub struct RegistrySpecRef {
     pub name: String,
     #[serde(default)]
     pub package_version: Option<String>,
+    /// Version range or dist-tag the package version was resolved from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_request: Option<String>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -60,18 +82,39 @@ pub struct LinkRecordMetadata {
     pub registry_refs: BTreeSet<RegistrySpecRef>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+/// Outcome of the last `pkgrep verify` against the published package artifact.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    pub package_version: String,
+    pub artifact_url: String,
+    /// Share of published files that are byte-identical in the linked checkout.
+    pub match_score: f64,
+    pub identical_files: usize,
+    pub missing_files: usize,
+    pub different_files: usize,
+    pub extra_files: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct ProjectLinkSummary {
     pub dep_spec: String,
     pub link_path: PathBuf,
     pub cache_key: String,
     pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subdirectory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_tag: Option<ReleaseTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<VerificationRecord>,
 }
 
 #[derive(Clone, Debug)]
 pub struct LinkMatch {
     pub dep_spec: String,
     pub link_path: PathBuf,
+    /// Package version recorded for the matched registry ref, when known.
+    pub package_version: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -84,10 +127,18 @@ struct ProjectManifest {
 struct ProjectManifestEntry {
     link_path: String,
     cache_key: String,
+    /// Monorepo package directory the link points at, relative to the checkout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subdirectory: Option<String>,
     #[serde(default)]
     aliases: BTreeSet<String>,
     #[serde(default)]
     registry_refs: BTreeSet<RegistrySpecRef>,
+    /// Release tag a version-derived revision was checked out from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    release_tag: Option<ReleaseTag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<VerificationRecord>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -111,7 +162,11 @@ pub fn global_ref_index_path(cache_root: &Path) -> PathBuf {
     cache_root.join("index").join("project_refs.json")
 }
 
-pub fn reconcile_global_index(cache_root: &Path) -> anyhow::Result<ReconcileGlobalIndexResult> {
+pub fn reconcile_global_index(
+    cache_root: &Path,
+    lock_timeout: Duration,
+) -> anyhow::Result<ReconcileGlobalIndexResult> {
+    let _index_lock = lock_global_ref_index(cache_root, lock_timeout)?;
     let path = global_ref_index_path(cache_root);
     let mut index: GlobalRefIndex = read_json_or_default(&path)?;
     ensure_global_ref_index_defaults(&mut index);
@@ -167,9 +222,10 @@ pub fn reconcile_global_index(cache_root: &Path) -> anyhow::Result<ReconcileGlob
 pub fn record_link_with_metadata(
     cwd: &Path,
     cache_root: &Path,
-    target: &GitPullTarget,
+    target: &PullTarget,
     materialized: &MaterializedSource,
     metadata: &LinkRecordMetadata,
+    lock_timeout: Duration,
 ) -> anyhow::Result<()> {
     let dep_spec = dep_spec(target);
     let project_root = normalize_project_root(cwd);
@@ -186,9 +242,14 @@ pub fn record_link_with_metadata(
             registry_refs.insert(inferred_registry_ref);
         }
 
+        // A verification only describes the checkout it was run against.
+        let mut verification = None;
         if let Some(existing_entry) = manifest.entries.get(&dep_spec) {
             aliases.extend(existing_entry.aliases.iter().cloned());
             registry_refs.extend(existing_entry.registry_refs.iter().cloned());
+            if existing_entry.cache_key == materialized.cache_key {
+                verification = existing_entry.verification.clone();
+            }
         }
 
         manifest.entries.insert(
@@ -196,13 +257,16 @@ pub fn record_link_with_metadata(
             ProjectManifestEntry {
                 link_path,
                 cache_key: materialized.cache_key.clone(),
+                subdirectory: materialized.linked_subdirectory.clone(),
+                release_tag: materialized.release_tag.clone(),
                 aliases,
                 registry_refs,
+                verification,
             },
         );
     })?;
 
-    update_global_ref_index(cache_root, |index| {
+    update_global_ref_index(cache_root, lock_timeout, |index| {
         let entry = index
             .entries
             .entry(materialized.cache_key.clone())
@@ -237,7 +301,8 @@ pub fn find_registry_link_matches(
     let mut matches = Vec::new();
     for (dep_spec, entry) in manifest.entries {
         let alias_match = entry.aliases.contains(original_dep_spec);
-        let registry_ref_match = entry.registry_refs.iter().any(|ref_spec| {
+        // Versionless refs sort first; prefer one that records the package version.
+        let matching_registry_ref = entry.registry_refs.iter().rev().find(|ref_spec| {
             if ref_spec.ecosystem != registry_ecosystem || ref_spec.name != locator {
                 return false;
             }
@@ -248,9 +313,12 @@ pub fn find_registry_link_matches(
             }
         });
 
-        if !alias_match && !registry_ref_match {
+        if !alias_match && matching_registry_ref.is_none() {
             continue;
         }
+        let package_version = matching_registry_ref
+            .and_then(|ref_spec| ref_spec.package_version.clone())
+            .or_else(|| version.map(str::to_string));
 
         let absolute_link_path = cwd.join(&entry.link_path);
         if !absolute_link_path.exists() {
@@ -260,6 +328,7 @@ pub fn find_registry_link_matches(
         matches.push(LinkMatch {
             dep_spec,
             link_path: absolute_link_path,
+            package_version,
         });
     }
 
@@ -293,6 +362,7 @@ pub fn find_git_link_matches(
         matches.push(LinkMatch {
             dep_spec,
             link_path: absolute_link_path,
+            package_version: None,
         });
     }
 
@@ -300,6 +370,25 @@ pub fn find_git_link_matches(
     Ok(matches)
 }
 
+/// The link recorded under `dep_spec`, either as its manifest key or as an alias.
+pub fn find_dep_spec_link(cwd: &Path, dep_spec: &str) -> anyhow::Result<Option<LinkMatch>> {
+    let path = project_manifest_path(cwd);
+    let mut manifest: ProjectManifest = read_json_or_default(&path)?;
+    ensure_project_manifest_defaults(&mut manifest);
+
+    Ok(manifest.entries.into_iter().find_map(|(key, entry)| {
+        if key != dep_spec && !entry.aliases.contains(dep_spec) {
+            return None;
+        }
+        let link_path = cwd.join(&entry.link_path);
+        link_path.exists().then_some(LinkMatch {
+            dep_spec: key,
+            link_path,
+            package_version: None,
+        })
+    }))
+}
+
 pub fn list_project_links(cwd: &Path) -> anyhow::Result<Vec<ProjectLinkSummary>> {
     let path = project_manifest_path(cwd);
     let mut manifest: ProjectManifest = read_json_or_default(&path)?;
@@ -319,6 +408,9 @@ pub fn list_project_links(cwd: &Path) -> anyhow::Result<Vec<ProjectLinkSummary>>
                 link_path,
                 cache_key: entry.cache_key,
                 aliases: entry.aliases.into_iter().collect(),
+                subdirectory: entry.subdirectory,
+                release_tag: entry.release_tag,
+                verification: entry.verification,
             })
         })
         .collect::<Vec<_>>();
@@ -326,28 +418,67 @@ pub fn list_project_links(cwd: &Path) -> anyhow::Result<Vec<ProjectLinkSummary>>
     Ok(summaries)
 }
 
+pub fn record_verification(
+    cwd: &Path,
+    dep_spec: &str,
+    verification: VerificationRecord,
+) -> anyhow::Result<()> {
+    let mut recorded = false;
+    update_project_manifest(cwd, |manifest| {
+        if let Some(entry) = manifest.entries.get_mut(dep_spec) {
+            entry.verification = Some(verification);
+            recorded = true;
+        }
+    })?;
+
+    if !recorded {
+        anyhow::bail!("dependency is not linked in this project: {}", dep_spec);
+    }
+    Ok(())
+}
+
 pub fn record_unlink(
     cwd: &Path,
     cache_root: &Path,
     removed_link_path: &Path,
     symlink_target: Option<&Path>,
+    lock_timeout: Duration,
 ) -> anyhow::Result<()> {
     let removed_link = path_for_manifest(cwd, removed_link_path);
+    let mut removed_cache_keys = BTreeSet::new();
+    let mut remaining_cache_keys = BTreeSet::new();
     update_project_manifest(cwd, |manifest| {
-        manifest
-            .entries
-            .retain(|_, entry| entry.link_path != removed_link);
+        manifest.entries.retain(|_, entry| {
+            if entry.link_path == removed_link {
+                removed_cache_keys.insert(entry.cache_key.clone());
+                false
+            } else {
+                remaining_cache_keys.insert(entry.cache_key.clone());
+                true
+            }
+        });
     })?;
 
-    let Some(symlink_target) = symlink_target else {
-        return Ok(());
+    // Links into a monorepo subdirectory point below the checkout root, so prefer
+    // the cache key recorded in the manifest over one derived from the link target.
+    let cache_key = match removed_cache_keys.into_iter().next() {
+        Some(cache_key) => cache_key,
+        None => {
+            let Some(symlink_target) = symlink_target else {
+                return Ok(());
+            };
+            let Some(cache_key) = cache_key_from_checkout_path(cache_root, symlink_target) else {
+                return Ok(());
+            };
+            cache_key
+        }
     };
-    let Some(cache_key) = cache_key_from_checkout_path(cache_root, symlink_target) else {
+    if remaining_cache_keys.contains(&cache_key) {
         return Ok(());
-    };
+    }
     let project_root = normalize_project_root(cwd);
 
-    update_global_ref_index(cache_root, |index| {
+    update_global_ref_index(cache_root, lock_timeout, |index| {
         if let Some(entry) = index.entries.get_mut(&cache_key) {
             entry.projects.remove(&project_root);
             if entry.projects.is_empty() {
@@ -373,8 +504,10 @@ fn update_project_manifest(
 
 fn update_global_ref_index(
     cache_root: &Path,
+    lock_timeout: Duration,
     mutator: impl FnOnce(&mut GlobalRefIndex),
 ) -> anyhow::Result<()> {
+    let _index_lock = lock_global_ref_index(cache_root, lock_timeout)?;
     let path = global_ref_index_path(cache_root);
     let mut index: GlobalRefIndex = read_json_or_default(&path)?;
     ensure_global_ref_index_defaults(&mut index);
@@ -383,6 +516,20 @@ fn update_global_ref_index(
     Ok(())
 }
 
+// The index is shared by every project using this cache, so each read-modify-write
+// holds the index lock to avoid dropping another process's project references.
+fn lock_global_ref_index(
+    cache_root: &Path,
+    lock_timeout: Duration,
+) -> anyhow::Result<cache_lock::CacheLock> {
+    cache_lock::acquire(
+        cache_root,
+        &cache_root.join("index"),
+        "global ref index",
+        lock_timeout,
+    )
+}
+
 fn read_json_or_default<T>(path: &Path) -> anyhow::Result<T>
 where
     T: Default + DeserializeOwned,
@@ -405,7 +552,7 @@ fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()>
     }
 
     let payload = serde_json::to_vec_pretty(value).context("failed to serialize JSON payload")?;
-    let temp_path = path.with_extension("tmp");
+    let temp_path = path.with_extension(format!("tmp.{}", std::process::id()));
     fs::write(&temp_path, payload).with_context(|| {
         format!(
             "failed to write temporary JSON file {}",
@@ -417,8 +564,25 @@ fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()>
     Ok(())
 }
 
-fn dep_spec(target: &GitPullTarget) -> String {
-    format!("git:{}@{}", target.git_url, target.requested_revision)
+// Packages from one monorepo revision share a repository URL and revision, so the
+// subdirectory is appended (`//<dir>`) to keep their manifest entries apart;
+// `depspec::parse` reads the suffix back.
+fn dep_spec(target: &PullTarget) -> String {
+    match target {
+        PullTarget::Git(target) => match &target.subdirectory {
+            Some(subdirectory) => format!(
+                "git:{}@{}//{}",
+                target.git_url, target.requested_revision, subdirectory
+            ),
+            None => format!("git:{}@{}", target.git_url, target.requested_revision),
+        },
+        PullTarget::Archive(target) => format!(
+            "{}:{}@{}",
+            target.ecosystem.as_str(),
+            target.package_name,
+            target.package_version
+        ),
+    }
 }
 
 fn normalize_project_root(cwd: &Path) -> String {
@@ -476,6 +640,14 @@ fn infer_registry_ref_from_cache_key(cache_key: &str) -> Option<RegistrySpecRef>
         "npm" => RegistrySpecEcosystem::Npm,
         "pypi" => RegistrySpecEcosystem::Pypi,
         "crates" => RegistrySpecEcosystem::Crates,
+        "go" => RegistrySpecEcosystem::Go,
+        "rubygems" => RegistrySpecEcosystem::Rubygems,
+        "maven" => RegistrySpecEcosystem::Maven,
+        "packagist" => RegistrySpecEcosystem::Packagist,
+        "nuget" => RegistrySpecEcosystem::Nuget,
+        "hex" => RegistrySpecEcosystem::Hex,
+        "pub" => RegistrySpecEcosystem::Pub,
+        "swift" => RegistrySpecEcosystem::Swift,
         _ => return None,
     };
 
@@ -484,17 +656,22 @@ fn infer_registry_ref_from_cache_key(cache_key: &str) -> Option<RegistrySpecRef>
         return None;
     }
 
+    // Only version-derived revisions double as the package version; a pinned
+    // commit (npm `gitHead`, crates `.cargo_vcs_info.json`, Go origins, a nuspec
+    // `<repository commit>`) or a POM `<scm><tag>` does not.
     let package_version = match ecosystem {
-        RegistrySpecEcosystem::Npm => None,
-        RegistrySpecEcosystem::Pypi => {
-            if requested_revision.is_empty() {
-                None
-            } else {
-                Some(requested_revision.to_string())
-            }
+        RegistrySpecEcosystem::Npm | RegistrySpecEcosystem::Go | RegistrySpecEcosystem::Maven => {
+            None
         }
-        RegistrySpecEcosystem::Crates => {
-            if requested_revision.is_empty() {
+        RegistrySpecEcosystem::Pypi
+        | RegistrySpecEcosystem::Crates
+        | RegistrySpecEcosystem::Rubygems
+        | RegistrySpecEcosystem::Packagist
+        | RegistrySpecEcosystem::Nuget
+        | RegistrySpecEcosystem::Hex
+        | RegistrySpecEcosystem::Pub
+        | RegistrySpecEcosystem::Swift => {
+            if requested_revision.is_empty() || looks_like_commit_id(requested_revision) {
                 None
             } else {
                 Some(requested_revision.to_string())
@@ -506,9 +683,14 @@ fn infer_registry_ref_from_cache_key(cache_key: &str) -> Option<RegistrySpecRef>
         ecosystem,
         name: locator,
         package_version,
+        version_request: None,
     })
 }
 
+fn looks_like_commit_id(revision: &str) -> bool {
+    revision.len() == 40 && revision.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
 fn looks_like_git_locator(locator: &str) -> bool {
     locator.contains("://")
         || locator.starts_with("git@")
@@ -13,9 +13,24 @@ pub enum InstalledVersionSource {
     PackageLock,
     PnpmLock,
     YarnLock,
+    BunLock,
     PackageJson,
     UvLock,
+    PoetryLock,
+    PdmLock,
+    PipfileLock,
     CargoLock,
+    GoMod,
+    GoSum,
+    GemfileLock,
+    GradleLockfile,
+    GradleVersionCatalog,
+    PomXml,
+    ComposerLock,
+    NugetPackagesLock,
+    DirectoryPackagesProps,
+    MixLock,
+    PubspecLock,
 }
 
 impl InstalledVersionSource {
@@ -25,9 +40,24 @@ impl InstalledVersionSource {
             Self::PackageLock => "package-lock.json",
             Self::PnpmLock => "pnpm-lock.yaml",
             Self::YarnLock => "yarn.lock",
+            Self::BunLock => "bun.lock",
             Self::PackageJson => "package.json",
             Self::UvLock => "uv.lock",
+            Self::PoetryLock => "poetry.lock",
+            Self::PdmLock => "pdm.lock",
+            Self::PipfileLock => "Pipfile.lock",
             Self::CargoLock => "Cargo.lock",
+            Self::GoMod => "go.mod",
+            Self::GoSum => "go.sum",
+            Self::GemfileLock => "Gemfile.lock",
+            Self::GradleLockfile => "gradle.lockfile",
+            Self::GradleVersionCatalog => "gradle/libs.versions.toml",
+            Self::PomXml => "pom.xml",
+            Self::ComposerLock => "composer.lock",
+            Self::NugetPackagesLock => "packages.lock.json",
+            Self::DirectoryPackagesProps => "Directory.Packages.props",
+            Self::MixLock => "mix.lock",
+            Self::PubspecLock => "pubspec.lock",
         }
     }
 }
@@ -97,6 +127,12 @@ pub fn detect_installed_npm_version(
                 source: InstalledVersionSource::YarnLock,
             })
         })
+        .or_else(|| {
+            version_from_bun_lock(cwd, package_name).map(|version| InstalledVersion {
+                version,
+                source: InstalledVersionSource::BunLock,
+            })
+        })
         .or_else(|| {
             version_from_package_json(cwd, package_name).map(|version| InstalledVersion {
                 version,
@@ -109,57 +145,267 @@ pub fn detect_installed_pypi_version(
     cwd: &Path,
     package_name: &str,
 ) -> Result<Option<InstalledVersion>> {
-    Ok(
-        version_from_uv_lock(cwd, package_name).map(|version| InstalledVersion {
-            version,
-            source: InstalledVersionSource::UvLock,
-        }),
-    )
+    let lockfiles = [
+        (providers::ProviderKind::Uv, InstalledVersionSource::UvLock),
+        (
+            providers::ProviderKind::Poetry,
+            InstalledVersionSource::PoetryLock,
+        ),
+        (
+            providers::ProviderKind::Pdm,
+            InstalledVersionSource::PdmLock,
+        ),
+        (
+            providers::ProviderKind::Pipfile,
+            InstalledVersionSource::PipfileLock,
+        ),
+    ];
+
+    Ok(lockfiles.into_iter().find_map(|(provider, source)| {
+        version_from_python_lock(cwd, provider, &source, package_name)
+            .map(|version| InstalledVersion { version, source })
+    }))
 }
 
 pub fn detect_installed_crates_version(
     cwd: &Path,
     package_name: &str,
 ) -> Result<Option<InstalledVersion>> {
-    let lock_path = cwd.join("Cargo.lock");
-    if !lock_path.exists() {
-        return Ok(None);
-    }
-
-    let deps = providers::parse_provider_input(&providers::ProviderInputMatch {
-        provider: providers::ProviderKind::Cargo,
-        path: lock_path,
-    })
-    .map_err(|err| {
-        anyhow::anyhow!("failed to parse Cargo.lock for crates version detection: {err}")
-    })?;
-
     let normalized_package_name = normalize_crates_package_name(package_name);
-    let versions = deps
-        .into_iter()
-        .filter(|dep| {
+    locked_version(
+        cwd,
+        &[(
+            providers::ProviderKind::Cargo,
+            InstalledVersionSource::CargoLock,
+        )],
+        "crates",
+        package_name,
+        |dep| {
             dep.ecosystem == providers::ProviderEcosystem::Crates
                 && normalize_crates_package_name(&dep.name) == normalized_package_name
                 && dep.git_hint.is_none()
-        })
-        .map(|dep| dep.version)
-        .collect::<std::collections::BTreeSet<_>>();
+        },
+    )
+}
 
-    match versions.len() {
-        0 => Ok(None),
-        1 => Ok(versions.into_iter().next().map(|version| InstalledVersion {
-            version,
-            source: InstalledVersionSource::CargoLock,
-        })),
-        _ => {
-            let joined = versions.into_iter().collect::<Vec<_>>().join(", ");
-            anyhow::bail!(
-                "multiple installed crates versions detected for {} in Cargo.lock: {}; use an explicit version",
-                package_name,
-                joined
-            );
+/// Reads the version Bundler locked from a `GEM` block of `Gemfile.lock`; gems
+/// locked from `GIT` or `PATH` sources have no registry version to pull.
+pub fn detect_installed_rubygems_version(
+    cwd: &Path,
+    gem_name: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[(
+            providers::ProviderKind::Bundler,
+            InstalledVersionSource::GemfileLock,
+        )],
+        "rubygems",
+        gem_name,
+        |dep| {
+            dep.ecosystem == providers::ProviderEcosystem::Rubygems
+                && dep.name == gem_name
+                && dep.git_hint.is_none()
+        },
+    )
+}
+
+/// Reads the version locked by a `{:hex, ...}` entry of `mix.lock`; `{:git, ...}`
+/// entries have no registry version to pull.
+pub fn detect_installed_hex_version(
+    cwd: &Path,
+    package_name: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[(
+            providers::ProviderKind::Mix,
+            InstalledVersionSource::MixLock,
+        )],
+        "hex",
+        package_name,
+        |dep| {
+            dep.ecosystem == providers::ProviderEcosystem::Hex
+                && dep.name == package_name
+                && dep.git_hint.is_none()
+        },
+    )
+}
+
+/// Reads the version locked by a `hosted` entry of `pubspec.lock`; `git` entries are
+/// pulled from their own repository instead.
+pub fn detect_installed_pub_version(
+    cwd: &Path,
+    package_name: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[(
+            providers::ProviderKind::PubspecLock,
+            InstalledVersionSource::PubspecLock,
+        )],
+        "pub",
+        package_name,
+        |dep| {
+            dep.ecosystem == providers::ProviderEcosystem::Pub
+                && dep.name == package_name
+                && dep.git_hint.is_none()
+        },
+    )
+}
+
+/// Reads the version Composer locked. Unlike Bundler's `GIT` blocks, git sources
+/// here do not mean an unpublished package: Composer records the git source of
+/// every package it can install from VCS, tagged releases included.
+pub fn detect_installed_packagist_version(
+    cwd: &Path,
+    package_name: &str,
+) -> Result<Option<InstalledVersion>> {
+    // Composer package names are case-insensitive and locked in lowercase.
+    locked_version(
+        cwd,
+        &[(
+            providers::ProviderKind::Composer,
+            InstalledVersionSource::ComposerLock,
+        )],
+        "packagist",
+        package_name,
+        |dep| {
+            dep.ecosystem == providers::ProviderEcosystem::Packagist
+                && dep.name.eq_ignore_ascii_case(package_name)
+        },
+    )
+}
+
+/// Prefers the version Gradle locked in `gradle.lockfile`, then the version catalog,
+/// then the versions declared in `pom.xml`. `coordinates` is `<groupId>:<artifactId>`.
+pub fn detect_installed_maven_version(
+    cwd: &Path,
+    coordinates: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[
+            (
+                providers::ProviderKind::GradleLockfile,
+                InstalledVersionSource::GradleLockfile,
+            ),
+            (
+                providers::ProviderKind::GradleVersionCatalog,
+                InstalledVersionSource::GradleVersionCatalog,
+            ),
+            (providers::ProviderKind::Pom, InstalledVersionSource::PomXml),
+        ],
+        "maven",
+        coordinates,
+        |dep| dep.ecosystem == providers::ProviderEcosystem::Maven && dep.name == coordinates,
+    )
+}
+
+/// Prefers the version NuGet restored in `packages.lock.json`, then the one pinned
+/// centrally in `Directory.Packages.props`. Package ids are case-insensitive.
+pub fn detect_installed_nuget_version(
+    cwd: &Path,
+    package_id: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[
+            (
+                providers::ProviderKind::NugetPackagesLock,
+                InstalledVersionSource::NugetPackagesLock,
+            ),
+            (
+                providers::ProviderKind::DirectoryPackagesProps,
+                InstalledVersionSource::DirectoryPackagesProps,
+            ),
+        ],
+        "nuget",
+        package_id,
+        |dep| {
+            dep.ecosystem == providers::ProviderEcosystem::Nuget
+                && dep.name.eq_ignore_ascii_case(package_id)
+        },
+    )
+}
+
+/// Prefers the `go.mod` requirement (after `replace` directives), then a single
+/// downloaded version recorded in `go.sum`.
+pub fn detect_installed_go_version(
+    cwd: &Path,
+    module_path: &str,
+) -> Result<Option<InstalledVersion>> {
+    locked_version(
+        cwd,
+        &[
+            (
+                providers::ProviderKind::GoMod,
+                InstalledVersionSource::GoMod,
+            ),
+            (
+                providers::ProviderKind::GoSum,
+                InstalledVersionSource::GoSum,
+            ),
+        ],
+        "go",
+        module_path,
+        |dep| dep.ecosystem == providers::ProviderEcosystem::Go && dep.name == module_path,
+    )
+}
+
+/// Tries each project file in `sources` in turn and returns the first version it
+/// locks for the package `name_matches` picks out; a file that locks several
+/// versions of it is ambiguous and fails. `label` names the ecosystem in errors.
+fn locked_version(
+    cwd: &Path,
+    sources: &[(providers::ProviderKind, InstalledVersionSource)],
+    label: &str,
+    package_name: &str,
+    name_matches: impl Fn(&providers::NormalizedDependency) -> bool,
+) -> Result<Option<InstalledVersion>> {
+    for (provider, source) in sources {
+        let path = cwd.join(source.as_str());
+        if !path.exists() {
+            continue;
+        }
+
+        let deps = providers::parse_provider_input(&providers::ProviderInputMatch {
+            provider: provider.clone(),
+            path,
+        })
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "failed to parse {} for {label} version detection: {err}",
+                source.as_str()
+            )
+        })?;
+        let versions = deps
+            .into_iter()
+            .filter(|dep| name_matches(dep))
+            .map(|dep| dep.version)
+            .collect::<std::collections::BTreeSet<_>>();
+
+        match versions.len() {
+            0 => continue,
+            1 => {
+                return Ok(versions.into_iter().next().map(|version| InstalledVersion {
+                    version,
+                    source: source.clone(),
+                }));
+            }
+            _ => {
+                let joined = versions.into_iter().collect::<Vec<_>>().join(", ");
+                anyhow::bail!(
+                    "multiple installed {label} versions detected for {} in {}: {}; use an explicit version",
+                    package_name,
+                    source.as_str(),
+                    joined
+                );
+            }
         }
     }
+
+    Ok(None)
 }
 
 fn version_from_node_modules(cwd: &Path, package_name: &str) -> Option<String> {
@@ -215,38 +461,35 @@ fn version_from_pnpm_lock(cwd: &Path, package_name: &str) -> Option<String> {
 }
 
 fn version_from_yarn_lock(cwd: &Path, package_name: &str) -> Option<String> {
-    let lock_path = cwd.join("yarn.lock");
-    let content = fs::read_to_string(lock_path).ok()?;
-    let prefixes = [format!("\"{package_name}@"), format!("{package_name}@")];
-    let mut lines = content.lines().peekable();
-
-    while let Some(line) = lines.next() {
-        if !prefixes.iter().any(|prefix| line.starts_with(prefix)) {
-            continue;
-        }
-
-        while let Some(following_line) = lines.peek().copied() {
-            let trimmed = following_line.trim();
-            if trimmed.starts_with("version ") {
-                let version = trimmed
-                    .trim_start_matches("version ")
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .to_string();
-                if !version.is_empty() {
-                    return Some(version);
-                }
-            }
+    version_from_js_lock(
+        cwd,
+        providers::ProviderKind::Yarn,
+        "yarn.lock",
+        package_name,
+    )
+}
 
-            if !following_line.starts_with(' ') && !following_line.starts_with('\t') {
-                break;
-            }
+fn version_from_bun_lock(cwd: &Path, package_name: &str) -> Option<String> {
+    version_from_js_lock(cwd, providers::ProviderKind::Bun, "bun.lock", package_name)
+}
 
-            lines.next();
-        }
+/// Reads a registry version through the lockfile provider, which handles both the
+/// Yarn v1 and Berry formats of `yarn.lock`.
+fn version_from_js_lock(
+    cwd: &Path,
+    provider: providers::ProviderKind,
+    file_name: &str,
+    package_name: &str,
+) -> Option<String> {
+    let path = cwd.join(file_name);
+    if !path.exists() {
+        return None;
     }
-
-    None
+    providers::parse_provider_input(&providers::ProviderInputMatch { provider, path })
+        .ok()?
+        .into_iter()
+        .find(|dep| dep.name == package_name && dep.git_hint.is_none())
+        .map(|dep| dep.version)
 }
 
 fn version_from_package_json(cwd: &Path, package_name: &str) -> Option<String> {
@@ -266,14 +509,19 @@ fn version_from_package_json_maps(parsed: &PackageJson, package_name: &str) -> O
         .and_then(|version| normalize_declared_version(version))
 }
 
-fn version_from_uv_lock(cwd: &Path, package_name: &str) -> Option<String> {
-    let lock_path = cwd.join("uv.lock");
+fn version_from_python_lock(
+    cwd: &Path,
+    provider: providers::ProviderKind,
+    source: &InstalledVersionSource,
+    package_name: &str,
+) -> Option<String> {
+    let lock_path = cwd.join(source.as_str());
     if !lock_path.exists() {
         return None;
     }
 
     let input = providers::ProviderInputMatch {
-        provider: providers::ProviderKind::Uv,
+        provider,
         path: lock_path,
     };
     let normalized_package_name = normalize_python_package_name(package_name);
@@ -288,6 +536,11 @@ fn version_from_uv_lock(cwd: &Path, package_name: &str) -> Option<String> {
             return None;
         }
 
+        // A git-sourced entry's version may not exist on the registry at all.
+        if dep.git_hint.is_some() {
+            return None;
+        }
+
         Some(dep.version)
     })
 }
@@ -398,7 +651,10 @@ mod tests {
 
     use crate::installed_version::{
         InstalledVersion, InstalledVersionSource, detect_installed_crates_version,
-        detect_installed_npm_version, detect_installed_pypi_version,
+        detect_installed_go_version, detect_installed_hex_version, detect_installed_maven_version,
+        detect_installed_npm_version, detect_installed_nuget_version,
+        detect_installed_packagist_version, detect_installed_pub_version,
+        detect_installed_pypi_version, detect_installed_rubygems_version,
     };
 
     #[test]
@@ -481,6 +737,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reads_yarn_berry_and_bun_lock_entries() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("yarn.lock"),
+            "__metadata:\n  version: 8\n  cacheKey: 10c0\n\n\"zod@npm:^3.0.0\":\n  version: 3.25.0\n  resolution: \"zod@npm:3.25.0\"\n  languageName: node\n  linkType: hard\n",
+        )
+        .expect("write yarn lock");
+
+        assert_eq!(
+            detect_installed_npm_version(temp.path(), "zod").expect("detect zod"),
+            Some(InstalledVersion {
+                version: "3.25.0".to_string(),
+                source: InstalledVersionSource::YarnLock,
+            })
+        );
+
+        fs::remove_file(temp.path().join("yarn.lock")).expect("remove yarn lock");
+        fs::write(
+            temp.path().join("bun.lock"),
+            "{\n  \"lockfileVersion\": 1,\n  \"packages\": {\n    \"zod\": [\"zod@3.24.1\", \"\", {}, \"sha512-placeholder\"],\n  },\n}\n",
+        )
+        .expect("write bun lock");
+
+        assert_eq!(
+            detect_installed_npm_version(temp.path(), "zod").expect("detect zod"),
+            Some(InstalledVersion {
+                version: "3.24.1".to_string(),
+                source: InstalledVersionSource::BunLock,
+            })
+        );
+    }
+
     #[test]
     fn normalizes_package_json_declared_versions() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -571,6 +860,71 @@ version = "3.4.2"
         );
     }
 
+    #[test]
+    fn reads_poetry_lock_entry_for_pypi() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("poetry.lock"),
+            "[[package]]\nname = \"Django\"\nversion = \"5.1.4\"\nfiles = []\n",
+        )
+        .expect("write poetry lock");
+
+        let version =
+            detect_installed_pypi_version(temp.path(), "django").expect("detect pypi version");
+        assert_eq!(
+            version,
+            Some(InstalledVersion {
+                version: "5.1.4".to_string(),
+                source: InstalledVersionSource::PoetryLock,
+            })
+        );
+    }
+
+    #[test]
+    fn prefers_uv_lock_over_other_python_lockfiles() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("uv.lock"),
+            "version = 1\n\n[[package]]\nname = \"requests\"\nversion = \"2.32.3\"\n",
+        )
+        .expect("write uv lock");
+        fs::write(
+            temp.path().join("Pipfile.lock"),
+            r#"{"default":{"requests":{"version":"==2.31.0"}},"develop":{}}"#,
+        )
+        .expect("write Pipfile lock");
+
+        let version =
+            detect_installed_pypi_version(temp.path(), "requests").expect("detect pypi version");
+        assert_eq!(
+            version.map(|detected| detected.source),
+            Some(InstalledVersionSource::UvLock)
+        );
+    }
+
+    #[test]
+    fn reads_pipfile_lock_entry_and_skips_git_entries() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("Pipfile.lock"),
+            r#"{"default":{"demo":{"git":"https://example.com/demo.git","ref":"abc123"}},"develop":{"pytest":{"version":"==8.3.4"}}}"#,
+        )
+        .expect("write Pipfile lock");
+
+        let pytest =
+            detect_installed_pypi_version(temp.path(), "pytest").expect("detect pytest version");
+        let demo = detect_installed_pypi_version(temp.path(), "demo").expect("detect demo version");
+
+        assert_eq!(
+            pytest,
+            Some(InstalledVersion {
+                version: "8.3.4".to_string(),
+                source: InstalledVersionSource::PipfileLock,
+            })
+        );
+        assert!(demo.is_none());
+    }
+
     #[test]
     fn detects_single_crates_version_from_cargo_lock() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -626,4 +980,214 @@ version = "3.4.2"
             })
         );
     }
+
+    #[test]
+    fn prefers_go_mod_requirement_over_go_sum() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("go.mod"),
+            "module example.com/app\n\nrequire github.com/spf13/cobra v1.8.0\n",
+        )
+        .expect("write go.mod");
+        fs::write(
+            temp.path().join("go.sum"),
+            "github.com/spf13/cobra v1.7.0 h1:x=\ngithub.com/spf13/cobra v1.8.0 h1:y=\n",
+        )
+        .expect("write go.sum");
+
+        let version = detect_installed_go_version(temp.path(), "github.com/spf13/cobra")
+            .expect("detect go version");
+        assert_eq!(
+            version,
+            Some(InstalledVersion {
+                version: "v1.8.0".to_string(),
+                source: InstalledVersionSource::GoMod,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_ambiguous_go_sum_versions() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("go.sum"),
+            "github.com/spf13/cobra v1.7.0 h1:x=\ngithub.com/spf13/cobra v1.8.0 h1:y=\n",
+        )
+        .expect("write go.sum");
+
+        let err = detect_installed_go_version(temp.path(), "github.com/spf13/cobra")
+            .expect_err("expected ambiguous versions to fail");
+        assert!(err.to_string().contains(
+            "multiple installed go versions detected for github.com/spf13/cobra in go.sum"
+        ));
+    }
+
+    #[test]
+    fn reads_gemfile_lock_gem_and_skips_git_sources() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("Gemfile.lock"),
+            "GIT\n  remote: https://github.com/rack/rack.git\n  revision: 0123abc\n  specs:\n    rack (3.1.0)\n\nGEM\n  remote: https://rubygems.org/\n  specs:\n    rails (7.1.3)\n      rack (>= 2.2.4)\n",
+        )
+        .expect("write Gemfile.lock");
+
+        let version =
+            detect_installed_rubygems_version(temp.path(), "rails").expect("detect rails");
+        assert_eq!(
+            version,
+            Some(InstalledVersion {
+                version: "7.1.3".to_string(),
+                source: InstalledVersionSource::GemfileLock,
+            })
+        );
+        assert_eq!(
+            detect_installed_rubygems_version(temp.path(), "rack").expect("detect rack"),
+            None
+        );
+    }
+
+    #[test]
+    fn prefers_gradle_lockfile_over_version_catalog() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("gradle.lockfile"),
+            "com.google.guava:guava:33.0.0-jre=compileClasspath\nempty=annotationProcessor\n",
+        )
+        .expect("write gradle.lockfile");
+        fs::create_dir_all(temp.path().join("gradle")).expect("create gradle dir");
+        fs::write(
+            temp.path().join("gradle/libs.versions.toml"),
+            "[libraries]\nguava = \"com.google.guava:guava:32.1.3-jre\"\n",
+        )
+        .expect("write libs.versions.toml");
+
+        let version = detect_installed_maven_version(temp.path(), "com.google.guava:guava")
+            .expect("detect guava");
+        assert_eq!(
+            version,
+            Some(InstalledVersion {
+                version: "33.0.0-jre".to_string(),
+                source: InstalledVersionSource::GradleLockfile,
+            })
+        );
+    }
+
+    #[test]
+    fn reads_composer_lock_version_for_packagist() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("composer.lock"),
+            r#"{"packages":[{"name":"monolog/monolog","version":"3.5.0","source":{"type":"git","url":"https://github.com/Seldaek/monolog.git","reference":"c915e2634718dbc8a4a15c61b0e62e7a44e14448"}}],"packages-dev":[]}"#,
+        )
+        .expect("write composer.lock");
+
+        let version = detect_installed_packagist_version(temp.path(), "Monolog/Monolog")
+            .expect("detect monolog");
+        assert_eq!(
+            version,
+            Some(InstalledVersion {
+                version: "3.5.0".to_string(),
+                source: InstalledVersionSource::ComposerLock,
+            })
+        );
+    }
+
+    #[test]
+    fn prefers_nuget_packages_lock_over_central_package_versions() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("packages.lock.json"),
+            r#"{"version":1,"dependencies":{"net8.0":{"Serilog":{"type":"Direct","requested":"[3.1.0, )","resolved":"3.1.1"}}}}"#,
+        )
+        .expect("write packages.lock.json");
+        fs::write(
+            temp.path().join("Directory.Packages.props"),
+            r#"<Project><ItemGroup><PackageVersion Include="Serilog" Version="3.1.0" /><PackageVersion Include="xunit" Version="2.6.6" /></ItemGroup></Project>"#,
+        )
+        .expect("write Directory.Packages.props");
+
+        assert_eq!(
+            detect_installed_nuget_version(temp.path(), "serilog").expect("detect serilog"),
+            Some(InstalledVersion {
+                version: "3.1.1".to_string(),
+                source: InstalledVersionSource::NugetPackagesLock,
+            })
+        );
+        assert_eq!(
+            detect_installed_nuget_version(temp.path(), "xunit").expect("detect xunit"),
+            Some(InstalledVersion {
+                version: "2.6.6".to_string(),
+                source: InstalledVersionSource::DirectoryPackagesProps,
+            })
+        );
+    }
+
+    #[test]
+    fn reads_mix_lock_hex_package_and_skips_git_dependencies() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("mix.lock"),
+            r#"%{
+  "jason": {:hex, :jason, "1.4.1", "af15", [:mix], [], "hexpm", "fbb0"},
+  "plug": {:git, "https://github.com/elixir-plug/plug.git", "0123456789abcdef0123456789abcdef01234567", [branch: "main"]},
+}
+"#,
+        )
+        .expect("write mix.lock");
+
+        assert_eq!(
+            detect_installed_hex_version(temp.path(), "jason").expect("detect jason"),
+            Some(InstalledVersion {
+                version: "1.4.1".to_string(),
+                source: InstalledVersionSource::MixLock,
+            })
+        );
+        assert_eq!(
+            detect_installed_hex_version(temp.path(), "plug").expect("detect plug"),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_pubspec_lock_hosted_package_and_skips_git_dependencies() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            temp.path().join("pubspec.lock"),
+            r#"packages:
+  http:
+    dependency: "direct main"
+    description:
+      name: http
+      sha256: "b9c29a161230ee03d3ccf545097fccd9b87a5264228c5d348202e0f0c28f9010"
+      url: "https://pub.dev"
+    source: hosted
+    version: "1.2.2"
+  shared_widgets:
+    dependency: "direct main"
+    description:
+      path: "packages/shared_widgets"
+      ref: main
+      resolved-ref: "0123456789abcdef0123456789abcdef01234567"
+      url: "https://github.com/acme/mobile.git"
+    source: git
+    version: "0.3.0"
+sdks:
+  dart: ">=3.3.0 <4.0.0"
+"#,
+        )
+        .expect("write pubspec.lock");
+
+        assert_eq!(
+            detect_installed_pub_version(temp.path(), "http").expect("detect http"),
+            Some(InstalledVersion {
+                version: "1.2.2".to_string(),
+                source: InstalledVersionSource::PubspecLock,
+            })
+        );
+        assert_eq!(
+            detect_installed_pub_version(temp.path(), "shared_widgets")
+                .expect("detect shared_widgets"),
+            None
+        );
+    }
 }
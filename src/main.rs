@@ -1,14 +1,20 @@
+mod archive;
+mod cache_lock;
 mod cli;
 mod commands;
 mod config;
 mod depspec;
 mod error;
+mod git_auth;
 mod index;
 mod installed_version;
 mod logging;
+mod pom;
 mod providers;
 mod registry_resolver;
+mod release_tags;
 mod source;
+mod worker_pool;
 
 use anyhow::Context;
 use clap::Parser;
@@ -30,7 +36,8 @@ fn run() -> anyhow::Result<()> {
     logging::init(cli.verbose)?;
 
     let cwd = std::env::current_dir().context("failed to get current working directory")?;
-    let config = config::load(&cwd).context("failed to load configuration")?;
+    let mut config = config::load(&cwd).context("failed to load configuration")?;
+    config.offline |= cli.offline;
 
     log_command_start(&cwd, &config, &cli.command);
 
@@ -42,6 +49,9 @@ fn log_command_start(cwd: &std::path::Path, config: &Config, command: &Command)
         command = command_name(command),
         cwd = %cwd.display(),
         worker_pool_size = config.worker_pool_size,
+        lock_timeout_secs = config.lock_timeout_secs,
+        checkout_strategy = config.checkout_strategy.as_str(),
+        offline = config.offline,
         "starting command"
     );
 }
@@ -52,6 +62,8 @@ fn command_name(command: &Command) -> &'static str {
         Command::Remove { .. } => "remove",
         Command::Path { .. } => "path",
         Command::List { .. } => "list",
+        Command::Grep { .. } => "grep",
+        Command::Verify { .. } => "verify",
         Command::Init => "init",
         Command::Cache { command } => match command {
             CacheCommand::Clean { .. } => "cache_clean",
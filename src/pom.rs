@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use roxmltree::{Document, Node};
+
+/// The parts of a Maven POM pkgrep reads: coordinates, the parent reference,
+/// `<properties>`, `<scm>`, and declared dependency versions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pom {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub parent: Option<PomCoordinates>,
+    pub properties: BTreeMap<String, String>,
+    pub scm: Option<PomScm>,
+    /// Entries of `<dependencyManagement>` followed by direct `<dependencies>`.
+    pub dependencies: Vec<PomDependency>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PomCoordinates {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PomScm {
+    pub connection: Option<String>,
+    pub developer_connection: Option<String>,
+    pub url: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PomDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+}
+
+impl Pom {
+    pub fn parse(raw: &str) -> Result<Self, roxmltree::Error> {
+        let document = Document::parse(raw)?;
+        let project = document.root_element();
+
+        let parent = child(project, "parent").and_then(|parent| {
+            Some(PomCoordinates {
+                group_id: child_text(parent, "groupId")?,
+                artifact_id: child_text(parent, "artifactId")?,
+                version: child_text(parent, "version")?,
+            })
+        });
+        let properties = child(project, "properties")
+            .map(|properties| {
+                properties
+                    .children()
+                    .filter(Node::is_element)
+                    .map(|property| {
+                        (
+                            property.tag_name().name().to_string(),
+                            property.text().unwrap_or_default().trim().to_string(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let scm = child(project, "scm").map(|scm| PomScm {
+            connection: child_text(scm, "connection"),
+            developer_connection: child_text(scm, "developerConnection"),
+            url: child_text(scm, "url"),
+            tag: child_text(scm, "tag"),
+        });
+
+        let managed = child(project, "dependencyManagement")
+            .and_then(|management| child(management, "dependencies"));
+        let direct = child(project, "dependencies");
+        let dependencies = [managed, direct]
+            .into_iter()
+            .flatten()
+            .flat_map(|dependencies| {
+                dependencies
+                    .children()
+                    .filter(|node| node.has_tag_name("dependency"))
+            })
+            .filter_map(|dependency| {
+                Some(PomDependency {
+                    group_id: child_text(dependency, "groupId")?,
+                    artifact_id: child_text(dependency, "artifactId")?,
+                    version: child_text(dependency, "version"),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            group_id: child_text(project, "groupId"),
+            artifact_id: child_text(project, "artifactId"),
+            version: child_text(project, "version"),
+            parent,
+            properties,
+            scm,
+            dependencies,
+        })
+    }
+
+    /// `groupId`, inherited from the parent when the POM does not set its own.
+    pub fn effective_group_id(&self) -> Option<&str> {
+        self.group_id
+            .as_deref()
+            .or_else(|| self.parent.as_ref().map(|parent| parent.group_id.as_str()))
+    }
+
+    /// `version`, inherited from the parent when the POM does not set its own.
+    pub fn effective_version(&self) -> Option<&str> {
+        self.version
+            .as_deref()
+            .or_else(|| self.parent.as_ref().map(|parent| parent.version.as_str()))
+    }
+
+    /// Expands `${...}` references to the project coordinates and to `<properties>`.
+    /// Returns `None` when a reference cannot be resolved from this POM alone.
+    pub fn interpolate(&self, raw: &str) -> Option<String> {
+        let mut current = raw.to_string();
+        // Properties may refer to other properties; bound the expansion depth.
+        for _ in 0..8 {
+            if !current.contains("${") {
+                return Some(current);
+            }
+            let mut expanded = String::with_capacity(current.len());
+            let mut rest = current.as_str();
+            while let Some(start) = rest.find("${") {
+                expanded.push_str(&rest[..start]);
+                let (name, after) = rest[start + 2..].split_once('}')?;
+                expanded.push_str(&self.property(name)?);
+                rest = after;
+            }
+            expanded.push_str(rest);
+            current = expanded;
+        }
+        None
+    }
+
+    fn property(&self, name: &str) -> Option<String> {
+        match name {
+            "project.groupId" | "pom.groupId" | "groupId" => {
+                self.effective_group_id().map(str::to_string)
+            }
+            "project.artifactId" | "pom.artifactId" | "artifactId" => self.artifact_id.clone(),
+            "project.version" | "pom.version" | "version" => {
+                self.effective_version().map(str::to_string)
+            }
+            "project.parent.version" => self.parent.as_ref().map(|parent| parent.version.clone()),
+            name => self.properties.get(name).cloned(),
+        }
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn child_text(node: Node<'_, '_>, name: &str) -> Option<String> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <parent>
+    <groupId>com.example</groupId>
+    <artifactId>demo-parent</artifactId>
+    <version>2.0.0</version>
+  </parent>
+  <artifactId>demo-core</artifactId>
+  <properties>
+    <jackson.version>2.17.0</jackson.version>
+    <repo.name>${project.artifactId}-repo</repo.name>
+  </properties>
+  <scm>
+    <connection>scm:git:https://github.com/example/${repo.name}.git</connection>
+    <tag>demo-${project.version}</tag>
+  </scm>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.fasterxml.jackson.core</groupId>
+        <artifactId>jackson-databind</artifactId>
+        <version>${jackson.version}</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+    #[test]
+    fn reads_coordinates_inherited_from_the_parent() {
+        let pom = Pom::parse(POM).expect("parse pom");
+
+        assert_eq!(pom.effective_group_id(), Some("com.example"));
+        assert_eq!(pom.artifact_id.as_deref(), Some("demo-core"));
+        assert_eq!(pom.effective_version(), Some("2.0.0"));
+        assert_eq!(
+            pom.parent
+                .as_ref()
+                .map(|parent| parent.artifact_id.as_str()),
+            Some("demo-parent")
+        );
+    }
+
+    #[test]
+    fn interpolates_properties_and_project_coordinates() {
+        let pom = Pom::parse(POM).expect("parse pom");
+        let scm = pom.scm.clone().expect("scm");
+
+        assert_eq!(
+            pom.interpolate(&scm.connection.expect("connection"))
+                .as_deref(),
+            Some("scm:git:https://github.com/example/demo-core-repo.git")
+        );
+        assert_eq!(
+            pom.interpolate(&scm.tag.expect("tag")).as_deref(),
+            Some("demo-2.0.0")
+        );
+        assert_eq!(pom.interpolate("${undefined.property}"), None);
+    }
+
+    #[test]
+    fn lists_managed_dependencies_before_direct_ones() {
+        let pom = Pom::parse(POM).expect("parse pom");
+
+        assert_eq!(
+            pom.dependencies,
+            vec![
+                PomDependency {
+                    group_id: String::from("com.fasterxml.jackson.core"),
+                    artifact_id: String::from("jackson-databind"),
+                    version: Some(String::from("${jackson.version}")),
+                },
+                PomDependency {
+                    group_id: String::from("com.google.guava"),
+                    artifact_id: String::from("guava"),
+                    version: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+// Hosted-git shorthands Bun keeps in resolutions instead of a clone URL.
+const HOSTED_GIT_PREFIXES: [(&str, &str); 3] = [
+    ("github:", "https://github.com/"),
+    ("gitlab:", "https://gitlab.com/"),
+    ("bitbucket:", "https://bitbucket.org/"),
+];
+
+#[derive(Debug, Deserialize)]
+struct BunLock {
+    /// Keyed by install path; each entry is `["<name>@<resolution>", ...]`.
+    #[serde(default)]
+    packages: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let lock: BunLock =
+        serde_json::from_str(&strip_jsonc(&raw)).map_err(|source| ProviderError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let mut deps: BTreeMap<(String, String), NormalizedDependency> = BTreeMap::new();
+    for entry in lock.packages.into_values() {
+        let Some(descriptor) = entry.first().and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        let Some((name, resolution)) = split_descriptor(descriptor) else {
+            continue;
+        };
+
+        let (version, git_hint) = match parse_git_hint(resolution) {
+            // Bun does not record the version of git packages; the commit stands in.
+            Some(git_hint) => (git_hint.requested_revision.clone(), Some(git_hint)),
+            None if is_registry_version(resolution) => (resolution.to_string(), None),
+            // `workspace:`, `link:`, `file:`, and tarball URLs are not pullable.
+            None => continue,
+        };
+        deps.entry((name.to_string(), version.clone()))
+            .or_insert(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Npm,
+                name: name.to_string(),
+                version,
+                git_hint,
+                repository_url: None,
+            });
+    }
+
+    Ok(deps.into_values().collect())
+}
+
+/// Splits `name@resolution`, keeping the leading `@` of scoped names.
+fn split_descriptor(descriptor: &str) -> Option<(&str, &str)> {
+    let separator = descriptor.get(1..)?.find('@')? + 1;
+    let (name, resolution) = (&descriptor[..separator], &descriptor[separator + 1..]);
+    (!name.is_empty() && !resolution.is_empty()).then_some((name, resolution))
+}
+
+fn is_registry_version(resolution: &str) -> bool {
+    resolution.starts_with(|ch: char| ch.is_ascii_digit()) && !resolution.contains([':', '/'])
+}
+
+fn parse_git_hint(resolution: &str) -> Option<GitSourceHint> {
+    let (url, revision) = resolution.rsplit_once('#')?;
+    let url = match HOSTED_GIT_PREFIXES
+        .iter()
+        .find_map(|(prefix, base)| Some((url.strip_prefix(prefix)?, base)))
+    {
+        Some((repository, base)) => format!("{base}{repository}.git"),
+        None => url.strip_prefix("git+")?.to_string(),
+    };
+    if revision.is_empty() {
+        return None;
+    }
+    Some(GitSourceHint {
+        url,
+        requested_revision: revision.to_string(),
+        subdirectory: None,
+    })
+}
+
+/// `bun.lock` is JSONC: JSON with comments and trailing commas.
+fn strip_jsonc(raw: &str) -> String {
+    let mut stripped = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    let mut in_string = false;
+    while let Some(ch) = chars.next() {
+        if in_string {
+            stripped.push(ch);
+            match ch {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => {
+                in_string = true;
+                stripped.push(ch);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|next| *next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = '\0';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ',' => {
+                let rest = chars.clone().find(|next| !next.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    stripped.push(ch);
+                }
+            }
+            _ => stripped.push(ch),
+        }
+    }
+    stripped
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct PubspecLock {
+    #[serde(default)]
+    packages: BTreeMap<String, PubspecLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PubspecLockPackage {
+    source: String,
+    #[serde(default)]
+    version: Option<String>,
+    /// A map for `hosted` and `git` sources; SDK packages only name the SDK.
+    #[serde(default)]
+    description: Option<serde_yml::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GitDescription {
+    url: Option<String>,
+    #[serde(default, rename = "resolved-ref")]
+    resolved_ref: Option<String>,
+    #[serde(default, rename = "ref")]
+    reference: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let lock: PubspecLock = serde_yml::from_str(&raw).map_err(|source| ProviderError::Yaml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // `sdk` packages ship with Flutter/Dart and `path` packages live in the project.
+    Ok(lock
+        .packages
+        .into_iter()
+        .filter_map(|(name, package)| {
+            let git_hint = match package.source.as_str() {
+                "hosted" => None,
+                "git" => Some(parse_git_hint(package.description?)?),
+                _ => return None,
+            };
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Pub,
+                name,
+                version: package.version?,
+                git_hint,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
+
+fn parse_git_hint(description: serde_yml::Value) -> Option<GitSourceHint> {
+    let description: GitDescription = serde_yml::from_value(description).ok()?;
+    let subdirectory = description
+        .path
+        .map(|path| path.trim().trim_matches('/').to_string())
+        .filter(|path| !path.is_empty() && path != ".");
+    Some(GitSourceHint {
+        url: description.url?,
+        requested_revision: description
+            .resolved_ref
+            .or(description.reference)
+            .unwrap_or_else(|| String::from("HEAD")),
+        subdirectory,
+    })
+}
@@ -0,0 +1,79 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+// Central package management pins versions with `<PackageVersion>`;
+// `<GlobalPackageReference>` adds a package to every project with its own version.
+const VERSIONED_ITEMS: [&str; 2] = ["PackageVersion", "GlobalPackageReference"];
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let document = Document::parse(&raw).map_err(|source| ProviderError::Xml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let properties = document
+        .descendants()
+        .filter(|node| node.has_tag_name("PropertyGroup"))
+        .flat_map(|group| group.children().filter(Node::is_element))
+        .filter_map(|property| {
+            Some((
+                property.tag_name().name().to_string(),
+                property.text()?.trim().to_string(),
+            ))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let mut seen = BTreeSet::new();
+    Ok(document
+        .descendants()
+        .filter(|node| VERSIONED_ITEMS.iter().any(|item| node.has_tag_name(*item)))
+        .filter_map(|item| {
+            let name = item.attribute("Include")?.trim().to_string();
+            let version = item.attribute("Version").map(str::to_string).or_else(|| {
+                item.children()
+                    .find(|child| child.has_tag_name("Version"))?
+                    .text()
+                    .map(str::to_string)
+            })?;
+            let version = expand_properties(version.trim(), &properties)?;
+            // Ranges and floating versions do not name a single release.
+            if name.is_empty() || version.is_empty() || version.contains(['[', '(', ',', '*']) {
+                return None;
+            }
+            if !seen.insert(name.to_ascii_lowercase()) {
+                return None;
+            }
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Nuget,
+                name,
+                version,
+                git_hint: None,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
+
+/// Expands `$(Name)` references to properties defined in the same file; versions
+/// that refer to properties from elsewhere in the build are skipped.
+fn expand_properties(raw: &str, properties: &BTreeMap<String, String>) -> Option<String> {
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("$(") {
+        expanded.push_str(&rest[..start]);
+        let (name, after) = rest[start + 2..].split_once(')')?;
+        expanded.push_str(properties.get(name.trim())?);
+        rest = after;
+    }
+    expanded.push_str(rest);
+    Some(expanded)
+}
@@ -0,0 +1,53 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct PackagesLock {
+    /// Target framework (`net8.0`, ...) to the packages restored for it.
+    #[serde(default)]
+    dependencies: BTreeMap<String, BTreeMap<String, LockedPackage>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LockedPackage {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    resolved: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parsed: PackagesLock =
+        serde_json::from_str(&raw).map_err(|source| ProviderError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    // The same package is usually locked once per target framework; `Project`
+    // entries are references to other projects in the solution, not packages.
+    let mut seen = BTreeSet::new();
+    Ok(parsed
+        .dependencies
+        .into_values()
+        .flatten()
+        .filter(|(_, package)| package.kind.as_deref() != Some("Project"))
+        .filter_map(|(name, package)| Some((name, package.resolved?)))
+        .filter(|(name, version)| seen.insert((name.to_ascii_lowercase(), version.clone())))
+        .map(|(name, version)| NormalizedDependency {
+            ecosystem: ProviderEcosystem::Nuget,
+            name,
+            version,
+            git_hint: None,
+            repository_url: None,
+        })
+        .collect())
+}
@@ -0,0 +1,281 @@
+use std::fs;
+use std::path::Path;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+/// The subset of Elixir term syntax `mix.lock` is written in. Lists (manager and
+/// dependency options) and numbers are parsed but not kept.
+enum Term {
+    String(String),
+    Atom(String),
+    List,
+    Tuple(Vec<Term>),
+    Map(Vec<(Term, Term)>),
+    Other,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let term =
+        TermParser::new(&raw)
+            .parse_document()
+            .map_err(|message| ProviderError::ElixirTerm {
+                path: path.to_path_buf(),
+                message,
+            })?;
+    let Term::Map(entries) = term else {
+        return Err(ProviderError::ElixirTerm {
+            path: path.to_path_buf(),
+            message: String::from("expected a map of locked dependencies"),
+        });
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let Term::String(app) = key else {
+                return None;
+            };
+            let Term::Tuple(fields) = value else {
+                return None;
+            };
+            dependency_from_lock_entry(app, &fields)
+        })
+        .collect())
+}
+
+// `{:hex, package, version, inner_checksum, managers, deps, repo, outer_checksum}`
+// names the Hex package, which may differ from the app key; `{:git, url, sha, opts}`
+// pins an exact commit. `:path` dependencies are never locked.
+fn dependency_from_lock_entry(app: String, fields: &[Term]) -> Option<NormalizedDependency> {
+    match fields {
+        [
+            Term::Atom(scm),
+            Term::Atom(package),
+            Term::String(version),
+            ..,
+        ] if scm == "hex" => Some(NormalizedDependency {
+            ecosystem: ProviderEcosystem::Hex,
+            name: package.clone(),
+            version: version.clone(),
+            git_hint: None,
+            repository_url: None,
+        }),
+        [Term::Atom(scm), Term::String(url), Term::String(sha), ..] if scm == "git" => {
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Hex,
+                name: app,
+                version: sha.clone(),
+                git_hint: Some(GitSourceHint {
+                    url: url.clone(),
+                    requested_revision: sha.clone(),
+                    subdirectory: None,
+                }),
+                repository_url: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+struct TermParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> TermParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn parse_document(mut self) -> Result<Term, String> {
+        let term = self.parse_term()?;
+        self.skip_whitespace();
+        if self.position < self.input.len() {
+            return Err(format!("unexpected input at byte {}", self.position));
+        }
+        Ok(term)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at byte {}", token, self.position))
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Term, String> {
+        self.skip_whitespace();
+        if self.rest().starts_with("%{") {
+            self.position += 2;
+            return self.parse_map();
+        }
+        match self.peek() {
+            Some('{') => {
+                self.position += 1;
+                self.parse_sequence('}').map(Term::Tuple)
+            }
+            Some('[') => {
+                self.position += 1;
+                self.parse_sequence(']').map(|_| Term::List)
+            }
+            Some('"') => self.parse_string().map(Term::String),
+            Some(':') => {
+                self.position += 1;
+                if self.peek() == Some('"') {
+                    self.parse_string().map(Term::Atom)
+                } else {
+                    Ok(Term::Atom(self.parse_word()))
+                }
+            }
+            Some(_) => {
+                let word = self.parse_word();
+                if word.is_empty() {
+                    return Err(format!("unexpected character at byte {}", self.position));
+                }
+                Ok(match word.as_str() {
+                    "true" | "false" | "nil" => Term::Atom(word),
+                    _ => Term::Other,
+                })
+            }
+            None => Err(String::from("unexpected end of input")),
+        }
+    }
+
+    /// Elements up to `close`; keyword pairs (`branch: "main"`) become `{atom, value}`.
+    fn parse_sequence(&mut self, close: char) -> Result<Vec<Term>, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(items);
+            }
+            let item = match self.parse_keyword_key() {
+                Some(key) => Term::Tuple(vec![Term::Atom(key), self.parse_term()?]),
+                None => self.parse_term()?,
+            };
+            items.push(item);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.position += 1;
+            } else if self.peek() != Some(close) {
+                return Err(format!(
+                    "expected ',' or '{}' at byte {}",
+                    close, self.position
+                ));
+            }
+        }
+    }
+
+    /// Map entries written as `"key": value`, `key: value`, or `key => value`.
+    fn parse_map(&mut self) -> Result<Term, String> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(Term::Map(entries));
+            }
+            let key = match self.parse_keyword_key() {
+                Some(key) => Term::Atom(key),
+                None if self.peek() == Some('"') => {
+                    let key = self.parse_string()?;
+                    if self.rest().starts_with(':') {
+                        self.position += 1;
+                    } else {
+                        self.expect("=>")?;
+                    }
+                    Term::String(key)
+                }
+                None => {
+                    let key = self.parse_term()?;
+                    self.expect("=>")?;
+                    key
+                }
+            };
+            let value = self.parse_term()?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.position += 1;
+            } else if self.peek() != Some('}') {
+                return Err(format!("expected ',' or '}}' at byte {}", self.position));
+            }
+        }
+    }
+
+    /// Consumes a bare `word:` keyword key, leaving the input untouched otherwise.
+    fn parse_keyword_key(&mut self) -> Option<String> {
+        let start = self.position;
+        let word = self.parse_word();
+        let rest = self.rest();
+        if !word.is_empty() && rest.starts_with(':') && rest[1..].starts_with(char::is_whitespace) {
+            self.position += 1;
+            return Some(word);
+        }
+        self.position = start;
+        None
+    }
+
+    fn parse_word(&mut self) -> String {
+        let rest = self.rest();
+        let end = rest
+            .find(|ch: char| {
+                !(ch.is_alphanumeric() || matches!(ch, '_' | '.' | '@' | '?' | '!' | '-' | '+'))
+            })
+            .unwrap_or(rest.len());
+        self.position += end;
+        rest[..end].to_string()
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        let start = self.position;
+        self.position += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.position += offset + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                ch => value.push(ch),
+            }
+        }
+        Err(format!("unterminated string starting at byte {}", start))
+    }
+}
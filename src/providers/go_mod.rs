@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Block {
+    None,
+    Require,
+    Replace,
+    Other,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut requires = Vec::new();
+    let mut replacements = BTreeMap::new();
+    let mut block = Block::None;
+    for line in raw.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if block != Block::None {
+            if line == ")" {
+                block = Block::None;
+            } else if block == Block::Require {
+                requires.extend(parse_requirement(line));
+            } else if block == Block::Replace {
+                replacements.extend(parse_replacement(line));
+            }
+            continue;
+        }
+
+        let (directive, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let directive_block = match directive {
+            "require" => Block::Require,
+            "replace" => Block::Replace,
+            _ => Block::Other,
+        };
+        if rest == "(" {
+            block = directive_block;
+        } else if directive_block == Block::Require {
+            requires.extend(parse_requirement(rest));
+        } else if directive_block == Block::Replace {
+            replacements.extend(parse_replacement(rest));
+        }
+    }
+
+    let mut deps = Vec::new();
+    for (module_path, version) in requires {
+        let replacement = replacements
+            .get(&(module_path.clone(), Some(version.clone())))
+            .or_else(|| replacements.get(&(module_path.clone(), None)));
+        let (name, version) = match replacement {
+            // Replaced by a local directory: there is nothing to fetch.
+            Some(None) => continue,
+            Some(Some((name, version))) => (name.clone(), version.clone()),
+            None => (module_path, version),
+        };
+        deps.push(NormalizedDependency {
+            ecosystem: ProviderEcosystem::Go,
+            name,
+            version,
+            git_hint: None,
+            repository_url: None,
+        });
+    }
+
+    Ok(deps)
+}
+
+fn parse_requirement(line: &str) -> Option<(String, String)> {
+    let mut fields = line.split_whitespace().map(unquote);
+    let module_path = fields.next()?;
+    let version = fields.next()?;
+    Some((module_path.to_string(), version.to_string()))
+}
+
+type ReplacementKey = (String, Option<String>);
+type ReplacementTarget = Option<(String, String)>;
+
+// `old [version] => new version` swaps in another module; `old [version] => ./dir`
+// points at a local directory and maps to `None`.
+fn parse_replacement(line: &str) -> Option<(ReplacementKey, ReplacementTarget)> {
+    let (old, new) = line.split_once("=>")?;
+    let mut old_fields = old.split_whitespace().map(unquote);
+    let old_module = old_fields.next()?.to_string();
+    let old_version = old_fields.next().map(str::to_string);
+
+    let mut new_fields = new.split_whitespace().map(unquote);
+    let new_module = new_fields.next()?;
+    let target = new_fields
+        .next()
+        .map(|new_version| (new_module.to_string(), new_version.to_string()));
+    Some(((old_module, old_version), target))
+}
+
+fn unquote(field: &str) -> &str {
+    field.trim_matches(|ch| ch == '"' || ch == '`')
+}
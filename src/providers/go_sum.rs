@@ -0,0 +1,38 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // Entries whose version ends in `/go.mod` only hash the module's go.mod file for
+    // version selection; the module source itself was never downloaded.
+    let modules = raw
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let module_path = fields.next()?;
+            let version = fields.next()?;
+            if version.ends_with("/go.mod") {
+                return None;
+            }
+            Some((module_path.to_string(), version.to_string()))
+        })
+        .collect::<BTreeSet<_>>();
+
+    Ok(modules
+        .into_iter()
+        .map(|(name, version)| NormalizedDependency {
+            ecosystem: ProviderEcosystem::Go,
+            name,
+            version,
+            git_hint: None,
+            repository_url: None,
+        })
+        .collect())
+}
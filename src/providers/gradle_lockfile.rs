@@ -0,0 +1,33 @@
+use std::fs;
+use std::path::Path;
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // Each line is `<group>:<artifact>:<version>=<configurations>`; `empty=` lists
+    // configurations that resolved nothing.
+    Ok(raw
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (coordinates, _configurations) = line.split_once('=')?;
+            let (module, version) = coordinates.rsplit_once(':')?;
+            if !module.contains(':') {
+                return None;
+            }
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Maven,
+                name: module.to_string(),
+                version: version.to_string(),
+                git_hint: None,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct VersionCatalog {
+    #[serde(default)]
+    versions: BTreeMap<String, CatalogVersion>,
+    #[serde(default)]
+    libraries: BTreeMap<String, CatalogLibrary>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CatalogVersion {
+    Plain(String),
+    Rich {
+        #[serde(default)]
+        strictly: Option<String>,
+        #[serde(default)]
+        require: Option<String>,
+        #[serde(default)]
+        prefer: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CatalogLibrary {
+    /// `"<group>:<artifact>:<version>"`
+    Notation(String),
+    Table {
+        #[serde(default)]
+        module: Option<String>,
+        #[serde(default)]
+        group: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        version: Option<CatalogLibraryVersion>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CatalogLibraryVersion {
+    Ref {
+        #[serde(rename = "ref")]
+        reference: String,
+    },
+    Inline(CatalogVersion),
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let catalog: VersionCatalog = toml::from_str(&raw).map_err(|source| ProviderError::Toml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    Ok(catalog
+        .libraries
+        .values()
+        .filter_map(|library| {
+            let (module, version) = match library {
+                CatalogLibrary::Notation(notation) => {
+                    let (module, version) = notation.rsplit_once(':')?;
+                    (module.to_string(), Some(version.to_string()))
+                }
+                CatalogLibrary::Table {
+                    module,
+                    group,
+                    name,
+                    version,
+                } => {
+                    let module = module
+                        .clone()
+                        .or_else(|| Some(format!("{}:{}", group.as_ref()?, name.as_ref()?)))?;
+                    let version = match version.as_ref()? {
+                        CatalogLibraryVersion::Ref { reference } => {
+                            catalog.versions.get(reference).and_then(concrete_version)
+                        }
+                        CatalogLibraryVersion::Inline(version) => concrete_version(version),
+                    };
+                    (module, version)
+                }
+            };
+            // Libraries without a version get theirs from a platform or BOM.
+            let version = version.filter(|version| is_concrete_version(version))?;
+            if !module.contains(':') {
+                return None;
+            }
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Maven,
+                name: module,
+                version,
+                git_hint: None,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
+
+fn concrete_version(version: &CatalogVersion) -> Option<String> {
+    match version {
+        CatalogVersion::Plain(version) => Some(version.clone()),
+        CatalogVersion::Rich {
+            strictly,
+            require,
+            prefer,
+        } => strictly
+            .clone()
+            .or_else(|| require.clone())
+            .or_else(|| prefer.clone()),
+    }
+}
+
+/// Rejects Gradle ranges (`[1.0,2.0)`) and dynamic versions (`1.+`, `latest.release`).
+fn is_concrete_version(version: &str) -> bool {
+    !version.is_empty()
+        && !version.starts_with("latest.")
+        && !version.contains(['[', ']', '(', ')', ',', '+'])
+}
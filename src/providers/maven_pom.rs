@@ -0,0 +1,44 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::pom::Pom;
+use crate::providers::{NormalizedDependency, ProviderEcosystem, ProviderError};
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let pom = Pom::parse(&raw).map_err(|source| ProviderError::Xml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // Direct dependencies usually take their version from `<dependencyManagement>`,
+    // which comes first; entries without a resolvable, exact version are skipped.
+    let mut seen = BTreeSet::new();
+    Ok(pom
+        .dependencies
+        .iter()
+        .filter_map(|dependency| {
+            let group_id = pom.interpolate(&dependency.group_id)?;
+            let artifact_id = pom.interpolate(&dependency.artifact_id)?;
+            let version = pom.interpolate(dependency.version.as_deref()?)?;
+            if version.contains(['[', ']', '(', ')', ',']) {
+                return None;
+            }
+            let name = format!("{group_id}:{artifact_id}");
+            if !seen.insert(name.clone()) {
+                return None;
+            }
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Maven,
+                name,
+                version,
+                git_hint: None,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
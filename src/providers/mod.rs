@@ -1,7 +1,23 @@
+mod bun_lock;
+mod dart_pubspec_lock;
+mod dotnet_directory_packages_props;
+mod dotnet_packages_lock;
+mod elixir_mix_lock;
+mod go_mod;
+mod go_sum;
+mod gradle_lockfile;
+mod gradle_version_catalog;
+mod maven_pom;
 mod npm_package_lock;
+mod php_composer_lock;
 mod pnpm_lock;
+mod python_pdm_lock;
+mod python_pipfile_lock;
+mod python_poetry_lock;
 mod python_uv_lock;
+mod ruby_gemfile_lock;
 mod rust_cargo_lock;
+mod swift_package_resolved;
 mod yarn_lock;
 
 use std::path::{Path, PathBuf};
@@ -12,7 +28,23 @@ const PACKAGE_LOCK: &str = "package-lock.json";
 const PNPM_LOCK: &str = "pnpm-lock.yaml";
 const CARGO_LOCK: &str = "Cargo.lock";
 const UV_LOCK: &str = "uv.lock";
+const POETRY_LOCK: &str = "poetry.lock";
+const PDM_LOCK: &str = "pdm.lock";
+const PIPFILE_LOCK: &str = "Pipfile.lock";
 const YARN_LOCK: &str = "yarn.lock";
+const BUN_LOCK: &str = "bun.lock";
+const GO_MOD: &str = "go.mod";
+const GO_SUM: &str = "go.sum";
+const GEMFILE_LOCK: &str = "Gemfile.lock";
+const GRADLE_LOCKFILE: &str = "gradle.lockfile";
+const GRADLE_VERSION_CATALOG: &str = "gradle/libs.versions.toml";
+const POM_XML: &str = "pom.xml";
+const COMPOSER_LOCK: &str = "composer.lock";
+const NUGET_PACKAGES_LOCK: &str = "packages.lock.json";
+const DIRECTORY_PACKAGES_PROPS: &str = "Directory.Packages.props";
+const MIX_LOCK: &str = "mix.lock";
+const PUBSPEC_LOCK: &str = "pubspec.lock";
+const PACKAGE_RESOLVED: &str = "Package.resolved";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ProviderKind {
@@ -20,7 +52,23 @@ pub enum ProviderKind {
     Pnpm,
     Cargo,
     Uv,
+    Poetry,
+    Pdm,
+    Pipfile,
     Yarn,
+    Bun,
+    GoMod,
+    GoSum,
+    Bundler,
+    GradleLockfile,
+    GradleVersionCatalog,
+    Pom,
+    Composer,
+    NugetPackagesLock,
+    DirectoryPackagesProps,
+    Mix,
+    PubspecLock,
+    PackageResolved,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -33,6 +81,8 @@ pub struct ProviderInputMatch {
 pub struct GitSourceHint {
     pub url: String,
     pub requested_revision: String,
+    /// Directory of the package inside the repository, for lockfiles that record one.
+    pub subdirectory: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,6 +90,14 @@ pub enum ProviderEcosystem {
     Npm,
     Pypi,
     Crates,
+    Go,
+    Rubygems,
+    Maven,
+    Packagist,
+    Nuget,
+    Hex,
+    Pub,
+    Swift,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -76,6 +134,15 @@ pub enum ProviderError {
         path: PathBuf,
         source: serde_yml::Error,
     },
+
+    #[error("failed to parse XML provider input {path}: {source}")]
+    Xml {
+        path: PathBuf,
+        source: roxmltree::Error,
+    },
+
+    #[error("failed to parse Elixir term provider input {path}: {message}")]
+    ElixirTerm { path: PathBuf, message: String },
 }
 
 pub fn detect_supported_project_files(project_root: &Path) -> Vec<ProviderInputMatch> {
@@ -105,6 +172,30 @@ pub fn detect_supported_project_files(project_root: &Path) -> Vec<ProviderInputM
         });
     }
 
+    let poetry_lock = project_root.join(POETRY_LOCK);
+    if poetry_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Poetry,
+            path: poetry_lock,
+        });
+    }
+
+    let pdm_lock = project_root.join(PDM_LOCK);
+    if pdm_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Pdm,
+            path: pdm_lock,
+        });
+    }
+
+    let pipfile_lock = project_root.join(PIPFILE_LOCK);
+    if pipfile_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Pipfile,
+            path: pipfile_lock,
+        });
+    }
+
     let cargo_lock = project_root.join(CARGO_LOCK);
     if cargo_lock.exists() {
         matches.push(ProviderInputMatch {
@@ -121,6 +212,110 @@ pub fn detect_supported_project_files(project_root: &Path) -> Vec<ProviderInputM
         });
     }
 
+    let bun_lock = project_root.join(BUN_LOCK);
+    if bun_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Bun,
+            path: bun_lock,
+        });
+    }
+
+    let go_mod = project_root.join(GO_MOD);
+    if go_mod.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::GoMod,
+            path: go_mod,
+        });
+    }
+
+    let go_sum = project_root.join(GO_SUM);
+    if go_sum.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::GoSum,
+            path: go_sum,
+        });
+    }
+
+    let gemfile_lock = project_root.join(GEMFILE_LOCK);
+    if gemfile_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Bundler,
+            path: gemfile_lock,
+        });
+    }
+
+    let gradle_lockfile = project_root.join(GRADLE_LOCKFILE);
+    if gradle_lockfile.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::GradleLockfile,
+            path: gradle_lockfile,
+        });
+    }
+
+    let gradle_version_catalog = project_root.join(GRADLE_VERSION_CATALOG);
+    if gradle_version_catalog.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::GradleVersionCatalog,
+            path: gradle_version_catalog,
+        });
+    }
+
+    let pom_xml = project_root.join(POM_XML);
+    if pom_xml.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Pom,
+            path: pom_xml,
+        });
+    }
+
+    let composer_lock = project_root.join(COMPOSER_LOCK);
+    if composer_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Composer,
+            path: composer_lock,
+        });
+    }
+
+    let nuget_packages_lock = project_root.join(NUGET_PACKAGES_LOCK);
+    if nuget_packages_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::NugetPackagesLock,
+            path: nuget_packages_lock,
+        });
+    }
+
+    let directory_packages_props = project_root.join(DIRECTORY_PACKAGES_PROPS);
+    if directory_packages_props.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::DirectoryPackagesProps,
+            path: directory_packages_props,
+        });
+    }
+
+    let mix_lock = project_root.join(MIX_LOCK);
+    if mix_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::Mix,
+            path: mix_lock,
+        });
+    }
+
+    let pubspec_lock = project_root.join(PUBSPEC_LOCK);
+    if pubspec_lock.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::PubspecLock,
+            path: pubspec_lock,
+        });
+    }
+
+    let package_resolved = project_root.join(PACKAGE_RESOLVED);
+    if package_resolved.exists() {
+        matches.push(ProviderInputMatch {
+            provider: ProviderKind::PackageResolved,
+            path: package_resolved,
+        });
+    }
+
     matches
 }
 
@@ -132,7 +327,23 @@ pub fn parse_provider_input(
         ProviderKind::Pnpm => pnpm_lock::parse(&input.path),
         ProviderKind::Cargo => rust_cargo_lock::parse(&input.path),
         ProviderKind::Uv => python_uv_lock::parse(&input.path),
+        ProviderKind::Poetry => python_poetry_lock::parse(&input.path),
+        ProviderKind::Pdm => python_pdm_lock::parse(&input.path),
+        ProviderKind::Pipfile => python_pipfile_lock::parse(&input.path),
         ProviderKind::Yarn => yarn_lock::parse(&input.path),
+        ProviderKind::Bun => bun_lock::parse(&input.path),
+        ProviderKind::GoMod => go_mod::parse(&input.path),
+        ProviderKind::GoSum => go_sum::parse(&input.path),
+        ProviderKind::Bundler => ruby_gemfile_lock::parse(&input.path),
+        ProviderKind::GradleLockfile => gradle_lockfile::parse(&input.path),
+        ProviderKind::GradleVersionCatalog => gradle_version_catalog::parse(&input.path),
+        ProviderKind::Pom => maven_pom::parse(&input.path),
+        ProviderKind::Composer => php_composer_lock::parse(&input.path),
+        ProviderKind::NugetPackagesLock => dotnet_packages_lock::parse(&input.path),
+        ProviderKind::DirectoryPackagesProps => dotnet_directory_packages_props::parse(&input.path),
+        ProviderKind::Mix => elixir_mix_lock::parse(&input.path),
+        ProviderKind::PubspecLock => dart_pubspec_lock::parse(&input.path),
+        ProviderKind::PackageResolved => swift_package_resolved::parse(&input.path),
     }
 }
 
@@ -210,6 +421,104 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn parses_poetry_lock_fixture() {
+        let path = fixture("fixtures/python/poetry.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Poetry,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse poetry.lock");
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Pypi
+                && dep.name == "requests"
+                && dep.version == "2.32.3"
+                && dep.git_hint.is_none()
+        }));
+        assert!(deps.iter().any(|dep| {
+            dep.name == "demo-git-package"
+                && dep.git_hint.as_ref().is_some_and(|hint| {
+                    hint.url == "https://example.com/demo-git-package.git"
+                        && hint.requested_revision == "abc123def456"
+                })
+        }));
+    }
+
+    #[test]
+    fn parses_pdm_lock_fixture() {
+        let path = fixture("fixtures/python/pdm.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Pdm,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse pdm.lock");
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Pypi
+                && dep.name == "requests"
+                && dep.version == "2.32.3"
+                && dep.git_hint.is_none()
+        }));
+        assert!(deps.iter().any(|dep| {
+            dep.name == "demo-git-package"
+                && dep.git_hint.as_ref().is_some_and(|hint| {
+                    hint.url == "https://example.com/demo-git-package.git"
+                        && hint.requested_revision == "abc123def456"
+                })
+        }));
+    }
+
+    #[test]
+    fn poetry_lock_skips_entries_without_a_version() {
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Poetry,
+            path: fixture("fixtures/python/incomplete/poetry.lock"),
+        };
+
+        let deps = parse_provider_input(&input).expect("parse poetry.lock");
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "requests");
+        assert_eq!(deps[0].version, "2.32.3");
+    }
+
+    #[test]
+    fn pdm_lock_skips_entries_without_a_version() {
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Pdm,
+            path: fixture("fixtures/python/incomplete/pdm.lock"),
+        };
+
+        let deps = parse_provider_input(&input).expect("parse pdm.lock");
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "requests");
+        assert_eq!(deps[0].version, "2.32.3");
+    }
+
+    #[test]
+    fn parses_pipfile_lock_fixture() {
+        let path = fixture("fixtures/python/Pipfile.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Pipfile,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse Pipfile.lock");
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Pypi
+                && dep.name == "requests"
+                && dep.version == "2.32.3"
+                && dep.git_hint.is_none()
+        }));
+        assert!(deps.iter().any(|dep| {
+            dep.name == "demo-git-package"
+                && dep.git_hint.as_ref().is_some_and(|hint| {
+                    hint.url == "https://example.com/demo-git-package.git"
+                        && hint.requested_revision == "abc123def456"
+                })
+        }));
+    }
+
     #[test]
     fn parses_cargo_lock_fixture() {
         let path = fixture("fixtures/rust/Cargo.lock");
@@ -258,17 +567,492 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn parses_yarn_berry_lock_fixture() {
+        let path = fixture("fixtures/js/berry/yarn.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Yarn,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse yarn berry lock");
+        let packages = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("@types/react", "18.3.3"),
+                ("demo-git-package", "1.0.0"),
+                ("react", "18.3.1"),
+            ]
+        );
+        assert_eq!(
+            deps[1].git_hint,
+            Some(GitSourceHint {
+                url: String::from("https://github.com/acme/demo-git-package.git"),
+                requested_revision: String::from("abc123def4567890abc123def4567890abc123de"),
+                subdirectory: None,
+            })
+        );
+        assert!(deps[0].git_hint.is_none() && deps[2].git_hint.is_none());
+    }
+
+    #[test]
+    fn parses_bun_lock_fixture() {
+        let path = fixture("fixtures/js/bun.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Bun,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse bun lock");
+        let packages = deps
+            .iter()
+            .map(|dep| {
+                (
+                    dep.name.as_str(),
+                    dep.version.as_str(),
+                    dep.git_hint.as_ref().map(|hint| hint.url.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("@tanstack/query-core", "5.51.1", None),
+                (
+                    "demo-git-package",
+                    "abc123def4567890abc123def4567890abc123de",
+                    Some("https://github.com/acme/demo-git-package.git")
+                ),
+                ("loose-envify", "1.4.0", None),
+                ("react", "18.3.1", None),
+                (
+                    "ui-kit",
+                    "9f8e7d6",
+                    Some("https://github.com/acme/ui-kit.git")
+                ),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Npm)
+        );
+    }
+
+    #[test]
+    fn parses_go_mod_fixture_with_replacements() {
+        let path = fixture("fixtures/go/go.mod");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::GoMod,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse go.mod");
+        let modules = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                ("github.com/spf13/cobra", "v1.8.0"),
+                ("golang.org/x/text", "v0.14.0"),
+                ("github.com/fixture/forked", "v1.0.1"),
+                ("github.com/google/uuid", "v1.6.0"),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Go)
+        );
+    }
+
+    #[test]
+    fn parses_go_sum_fixture_skipping_go_mod_only_entries() {
+        let path = fixture("fixtures/go/go.sum");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::GoSum,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse go.sum");
+        assert_eq!(deps.len(), 3);
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Go
+                && dep.name == "github.com/spf13/cobra"
+                && dep.version == "v1.8.0"
+        }));
+        assert!(!deps.iter().any(|dep| dep.name == "github.com/spf13/pflag"));
+    }
+
+    #[test]
+    fn parses_gemfile_lock_fixture() {
+        let path = fixture("fixtures/ruby/Gemfile.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Bundler,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse Gemfile.lock");
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Rubygems
+                && dep.name == "rails"
+                && dep.version == "7.1.3"
+                && dep.git_hint.is_none()
+        }));
+        assert!(deps.iter().any(|dep| {
+            dep.name == "demo-git-package"
+                && dep.version == "1.0.0"
+                && dep.git_hint.as_ref().is_some_and(|hint| {
+                    hint.url == "https://example.com/demo-git-package.git"
+                        && hint.requested_revision == "abc123def456"
+                })
+        }));
+        assert_eq!(
+            deps.iter()
+                .filter(|dep| dep.name == "nokogiri")
+                .map(|dep| dep.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.16.2"]
+        );
+        assert!(!deps.iter().any(|dep| dep.name == "local_engine"));
+    }
+
+    #[test]
+    fn parses_gradle_lockfile_fixture() {
+        let path = fixture("fixtures/jvm/gradle.lockfile");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::GradleLockfile,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse gradle.lockfile");
+        assert_eq!(deps.len(), 3);
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Maven
+                && dep.name == "com.google.guava:guava"
+                && dep.version == "33.0.0-jre"
+        }));
+    }
+
+    #[test]
+    fn parses_gradle_version_catalog_fixture() {
+        let path = fixture("fixtures/jvm/libs.versions.toml");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::GradleVersionCatalog,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse libs.versions.toml");
+        let modules = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                ("com.google.guava:guava", "33.0.0-jre"),
+                ("org.junit.jupiter:junit-jupiter", "5.10.1"),
+                ("com.squareup.okhttp3:okhttp", "4.12.0"),
+                ("org.slf4j:slf4j-api", "2.0.9"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_pom_xml_fixture_with_managed_versions() {
+        let path = fixture("fixtures/jvm/pom.xml");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Pom,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse pom.xml");
+        let modules = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                ("com.google.guava:guava", "33.0.0-jre"),
+                ("org.slf4j:slf4j-api", "2.0.9"),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Maven)
+        );
+    }
+
+    #[test]
+    fn parses_composer_lock_fixture_with_exact_commits() {
+        let path = fixture("fixtures/php/composer.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Composer,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse composer.lock");
+        assert_eq!(deps.len(), 4);
+        assert!(deps.iter().any(|dep| {
+            dep.ecosystem == ProviderEcosystem::Packagist
+                && dep.name == "monolog/monolog"
+                && dep.version == "3.5.0"
+                && dep.git_hint.as_ref().is_some_and(|hint| {
+                    hint.url == "https://github.com/Seldaek/monolog.git"
+                        && hint.requested_revision == "c915e2634718dbc8a4a15c61b0e62e7a44e14448"
+                })
+        }));
+        assert!(
+            deps.iter()
+                .any(|dep| { dep.name == "phpunit/phpunit" && dep.git_hint.is_some() })
+        );
+        assert!(
+            deps.iter()
+                .any(|dep| { dep.name == "acme/internal-tools" && dep.git_hint.is_none() })
+        );
+    }
+
+    #[test]
+    fn parses_nuget_packages_lock_fixture_once_per_package() {
+        let path = fixture("fixtures/dotnet/packages.lock.json");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::NugetPackagesLock,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse packages.lock.json");
+        let packages = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("Newtonsoft.Json", "13.0.3"),
+                ("Microsoft.Extensions.Primitives", "8.0.0"),
+                ("Serilog", "3.1.1"),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Nuget && dep.git_hint.is_none())
+        );
+    }
+
+    #[test]
+    fn parses_directory_packages_props_fixture_with_properties() {
+        let path = fixture("fixtures/dotnet/Directory.Packages.props");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::DirectoryPackagesProps,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse Directory.Packages.props");
+        let packages = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("Newtonsoft.Json", "13.0.3"),
+                ("Serilog", "3.1.1"),
+                ("xunit", "2.6.6"),
+                ("Nerdbank.GitVersioning", "3.6.133"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_mix_lock_fixture_with_hex_packages_and_git_commits() {
+        let path = fixture("fixtures/elixir/mix.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::Mix,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse mix.lock");
+        let packages = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("castore", "1.0.5"),
+                ("jason", "1.4.1"),
+                ("phoenix", "8d2c4d8b1f7e3c2a9a6f0d5e4b3c2a1908f7e6d5"),
+                ("phoenix_pubsub", "2.1.3"),
+                ("telemetry", "1.2.1"),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Hex)
+        );
+        assert_eq!(
+            deps[2].git_hint,
+            Some(GitSourceHint {
+                url: String::from("https://github.com/phoenixframework/phoenix.git"),
+                requested_revision: String::from("8d2c4d8b1f7e3c2a9a6f0d5e4b3c2a1908f7e6d5"),
+                subdirectory: None,
+            })
+        );
+        assert!(
+            deps.iter()
+                .filter(|dep| dep.name != "phoenix")
+                .all(|dep| dep.git_hint.is_none())
+        );
+    }
+
+    #[test]
+    fn parses_pubspec_lock_fixture_with_git_subdirectories() {
+        let path = fixture("fixtures/dart/pubspec.lock");
+        let input = ProviderInputMatch {
+            provider: ProviderKind::PubspecLock,
+            path,
+        };
+
+        let deps = parse_provider_input(&input).expect("parse pubspec.lock");
+        let packages = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("collection", "1.18.0"),
+                ("design_tokens", "2.0.0"),
+                ("http", "1.2.0"),
+                ("shared_widgets", "0.3.1"),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Pub)
+        );
+        assert_eq!(
+            deps[3].git_hint,
+            Some(GitSourceHint {
+                url: String::from("https://github.com/acme/mobile-kit.git"),
+                requested_revision: String::from("5b3c1f2e8d7a6b9c0d1e2f3a4b5c6d7e8f9a0b1c"),
+                subdirectory: Some(String::from("packages/shared_widgets")),
+            })
+        );
+        assert_eq!(
+            deps[1]
+                .git_hint
+                .as_ref()
+                .map(|hint| hint.subdirectory.clone()),
+            Some(None)
+        );
+        assert!(deps[0].git_hint.is_none() && deps[2].git_hint.is_none());
+    }
+
+    #[test]
+    fn parses_package_resolved_fixtures_as_exact_git_hints() {
+        let deps = parse_provider_input(&ProviderInputMatch {
+            provider: ProviderKind::PackageResolved,
+            path: fixture("fixtures/swift/Package.resolved"),
+        })
+        .expect("parse Package.resolved v3");
+        let pins = deps
+            .iter()
+            .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pins,
+            vec![
+                ("alamofire", "5.9.1"),
+                ("design-system", "0.4.0"),
+                (
+                    "swift-collections",
+                    "94cf62b3ba8d4bed62680a282d4c25f9c63c2efb"
+                ),
+            ]
+        );
+        assert!(
+            deps.iter()
+                .all(|dep| dep.ecosystem == ProviderEcosystem::Swift)
+        );
+        assert_eq!(
+            deps[0].git_hint,
+            Some(GitSourceHint {
+                url: String::from("https://github.com/Alamofire/Alamofire.git"),
+                requested_revision: String::from("f455c2975872ccd2d9c81594c658af65716e9b9a"),
+                subdirectory: None,
+            })
+        );
+
+        let v1_deps = parse_provider_input(&ProviderInputMatch {
+            provider: ProviderKind::PackageResolved,
+            path: fixture("fixtures/swift/v1/Package.resolved"),
+        })
+        .expect("parse Package.resolved v1");
+        let v1_pins = v1_deps
+            .iter()
+            .map(|dep| {
+                (
+                    dep.name.as_str(),
+                    dep.version.as_str(),
+                    dep.git_hint.as_ref().map(|hint| hint.url.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            v1_pins,
+            vec![
+                (
+                    "alamofire",
+                    "5.9.1",
+                    Some("https://github.com/Alamofire/Alamofire.git")
+                ),
+                (
+                    "swift-log",
+                    "1.5.4",
+                    Some("https://github.com/apple/swift-log")
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn detects_project_files() {
         let temp = tempfile::tempdir().expect("tempdir");
         std::fs::write(temp.path().join(PACKAGE_LOCK), "{}").expect("write package-lock");
         std::fs::write(temp.path().join(PNPM_LOCK), "").expect("write pnpm lock");
         std::fs::write(temp.path().join(UV_LOCK), "").expect("write uv.lock");
+        std::fs::write(temp.path().join(POETRY_LOCK), "").expect("write poetry.lock");
+        std::fs::write(temp.path().join(PDM_LOCK), "").expect("write pdm.lock");
+        std::fs::write(temp.path().join(PIPFILE_LOCK), "{}").expect("write Pipfile.lock");
         std::fs::write(temp.path().join(CARGO_LOCK), "").expect("write cargo lock");
         std::fs::write(temp.path().join(YARN_LOCK), "").expect("write yarn lock");
+        std::fs::write(temp.path().join(BUN_LOCK), "{}").expect("write bun lock");
+        std::fs::write(temp.path().join(GO_MOD), "").expect("write go.mod");
+        std::fs::write(temp.path().join(GO_SUM), "").expect("write go.sum");
+        std::fs::write(temp.path().join(GEMFILE_LOCK), "").expect("write Gemfile.lock");
+        std::fs::write(temp.path().join(GRADLE_LOCKFILE), "").expect("write gradle.lockfile");
+        std::fs::create_dir_all(temp.path().join("gradle")).expect("create gradle dir");
+        std::fs::write(temp.path().join(GRADLE_VERSION_CATALOG), "")
+            .expect("write libs.versions.toml");
+        std::fs::write(temp.path().join(POM_XML), "").expect("write pom.xml");
+        std::fs::write(temp.path().join(COMPOSER_LOCK), "{}").expect("write composer.lock");
+        std::fs::write(temp.path().join(NUGET_PACKAGES_LOCK), "{}")
+            .expect("write packages.lock.json");
+        std::fs::write(temp.path().join(DIRECTORY_PACKAGES_PROPS), "<Project />")
+            .expect("write Directory.Packages.props");
+        std::fs::write(temp.path().join(MIX_LOCK), "%{}").expect("write mix.lock");
+        std::fs::write(temp.path().join(PUBSPEC_LOCK), "packages: {}\n")
+            .expect("write pubspec.lock");
+        std::fs::write(temp.path().join(PACKAGE_RESOLVED), "{}").expect("write Package.resolved");
 
         let detected = detect_supported_project_files(temp.path());
-        assert_eq!(detected.len(), 5);
+        assert_eq!(detected.len(), 21);
         assert!(
             detected
                 .iter()
@@ -289,10 +1073,75 @@ mod tests {
                 .iter()
                 .any(|m| matches!(m.provider, ProviderKind::Uv))
         );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Poetry))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Pdm))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Pipfile))
+        );
         assert!(
             detected
                 .iter()
                 .any(|m| matches!(m.provider, ProviderKind::Yarn))
         );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::GoMod))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::GoSum))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Bundler))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::GradleVersionCatalog))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Composer))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::DirectoryPackagesProps))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Mix))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::PubspecLock))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::PackageResolved))
+        );
+        assert!(
+            detected
+                .iter()
+                .any(|m| matches!(m.provider, ProviderKind::Bun))
+        );
     }
 }
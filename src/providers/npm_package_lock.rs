@@ -117,6 +117,7 @@ fn parse_git_hint_from_npm_resolved(resolved: &str) -> Option<GitSourceHint> {
     Some(GitSourceHint {
         url: url.to_string(),
         requested_revision: revision.to_string(),
+        subdirectory: None,
     })
 }
 
@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct ComposerLock {
+    #[serde(default)]
+    packages: Vec<ComposerPackage>,
+    #[serde(default, rename = "packages-dev")]
+    packages_dev: Vec<ComposerPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    source: Option<ComposerSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerSource {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    reference: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parsed: ComposerLock =
+        serde_json::from_str(&raw).map_err(|source| ProviderError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    Ok(parsed
+        .packages
+        .into_iter()
+        .chain(parsed.packages_dev)
+        .map(|package| NormalizedDependency {
+            ecosystem: ProviderEcosystem::Packagist,
+            name: package.name,
+            version: package.version,
+            git_hint: package.source.and_then(parse_git_hint_from_composer_source),
+            repository_url: None,
+        })
+        .collect())
+}
+
+// Composer locks the exact commit of every package installed from a VCS
+// repository, so `source.reference` needs no tag guessing.
+fn parse_git_hint_from_composer_source(source: ComposerSource) -> Option<GitSourceHint> {
+    if source.kind != "git" {
+        return None;
+    }
+
+    Some(GitSourceHint {
+        url: source.url,
+        requested_revision: source.reference.unwrap_or_else(|| String::from("HEAD")),
+        subdirectory: None,
+    })
+}
@@ -126,6 +126,7 @@ fn parse_git_hint_from_resolution(resolution: &PnpmResolution) -> Option<GitSour
     Some(GitSourceHint {
         url: repo.to_string(),
         requested_revision: commit.to_string(),
+        subdirectory: None,
     })
 }
 
@@ -139,6 +140,7 @@ fn parse_git_hint_from_version_ref(raw_ref: &str) -> Option<GitSourceHint> {
     Some(GitSourceHint {
         url: url.to_string(),
         requested_revision: revision.to_string(),
+        subdirectory: None,
     })
 }
 
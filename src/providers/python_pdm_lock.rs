@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct PdmLock {
+    #[serde(default)]
+    package: Vec<PdmPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PdmPackage {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    git: Option<String>,
+    #[serde(default)]
+    revision: Option<String>,
+    #[serde(default, rename = "ref")]
+    reference: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parsed: PdmLock = toml::from_str(&raw).map_err(|source| ProviderError::Toml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // Entries without a name or version (local path packages) cannot be resolved,
+    // so they are skipped rather than failing the whole lockfile.
+    Ok(parsed
+        .package
+        .into_iter()
+        .filter_map(|package| {
+            let (Some(name), Some(version)) = (package.name, package.version) else {
+                return None;
+            };
+            // `revision` is the commit PDM locked; `ref` is the branch or tag the
+            // user asked for.
+            let git_hint = package.git.map(|url| GitSourceHint {
+                url,
+                requested_revision: package
+                    .revision
+                    .or(package.reference)
+                    .unwrap_or_else(|| String::from("HEAD")),
+                subdirectory: None,
+            });
+
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Pypi,
+                name,
+                version,
+                git_hint,
+                repository_url: None,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct PipfileLock {
+    #[serde(default)]
+    default: BTreeMap<String, PipfileLockEntry>,
+    #[serde(default)]
+    develop: BTreeMap<String, PipfileLockEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileLockEntry {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    git: Option<String>,
+    #[serde(default, rename = "ref")]
+    reference: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parsed: PipfileLock = serde_json::from_str(&raw).map_err(|source| ProviderError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut deps: BTreeMap<String, NormalizedDependency> = BTreeMap::new();
+    for (name, entry) in parsed.default.into_iter().chain(parsed.develop) {
+        let git_hint = entry.git.map(|url| GitSourceHint {
+            url: url.strip_prefix("git+").unwrap_or(&url).to_string(),
+            requested_revision: entry
+                .reference
+                .clone()
+                .unwrap_or_else(|| String::from("HEAD")),
+            subdirectory: None,
+        });
+
+        // Registry entries pin `==<version>`; git entries usually carry only the ref.
+        let version = match entry.version {
+            Some(version) => version.trim_start_matches("==").to_string(),
+            None => match &git_hint {
+                Some(git_hint) => git_hint.requested_revision.clone(),
+                None => continue,
+            },
+        };
+
+        deps.entry(name.clone())
+            .or_insert_with(|| NormalizedDependency {
+                ecosystem: ProviderEcosystem::Pypi,
+                name,
+                version,
+                git_hint,
+                repository_url: None,
+            });
+    }
+
+    Ok(deps.into_values().collect())
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryPackage {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    source: Option<PoetrySource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetrySource {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    reference: Option<String>,
+    #[serde(default)]
+    resolved_reference: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parsed: PoetryLock = toml::from_str(&raw).map_err(|source| ProviderError::Toml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // Entries without a name or version cannot be resolved, so they are skipped
+    // rather than failing the whole lockfile.
+    Ok(parsed
+        .package
+        .into_iter()
+        .filter_map(|package| {
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Pypi,
+                name: package.name?,
+                version: package.version?,
+                git_hint: package.source.and_then(parse_git_hint_from_poetry_source),
+                repository_url: None,
+            })
+        })
+        .collect())
+}
+
+fn parse_git_hint_from_poetry_source(source: PoetrySource) -> Option<GitSourceHint> {
+    if source.kind != "git" {
+        return None;
+    }
+
+    // `resolved_reference` is the commit Poetry locked; `reference` is the
+    // branch, tag, or rev the user asked for.
+    let requested_revision = source
+        .resolved_reference
+        .or(source.reference)
+        .unwrap_or_else(|| String::from("HEAD"));
+
+    Some(GitSourceHint {
+        url: source.url?,
+        requested_revision,
+        subdirectory: None,
+    })
+}
@@ -65,5 +65,6 @@ fn parse_git_hint_from_uv_source(
     Some(GitSourceHint {
         url: git_url.to_string(),
         requested_revision: requested_revision.to_string(),
+        subdirectory: None,
     })
 }
@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+#[derive(Default)]
+struct GitSource {
+    remote: Option<String>,
+    revision: Option<String>,
+    reference: Option<String>,
+}
+
+enum Section {
+    Gem,
+    Git(GitSource),
+    Other,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut seen = BTreeSet::new();
+    let mut deps = Vec::new();
+    let mut section = Section::Other;
+    for line in raw.lines() {
+        if !line.starts_with(' ') {
+            // `PATH` blocks point at gems inside the project itself, so there is
+            // nothing to pull for them.
+            section = match line.trim_end() {
+                "GEM" => Section::Gem,
+                "GIT" => Section::Git(GitSource::default()),
+                _ => Section::Other,
+            };
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let line = line.trim();
+        match indent {
+            2 => {
+                if let Section::Git(source) = &mut section
+                    && let Some((key, value)) = line.split_once(": ")
+                {
+                    let value = value.trim().to_string();
+                    match key {
+                        "remote" => source.remote = Some(value),
+                        "revision" => source.revision = Some(value),
+                        "ref" | "tag" | "branch" => {
+                            source.reference.get_or_insert(value);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // Deeper lines list the dependencies of the spec above them.
+            4 => {
+                let git_hint = match &section {
+                    Section::Gem => None,
+                    Section::Git(source) => git_hint(source),
+                    Section::Other => continue,
+                };
+                let Some((name, version)) = parse_spec_line(line) else {
+                    continue;
+                };
+                if !seen.insert((name.clone(), version.clone())) {
+                    continue;
+                }
+                deps.push(NormalizedDependency {
+                    ecosystem: ProviderEcosystem::Rubygems,
+                    name,
+                    version,
+                    git_hint,
+                    repository_url: None,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(deps)
+}
+
+fn git_hint(source: &GitSource) -> Option<GitSourceHint> {
+    // `revision` is the commit Bundler locked; `ref`, `tag`, or `branch` is what the
+    // Gemfile asked for.
+    let requested_revision = source
+        .revision
+        .clone()
+        .or_else(|| source.reference.clone())
+        .unwrap_or_else(|| String::from("HEAD"));
+
+    Some(GitSourceHint {
+        url: source.remote.clone()?,
+        requested_revision,
+        subdirectory: None,
+    })
+}
+
+/// Parses `name (version)`, dropping the platform suffix of native gems such as
+/// `nokogiri (1.16.2-x86_64-linux)`; RubyGems versions never contain `-` themselves.
+fn parse_spec_line(line: &str) -> Option<(String, String)> {
+    let (name, rest) = line.split_once(" (")?;
+    let version = rest.strip_suffix(')')?;
+    let version = version
+        .split_once('-')
+        .map_or(version, |(version, _)| version);
+    Some((name.to_string(), version.to_string()))
+}
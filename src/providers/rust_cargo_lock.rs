@@ -58,6 +58,7 @@ fn parse_git_hint_from_source(source: &str) -> Option<GitSourceHint> {
         requested_revision: query
             .and_then(parse_preferred_revision)
             .unwrap_or_else(|| fallback_revision.to_string()),
+        subdirectory: None,
     })
 }
 
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
+
+/// Version 1 nests pins under `object`; versions 2 and 3 list them at the top level.
+#[derive(Debug, Deserialize)]
+struct PackageResolved {
+    #[serde(default)]
+    object: Option<PackageResolvedV1Object>,
+    #[serde(default)]
+    pins: Vec<PinV2>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageResolvedV1Object {
+    #[serde(default)]
+    pins: Vec<PinV1>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinV1 {
+    #[serde(rename = "repositoryURL")]
+    repository_url: String,
+    state: PinState,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinV2 {
+    identity: String,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    location: String,
+    state: PinState,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinState {
+    #[serde(default)]
+    revision: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let resolved: PackageResolved =
+        serde_json::from_str(&raw).map_err(|source| ProviderError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let pins = match resolved.object {
+        Some(object) => object
+            .pins
+            .into_iter()
+            .map(|pin| {
+                (
+                    identity_from_location(&pin.repository_url),
+                    pin.repository_url,
+                    pin.state,
+                )
+            })
+            .collect::<Vec<_>>(),
+        None => resolved
+            .pins
+            .into_iter()
+            // Registry pins carry no source control location to mirror.
+            .filter(|pin| pin.kind.as_deref() != Some("registry"))
+            .map(|pin| (pin.identity, pin.location, pin.state))
+            .collect(),
+    };
+
+    Ok(pins
+        .into_iter()
+        .filter_map(|(identity, location, state)| {
+            let revision = state.revision.filter(|revision| !revision.is_empty())?;
+            if identity.is_empty() || location.is_empty() {
+                return None;
+            }
+            Some(NormalizedDependency {
+                ecosystem: ProviderEcosystem::Swift,
+                name: identity,
+                // Branch and revision pins have no version; the commit stands in for it.
+                version: state.version.unwrap_or_else(|| revision.clone()),
+                git_hint: Some(GitSourceHint {
+                    url: location,
+                    requested_revision: revision,
+                    subdirectory: None,
+                }),
+                repository_url: None,
+            })
+        })
+        .collect())
+}
+
+/// SwiftPM derives a package identity from the last path component of its
+/// location, lowercased and without a `.git` suffix.
+fn identity_from_location(location: &str) -> String {
+    let last_component = location
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    last_component
+        .strip_suffix(".git")
+        .unwrap_or(last_component)
+        .to_ascii_lowercase()
+}
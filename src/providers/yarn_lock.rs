@@ -2,14 +2,30 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
+use serde::Deserialize;
+
 use crate::providers::{GitSourceHint, NormalizedDependency, ProviderEcosystem, ProviderError};
 
+/// A package entry of a Yarn Berry (v2+) lockfile.
+#[derive(Debug, Deserialize)]
+struct BerryEntry {
+    #[serde(default)]
+    version: Option<serde_yml::Value>,
+    #[serde(default)]
+    resolution: Option<String>,
+}
+
 pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderError> {
     let raw = fs::read_to_string(path).map_err(|source| ProviderError::Read {
         path: path.to_path_buf(),
         source,
     })?;
 
+    // Berry writes YAML with a `__metadata` block; Yarn v1 uses its own line format.
+    if raw.lines().any(|line| line.starts_with("__metadata:")) {
+        return parse_berry(path, &raw);
+    }
+
     let mut deps: BTreeMap<(String, String), NormalizedDependency> = BTreeMap::new();
     let mut selectors: Vec<String> = Vec::new();
     let mut version: Option<String> = None;
@@ -40,6 +56,81 @@ pub(super) fn parse(path: &Path) -> Result<Vec<NormalizedDependency>, ProviderEr
     Ok(deps.into_values().collect())
 }
 
+fn parse_berry(path: &Path, raw: &str) -> Result<Vec<NormalizedDependency>, ProviderError> {
+    let entries: BTreeMap<String, serde_yml::Value> =
+        serde_yml::from_str(raw).map_err(|source| ProviderError::Yaml {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let mut deps: BTreeMap<(String, String), NormalizedDependency> = BTreeMap::new();
+    for (key, value) in entries {
+        if key == "__metadata" {
+            continue;
+        }
+        let Ok(entry) = serde_yml::from_value::<BerryEntry>(value) else {
+            continue;
+        };
+        // Aliases resolve under the real package name, so the resolution names the package.
+        let Some((name, reference)) = entry.resolution.as_deref().and_then(split_descriptor) else {
+            continue;
+        };
+        let version = match entry.version {
+            Some(serde_yml::Value::String(version)) => version,
+            Some(serde_yml::Value::Number(version)) => version.to_string(),
+            _ => continue,
+        };
+
+        let git_hint = if reference.starts_with("npm:") {
+            None
+        } else {
+            // `workspace:`, `link:`, `portal:`, `patch:`, and tarball references are
+            // local or derived from another entry; only git commits can be pulled.
+            let Some(git_hint) = parse_berry_git_hint(reference) else {
+                continue;
+            };
+            Some(git_hint)
+        };
+        merge_dependency(
+            &mut deps,
+            NormalizedDependency {
+                ecosystem: ProviderEcosystem::Npm,
+                name: name.to_string(),
+                version,
+                git_hint,
+                repository_url: None,
+            },
+        );
+    }
+
+    Ok(deps.into_values().collect())
+}
+
+/// Splits `name@reference`, keeping the leading `@` of scoped names.
+fn split_descriptor(descriptor: &str) -> Option<(&str, &str)> {
+    let separator = descriptor.get(1..)?.find('@')? + 1;
+    let (name, reference) = (&descriptor[..separator], &descriptor[separator + 1..]);
+    (!name.is_empty() && !reference.is_empty()).then_some((name, reference))
+}
+
+/// Berry pins git dependencies as `<url>#commit=<sha>`, with other `key=value`
+/// pairs such as `head=main` joined by `&`.
+fn parse_berry_git_hint(reference: &str) -> Option<GitSourceHint> {
+    let (url, fragment) = reference.rsplit_once('#')?;
+    let commit = fragment
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("commit="))?;
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    if url.is_empty() || commit.is_empty() {
+        return None;
+    }
+    Some(GitSourceHint {
+        url: url.to_string(),
+        requested_revision: commit.to_string(),
+        subdirectory: None,
+    })
+}
+
 fn flush_entry(
     deps: &mut BTreeMap<(String, String), NormalizedDependency>,
     selectors: &[String],
@@ -111,6 +202,7 @@ fn parse_git_hint(raw: &str) -> Option<GitSourceHint> {
     Some(GitSourceHint {
         url: url.to_string(),
         requested_revision: revision.to_string(),
+        subdirectory: None,
     })
 }
 
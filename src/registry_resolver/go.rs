@@ -0,0 +1,484 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, normalize_subdirectory, published_archive_resolution,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_GO_PROXY: &str = "https://proxy.golang.org";
+// Hosts that serve repositories at `<host>/<owner>/<repo>`, so the repository can be
+// derived from the module path without a `?go-get=1` lookup.
+const KNOWN_GIT_HOSTS: [&str; 3] = ["github.com", "gitlab.com", "bitbucket.org"];
+
+#[derive(Debug, Deserialize)]
+struct GoVersionInfo {
+    #[serde(rename = "Version")]
+    version: String,
+    #[serde(default, rename = "Origin")]
+    origin: Option<GoOrigin>,
+}
+
+/// Where the proxy fetched the module from; recorded by `proxy.golang.org` since Go 1.19.
+#[derive(Debug, Deserialize)]
+struct GoOrigin {
+    #[serde(default, rename = "VCS")]
+    vcs: Option<String>,
+    #[serde(default, rename = "URL")]
+    url: Option<String>,
+    #[serde(default, rename = "Subdir")]
+    subdir: Option<String>,
+    #[serde(default, rename = "Hash")]
+    hash: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct GoImport<'a> {
+    prefix: &'a str,
+    vcs: &'a str,
+    repo_url: &'a str,
+}
+
+pub(super) fn resolve_go(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let module_path = spec.locator.clone();
+
+    let (selected_version, origin) = match (go_proxy_base()?, spec.version) {
+        (Some(proxy), Some(version)) => {
+            let info =
+                fetch_version_info(client, &proxy, &module_path, &version)?.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "go module '{}' does not contain requested version '{}'",
+                        module_path,
+                        version
+                    )
+                })?;
+            (info.version, info.origin)
+        }
+        (Some(proxy), None) => {
+            let info = fetch_latest_info(client, &proxy, &module_path)?;
+            (info.version, info.origin)
+        }
+        (None, Some(version)) => (version, None),
+        (None, None) => anyhow::bail!(
+            "go module '{}' needs a module proxy to pick the latest version; set GOPROXY or pass an explicit version",
+            module_path
+        ),
+    };
+
+    let origin = origin.filter(|origin| origin.vcs.as_deref() == Some("git"));
+    let origin_repository = origin.as_ref().and_then(|origin| {
+        let git_url = git_url_or_local_path(origin.url.as_deref()?)?;
+        let subdirectory = origin.subdir.as_deref().and_then(normalize_subdirectory);
+        Some((git_url, subdirectory))
+    });
+    let Some((git_url, subdirectory)) =
+        origin_repository.or_else(|| discover_repository(client, &module_path))
+    else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Go,
+            module_path,
+            selected_version,
+            "module proxy records no git origin and the module path does not map to a git repository",
+        ));
+    };
+
+    let published_commit = origin
+        .and_then(|origin| origin.hash)
+        .or_else(|| pseudo_version_commit(&selected_version).map(str::to_string));
+    let (requested_revision, requested_revision_source) = match published_commit {
+        Some(commit) => (commit, RequestedRevisionSource::ExactMetadata),
+        None => (
+            version_tag(&selected_version, subdirectory.as_deref()),
+            RequestedRevisionSource::VersionDerived,
+        ),
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Go,
+            locator: module_path,
+            git_url,
+            requested_revision,
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+pub(super) fn module_zip_artifact(
+    module_path: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    let proxy = go_proxy_base()?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "go module zips are served by a module proxy, but GOPROXY does not name one"
+        )
+    })?;
+    Ok(PublishedArtifact {
+        url: proxy_url(
+            &proxy,
+            module_path,
+            &format!("@v/{}.zip", escape_module_path(version)),
+        )?,
+        kind: ArtifactKind::GoModuleZip,
+        format: ArchiveFormat::Zip,
+        // Module zips wrap their files in `<module path>@<version>/`.
+        strip_components: module_path.split('/').count(),
+    })
+}
+
+/// First proxy named by `PKGREP_GOPROXY` or `GOPROXY`, or `None` when only `direct`
+/// is configured.
+fn go_proxy_base() -> anyhow::Result<Option<String>> {
+    let raw = std::env::var("PKGREP_GOPROXY")
+        .or_else(|_| std::env::var("GOPROXY"))
+        .unwrap_or_else(|_| DEFAULT_GO_PROXY.to_string());
+    for entry in raw
+        .split([',', '|'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        match entry {
+            "direct" => continue,
+            "off" => anyhow::bail!("go module proxy access is disabled (GOPROXY=off)"),
+            proxy => return Ok(Some(proxy.trim_end_matches('/').to_string())),
+        }
+    }
+    Ok(None)
+}
+
+fn proxy_url(proxy: &str, module_path: &str, suffix: &str) -> anyhow::Result<Url> {
+    let raw = format!("{}/{}/{}", proxy, escape_module_path(module_path), suffix);
+    Url::parse(&raw).with_context(|| format!("invalid go module proxy URL: {}", proxy))
+}
+
+/// Applies the module proxy's case encoding: every uppercase letter becomes `!` plus
+/// its lowercase form, so paths stay unique on case-insensitive filesystems.
+fn escape_module_path(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+        if ch.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(ch.to_ascii_lowercase());
+        } else {
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+fn fetch_version_info(
+    client: &RegistryClient,
+    proxy: &str,
+    module_path: &str,
+    version: &str,
+) -> anyhow::Result<Option<GoVersionInfo>> {
+    let url = proxy_url(
+        proxy,
+        module_path,
+        &format!("@v/{}.info", escape_module_path(version)),
+    )?;
+    let Some(bytes) = fetch_url_bytes(client, &url)
+        .with_context(|| format!("failed to fetch go module info for '{}'", module_path))?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .with_context(|| format!("failed to parse go module info from {}", url))
+}
+
+// File-based proxies often carry only `@v/list`, so fall back to the highest listed
+// version when `@latest` is missing.
+fn fetch_latest_info(
+    client: &RegistryClient,
+    proxy: &str,
+    module_path: &str,
+) -> anyhow::Result<GoVersionInfo> {
+    let latest_url = proxy_url(proxy, module_path, "@latest")?;
+    if let Some(bytes) = fetch_url_bytes(client, &latest_url).with_context(|| {
+        format!(
+            "failed to fetch latest go module version for '{}'",
+            module_path
+        )
+    })? {
+        return serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse go module info from {}", latest_url));
+    }
+
+    let list_url = proxy_url(proxy, module_path, "@v/list")?;
+    let list = fetch_url_bytes(client, &list_url)
+        .with_context(|| format!("failed to list go module versions for '{}'", module_path))?
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    let version = latest_listed_version(&list)
+        .ok_or_else(|| anyhow::anyhow!("go module proxy has no versions for '{}'", module_path))?;
+    fetch_version_info(client, proxy, module_path, &version)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "go module proxy lists '{}' for '{}' but serves no info for it",
+            version,
+            module_path
+        )
+    })
+}
+
+fn latest_listed_version(list: &str) -> Option<String> {
+    list.lines()
+        .map(str::trim)
+        .filter_map(|version| Some((listed_version_key(version)?, version)))
+        .max()
+        .map(|(_, version)| version.to_string())
+}
+
+// Like `go get`, any release wins over pre-releases; pre-releases compare as text.
+fn listed_version_key(version: &str) -> Option<(bool, u64, u64, u64, &str)> {
+    let core = version.strip_prefix('v')?;
+    let core = core.split_once('+').map_or(core, |(core, _)| core);
+    let (core, prerelease) = core.split_once('-').unwrap_or((core, ""));
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let key = (
+        prerelease.is_empty(),
+        parts.next()??,
+        parts.next()??,
+        parts.next()??,
+        prerelease,
+    );
+    parts.next().is_none().then_some(key)
+}
+
+/// Maps a module path to its git repository and the module's directory inside it,
+/// through the host layout or the `go-import` meta tag served at `?go-get=1`.
+fn discover_repository(
+    client: &RegistryClient,
+    module_path: &str,
+) -> Option<(String, Option<String>)> {
+    let components = module_path.split('/').collect::<Vec<_>>();
+    if components.len() >= 3 && KNOWN_GIT_HOSTS.contains(&components[0]) {
+        return Some((
+            format!("https://{}.git", components[..3].join("/")),
+            module_subdirectory(&components[3..]),
+        ));
+    }
+
+    let url = Url::parse(&format!("https://{module_path}?go-get=1")).ok()?;
+    let html = match fetch_url_bytes(client, &url) {
+        Ok(Some(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+        Ok(None) => return None,
+        Err(err) => {
+            warn!(
+                module = %module_path,
+                error = %format!("{err:#}"),
+                "go-get discovery failed"
+            );
+            return None;
+        }
+    };
+    let import = parse_go_import(&html, module_path)?;
+    if import.vcs != "git" {
+        debug!(
+            module = %module_path,
+            vcs = import.vcs,
+            "go-import meta tag does not point at a git repository"
+        );
+        return None;
+    }
+
+    let git_url = git_url_or_local_path(import.repo_url)?;
+    let relative = module_path
+        .strip_prefix(import.prefix)
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    let components = relative
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+    Some((git_url, module_subdirectory(&components)))
+}
+
+fn parse_go_import<'a>(html: &'a str, module_path: &str) -> Option<GoImport<'a>> {
+    for tag in html.split("<meta").skip(1) {
+        let tag = tag.split('>').next().unwrap_or_default();
+        if html_attribute(tag, "name") != Some("go-import") {
+            continue;
+        }
+        let Some(content) = html_attribute(tag, "content") else {
+            continue;
+        };
+        let mut fields = content.split_whitespace();
+        let (Some(prefix), Some(vcs), Some(repo_url)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if module_path == prefix || module_path.starts_with(&format!("{prefix}/")) {
+            return Some(GoImport {
+                prefix,
+                vcs,
+                repo_url,
+            });
+        }
+    }
+    None
+}
+
+fn html_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    for quote in ['"', '\''] {
+        let marker = format!("{name}={quote}");
+        if let Some(start) = tag.find(&marker) {
+            return tag[start + marker.len()..].split(quote).next();
+        }
+    }
+    None
+}
+
+// A trailing `/vN` is usually a major-version suffix served from a branch or tag of
+// the repository root rather than a real directory, so it is not part of the path.
+fn module_subdirectory(components: &[&str]) -> Option<String> {
+    let components = match components.split_last() {
+        Some((last, rest)) if is_major_version_suffix(last) => rest,
+        _ => components,
+    };
+    normalize_subdirectory(&components.join("/"))
+}
+
+fn is_major_version_suffix(component: &str) -> bool {
+    component
+        .strip_prefix('v')
+        .and_then(|major| major.parse::<u64>().ok())
+        .is_some_and(|major| major >= 2)
+}
+
+/// Tag a release version is published under: nested modules prefix it with their
+/// directory, and `+incompatible` is not part of the tag.
+fn version_tag(version: &str, subdirectory: Option<&str>) -> String {
+    let version = version.strip_suffix("+incompatible").unwrap_or(version);
+    let prefix = subdirectory
+        .map(|subdirectory| {
+            let components = subdirectory.split('/').collect::<Vec<_>>();
+            match components.split_last() {
+                Some((last, rest)) if is_major_version_suffix(last) => rest.join("/"),
+                _ => subdirectory.to_string(),
+            }
+        })
+        .unwrap_or_default();
+    if prefix.is_empty() {
+        version.to_string()
+    } else {
+        format!("{prefix}/{version}")
+    }
+}
+
+/// Commit prefix encoded in a pseudo-version such as
+/// `v0.0.0-20240101120000-abcdef123456` or `v1.2.4-0.20240101120000-abcdef123456`.
+fn pseudo_version_commit(version: &str) -> Option<&str> {
+    let version = version.strip_suffix("+incompatible").unwrap_or(version);
+    let (rest, commit) = version.rsplit_once('-')?;
+    let is_commit = commit.len() == 12
+        && commit
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || ('a'..='f').contains(&ch));
+    let timestamp = rest.rsplit(['-', '.']).next()?;
+    let is_timestamp = (8..=14).contains(&timestamp.len())
+        && timestamp.chars().all(|ch| ch.is_ascii_digit())
+        && rest.len() > timestamp.len();
+    (version.starts_with('v') && is_commit && is_timestamp).then_some(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::metadata_cache::MetadataCache;
+    use super::*;
+
+    #[test]
+    fn escapes_uppercase_module_path_letters() {
+        assert_eq!(
+            escape_module_path("github.com/BurntSushi/toml"),
+            "github.com/!burnt!sushi/toml"
+        );
+    }
+
+    #[test]
+    fn pseudo_versions_map_to_commit_prefixes() {
+        assert_eq!(
+            pseudo_version_commit("v0.0.0-20240101-abcdef123456"),
+            Some("abcdef123456")
+        );
+        assert_eq!(
+            pseudo_version_commit("v1.2.4-0.20191109021931-daa7c04131f5"),
+            Some("daa7c04131f5")
+        );
+        assert_eq!(
+            pseudo_version_commit("v2.0.0-pre.0.20191109021931-daa7c04131f5+incompatible"),
+            Some("daa7c04131f5")
+        );
+        assert_eq!(pseudo_version_commit("v1.8.0"), None);
+        assert_eq!(pseudo_version_commit("v1.0.0-rc.1"), None);
+    }
+
+    #[test]
+    fn release_tags_use_nested_module_directory_prefix() {
+        assert_eq!(version_tag("v1.8.0", None), "v1.8.0");
+        assert_eq!(version_tag("v2.0.0+incompatible", None), "v2.0.0");
+        assert_eq!(version_tag("v0.3.1", Some("otel/sdk")), "otel/sdk/v0.3.1");
+        assert_eq!(version_tag("v2.1.0", Some("api/v2")), "api/v2.1.0");
+    }
+
+    #[test]
+    fn known_hosts_map_module_path_to_repository_and_subdirectory() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let client = &RegistryClient::with_cache(MetadataCache::new(temp.path(), false))
+            .expect("registry client");
+        assert_eq!(
+            discover_repository(client, "github.com/aws/aws-sdk-go-v2/service/s3"),
+            Some((
+                String::from("https://github.com/aws/aws-sdk-go-v2.git"),
+                Some(String::from("service/s3"))
+            ))
+        );
+        assert_eq!(
+            discover_repository(client, "github.com/go-chi/chi/v5"),
+            Some((String::from("https://github.com/go-chi/chi.git"), None))
+        );
+    }
+
+    #[test]
+    fn parses_go_import_meta_tag_for_module_prefix() {
+        let html = r#"<html><head>
+<meta name="go-source" content="golang.org/x/text https://github.com/golang/text">
+<meta name="go-import" content="golang.org/x/text git https://go.googlesource.com/text">
+</head></html>"#;
+
+        assert_eq!(
+            parse_go_import(html, "golang.org/x/text/unicode/norm"),
+            Some(GoImport {
+                prefix: "golang.org/x/text",
+                vcs: "git",
+                repo_url: "https://go.googlesource.com/text",
+            })
+        );
+        assert_eq!(parse_go_import(html, "golang.org/x/textual"), None);
+    }
+
+    #[test]
+    fn picks_highest_release_from_version_list() {
+        assert_eq!(
+            latest_listed_version("v1.2.0\nv1.10.0\nv1.11.0-rc.1\nv1.9.3\n").as_deref(),
+            Some("v1.10.0")
+        );
+        assert_eq!(
+            latest_listed_version("v0.1.0-rc.1\nv0.1.0-rc.2\n").as_deref(),
+            Some("v0.1.0-rc.2")
+        );
+        assert_eq!(latest_listed_version(""), None);
+    }
+}
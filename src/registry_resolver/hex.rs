@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, published_archive_resolution,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_HEX_API_BASE: &str = "https://hex.pm/api";
+const DEFAULT_HEX_REPOSITORY_BASE: &str = "https://repo.hex.pm";
+// `links` is free-form; these are the labels packages use for their source repository.
+const SOURCE_LINK_LABELS: [&str; 7] = [
+    "github",
+    "gitlab",
+    "source",
+    "source code",
+    "repository",
+    "bitbucket",
+    "codeberg",
+];
+const FORGE_HOSTS: [&str; 4] = ["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"];
+
+/// The fields pkgrep reads from `/api/packages/<name>`.
+#[derive(Debug, Deserialize)]
+struct HexPackage {
+    #[serde(default)]
+    latest_stable_version: Option<String>,
+    #[serde(default)]
+    latest_version: Option<String>,
+    #[serde(default)]
+    meta: HexPackageMeta,
+    #[serde(default)]
+    releases: Vec<HexRelease>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HexPackageMeta {
+    #[serde(default)]
+    links: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HexRelease {
+    version: String,
+}
+
+pub(super) fn resolve_hex(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let endpoint = hex_url(&hex_api_base(), &["packages", &package_name])?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch hex metadata from {}", endpoint))?
+        .ok_or_else(|| anyhow::anyhow!("hex package '{}' was not found", package_name))?;
+    let package: HexPackage = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse hex metadata JSON for '{}'", package_name))?;
+
+    let selected_version = match spec.version {
+        Some(version) => {
+            if !package
+                .releases
+                .iter()
+                .any(|release| release.version == version)
+            {
+                anyhow::bail!(
+                    "hex package '{}' does not contain requested version '{}'",
+                    package_name,
+                    version
+                );
+            }
+            version
+        }
+        None => package
+            .latest_stable_version
+            .clone()
+            .or_else(|| package.latest_version.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("hex package '{}' has no published version", package_name)
+            })?,
+    };
+
+    let Some(repository_url) = source_link(&package.meta.links) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Hex,
+            package_name,
+            selected_version,
+            "metadata links do not include a source repository",
+        ));
+    };
+    let Some(git_url) = git_url_or_local_path(repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Hex,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Hex,
+            locator: package_name,
+            git_url,
+            requested_revision: selected_version.clone(),
+            subdirectory: None,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source: RequestedRevisionSource::VersionDerived,
+    })
+}
+
+pub(super) fn tarball_artifact(
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    Ok(PublishedArtifact {
+        url: hex_url(
+            &hex_repository_base(),
+            &["tarballs", &format!("{package_name}-{version}.tar")],
+        )?,
+        kind: ArtifactKind::HexTarball,
+        format: ArchiveFormat::HexTarball,
+        strip_components: 0,
+    })
+}
+
+fn hex_api_base() -> String {
+    std::env::var("PKGREP_HEX_API_URL").unwrap_or_else(|_| DEFAULT_HEX_API_BASE.to_string())
+}
+
+fn hex_repository_base() -> String {
+    std::env::var("PKGREP_HEX_REPOSITORY_URL")
+        .unwrap_or_else(|_| DEFAULT_HEX_REPOSITORY_BASE.to_string())
+}
+
+fn hex_url(base: &str, segments: &[&str]) -> anyhow::Result<Url> {
+    let mut url = Url::parse(base).with_context(|| format!("invalid hex URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid hex URL path: {}", base))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// Prefers a link labelled as the source repository, then any link to a known forge.
+fn source_link(links: &BTreeMap<String, String>) -> Option<&str> {
+    let labelled = links.iter().find_map(|(label, url)| {
+        SOURCE_LINK_LABELS
+            .contains(&label.trim().to_ascii_lowercase().as_str())
+            .then_some(url.as_str())
+    });
+    labelled
+        .or_else(|| {
+            links.values().map(String::as_str).find(|url| {
+                Url::parse(url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .is_some_and(|host| FORGE_HOSTS.contains(&host.as_str()))
+            })
+        })
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_source_repository_from_package_links() {
+        let links = BTreeMap::from([
+            (
+                String::from("Changelog"),
+                String::from("https://hexdocs.pm/jason/changelog.html"),
+            ),
+            (
+                String::from("GitHub"),
+                String::from("https://github.com/michalmuskala/jason"),
+            ),
+        ]);
+        assert_eq!(
+            source_link(&links),
+            Some("https://github.com/michalmuskala/jason")
+        );
+
+        let unlabelled = BTreeMap::from([
+            (
+                String::from("Docs"),
+                String::from("https://hexdocs.pm/demo"),
+            ),
+            (
+                String::from("Project"),
+                String::from("https://gitlab.com/acme/demo"),
+            ),
+        ]);
+        assert_eq!(
+            source_link(&unlabelled),
+            Some("https://gitlab.com/acme/demo")
+        );
+        assert_eq!(
+            source_link(&BTreeMap::from([(
+                String::from("Docs"),
+                String::from("https://hexdocs.pm/demo")
+            )])),
+            None
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::path::Path;
+
+use anyhow::Context;
+use reqwest::Url;
+use tracing::warn;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, normalize_subdirectory, published_archive_resolution,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::pom::Pom;
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_MAVEN_REPOSITORY_BASE: &str = "https://repo1.maven.org/maven2";
+// Parent chains are short in practice; the bound also guards against cycles.
+const MAX_PARENT_DEPTH: usize = 8;
+
+struct ScmRepository {
+    git_url: String,
+    /// The release tag recorded in the artifact's own POM, if any.
+    tag: Option<String>,
+    /// Set when the `<scm>` came from a parent POM, which usually describes the
+    /// root of a multi-module build.
+    inherited: bool,
+}
+
+pub(super) fn resolve_maven(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let coordinates = spec.locator.clone();
+    let (group_id, artifact_id) = split_coordinates(&coordinates)?;
+
+    let selected_version = match spec.version {
+        Some(version) => version,
+        None => latest_version(client, group_id, artifact_id)?,
+    };
+    let pom = fetch_pom(client, group_id, artifact_id, &selected_version)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "maven artifact '{}' does not contain requested version '{}'",
+            coordinates,
+            selected_version
+        )
+    })?;
+
+    let Some(scm) = scm_repository(client, &pom) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Maven,
+            coordinates,
+            selected_version,
+            "POM (and its parent POMs) does not provide a git <scm> connection or URL",
+        ));
+    };
+
+    // Modules of a multi-module build conventionally live in a directory named after
+    // their artifactId; the link falls back to the repository root when it does not.
+    let subdirectory = if scm.inherited {
+        normalize_subdirectory(artifact_id)
+    } else {
+        None
+    };
+    let (requested_revision, requested_revision_source) = match scm.tag {
+        Some(tag) => (tag, RequestedRevisionSource::ExactMetadata),
+        None => (
+            selected_version.clone(),
+            RequestedRevisionSource::VersionDerived,
+        ),
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Maven,
+            locator: coordinates,
+            git_url: scm.git_url,
+            requested_revision,
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+pub(super) fn sources_jar_artifact(
+    coordinates: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    let (group_id, artifact_id) = split_coordinates(coordinates)?;
+    Ok(PublishedArtifact {
+        url: artifact_url(
+            group_id,
+            artifact_id,
+            Some(version),
+            &format!("{artifact_id}-{version}-sources.jar"),
+        )?,
+        kind: ArtifactKind::MavenSourcesJar,
+        format: ArchiveFormat::Zip,
+        strip_components: 0,
+    })
+}
+
+fn split_coordinates(coordinates: &str) -> anyhow::Result<(&str, &str)> {
+    match coordinates.split_once(':') {
+        Some((group_id, artifact_id))
+            if !group_id.is_empty() && !artifact_id.is_empty() && !artifact_id.contains(':') =>
+        {
+            Ok((group_id, artifact_id))
+        }
+        _ => anyhow::bail!(
+            "invalid maven coordinates '{}': expected '<groupId>:<artifactId>'",
+            coordinates
+        ),
+    }
+}
+
+/// Repository root from `PKGREP_MAVEN_REPOSITORY_URL`: an HTTP(S) or `file://` URL,
+/// or a local directory laid out like Maven Central.
+fn maven_repository_base() -> anyhow::Result<Url> {
+    let base = std::env::var("PKGREP_MAVEN_REPOSITORY_URL")
+        .unwrap_or_else(|_| DEFAULT_MAVEN_REPOSITORY_BASE.to_string());
+    if Path::new(&base).is_absolute() {
+        return Url::from_directory_path(&base)
+            .map_err(|_| anyhow::anyhow!("invalid maven repository directory: {}", base));
+    }
+    Url::parse(&base).with_context(|| format!("invalid maven repository URL: {}", base))
+}
+
+fn artifact_url(
+    group_id: &str,
+    artifact_id: &str,
+    version: Option<&str>,
+    file_name: &str,
+) -> anyhow::Result<Url> {
+    let mut url = maven_repository_base()?;
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("invalid maven repository URL path"))?;
+        segments.pop_if_empty();
+        segments.extend(group_id.split('.'));
+        segments.push(artifact_id);
+        if let Some(version) = version {
+            segments.push(version);
+        }
+        segments.push(file_name);
+    }
+    Ok(url)
+}
+
+fn latest_version(
+    client: &RegistryClient,
+    group_id: &str,
+    artifact_id: &str,
+) -> anyhow::Result<String> {
+    let endpoint = artifact_url(group_id, artifact_id, None, "maven-metadata.xml")?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch maven metadata from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "maven artifact '{}:{}' was not found",
+                group_id,
+                artifact_id
+            )
+        })?;
+    let raw = String::from_utf8(bytes)
+        .with_context(|| format!("maven metadata at {} is not UTF-8", endpoint))?;
+    latest_version_from_metadata(&raw)
+        .with_context(|| format!("failed to parse maven metadata from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "maven artifact '{}:{}' has no published version",
+                group_id,
+                artifact_id
+            )
+        })
+}
+
+/// Picks `<release>`, then `<latest>`, then the last listed version from
+/// `maven-metadata.xml`.
+fn latest_version_from_metadata(raw: &str) -> anyhow::Result<Option<String>> {
+    let document = roxmltree::Document::parse(raw)?;
+    let Some(versioning) = document
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("versioning"))
+    else {
+        return Ok(None);
+    };
+    let text_of = |name: &str| {
+        versioning
+            .children()
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    let listed = || {
+        versioning
+            .children()
+            .find(|node| node.has_tag_name("versions"))?
+            .children()
+            .filter(|node| node.has_tag_name("version"))
+            .filter_map(|node| node.text())
+            .map(str::trim)
+            .rfind(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    Ok(text_of("release")
+        .or_else(|| text_of("latest"))
+        .or_else(listed))
+}
+
+fn fetch_pom(
+    client: &RegistryClient,
+    group_id: &str,
+    artifact_id: &str,
+    version: &str,
+) -> anyhow::Result<Option<Pom>> {
+    let endpoint = artifact_url(
+        group_id,
+        artifact_id,
+        Some(version),
+        &format!("{artifact_id}-{version}.pom"),
+    )?;
+    let Some(bytes) = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch POM from {}", endpoint))?
+    else {
+        return Ok(None);
+    };
+    let raw =
+        String::from_utf8(bytes).with_context(|| format!("POM at {} is not UTF-8", endpoint))?;
+    Pom::parse(&raw)
+        .map(Some)
+        .with_context(|| format!("failed to parse POM from {}", endpoint))
+}
+
+/// Reads the git repository from `<scm>`, walking up parent POMs of the same
+/// `groupId`. Organization-wide parents (for example `org.apache:apache`) describe
+/// their own repository, so the walk stops at a parent from another group.
+fn scm_repository(client: &RegistryClient, pom: &Pom) -> Option<ScmRepository> {
+    let group_id = pom.effective_group_id()?.to_string();
+    let mut current = pom.clone();
+    let mut inherited = false;
+    for _ in 0..MAX_PARENT_DEPTH {
+        if let Some(git_url) = scm_git_url(&current) {
+            let tag = (!inherited)
+                .then(|| current.scm.as_ref()?.tag.as_deref())
+                .flatten()
+                .and_then(|tag| current.interpolate(tag))
+                .filter(|tag| tag != "HEAD");
+            return Some(ScmRepository {
+                git_url,
+                tag,
+                inherited,
+            });
+        }
+
+        let parent = current.parent.clone()?;
+        if parent.group_id != group_id {
+            return None;
+        }
+        current = match fetch_pom(
+            client,
+            &parent.group_id,
+            &parent.artifact_id,
+            &parent.version,
+        ) {
+            Ok(Some(parent_pom)) => parent_pom,
+            Ok(None) => return None,
+            Err(err) => {
+                warn!(
+                    parent = %format!("{}:{}:{}", parent.group_id, parent.artifact_id, parent.version),
+                    error = %format!("{err:#}"),
+                    "failed to read parent POM"
+                );
+                return None;
+            }
+        };
+        inherited = true;
+    }
+    None
+}
+
+fn scm_git_url(pom: &Pom) -> Option<String> {
+    let scm = pom.scm.as_ref()?;
+    [
+        scm.connection.as_deref(),
+        scm.developer_connection.as_deref(),
+        scm.url.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|raw| pom.interpolate(raw))
+    .find_map(|raw| scm_url_to_git(&raw))
+}
+
+/// Maps a Maven SCM URL (`scm:git:<url>`) or plain repository URL to a git URL;
+/// other SCM providers (`scm:svn:...`) are not supported.
+fn scm_url_to_git(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let raw = match raw.strip_prefix("scm:") {
+        Some(provider_url) => provider_url.strip_prefix("git:")?,
+        None => raw,
+    };
+    git_url_or_local_path(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_scm_connections_to_git_urls() {
+        assert_eq!(
+            scm_url_to_git("scm:git:https://github.com/google/guava.git").as_deref(),
+            Some("https://github.com/google/guava.git")
+        );
+        assert_eq!(
+            scm_url_to_git("scm:git:git@github.com:google/guava.git").as_deref(),
+            Some("https://github.com/google/guava.git")
+        );
+        assert_eq!(
+            scm_url_to_git("https://github.com/google/guava").as_deref(),
+            Some("https://github.com/google/guava.git")
+        );
+        assert_eq!(
+            scm_url_to_git("scm:svn:https://svn.apache.org/repos/asf/commons"),
+            None
+        );
+    }
+
+    #[test]
+    fn prefers_release_version_from_maven_metadata() {
+        let metadata = r#"<metadata>
+  <groupId>com.google.guava</groupId>
+  <artifactId>guava</artifactId>
+  <versioning>
+    <latest>33.1.0-jre-SNAPSHOT</latest>
+    <release>33.0.0-jre</release>
+    <versions><version>32.1.3-jre</version><version>33.0.0-jre</version></versions>
+  </versioning>
+</metadata>"#;
+        assert_eq!(
+            latest_version_from_metadata(metadata)
+                .expect("parse")
+                .as_deref(),
+            Some("33.0.0-jre")
+        );
+
+        let listed_only = "<metadata><versioning><versions><version>1.0</version><version>1.1</version></versions></versioning></metadata>";
+        assert_eq!(
+            latest_version_from_metadata(listed_only)
+                .expect("parse")
+                .as_deref(),
+            Some("1.1")
+        );
+    }
+
+    #[test]
+    fn rejects_coordinates_without_an_artifact_id() {
+        assert!(split_coordinates("com.google.guava").is_err());
+        assert!(split_coordinates("com.google.guava:guava:jar").is_err());
+        assert_eq!(
+            split_coordinates("com.google.guava:guava").expect("coordinates"),
+            ("com.google.guava", "guava")
+        );
+    }
+}
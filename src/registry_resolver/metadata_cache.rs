@@ -0,0 +1,234 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Context;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ETAG, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use super::registry_get;
+
+const METADATA_CACHE_DIR: &str = "registry-metadata";
+
+/// Registry responses kept on disk under the cache root and revalidated with their
+/// `ETag` / `Last-Modified` validators; offline, they are served without asking.
+#[derive(Clone, Debug)]
+pub(super) struct MetadataCache {
+    dir: PathBuf,
+    offline: bool,
+}
+
+/// Validators stored on the first line of a cache entry, ahead of the body.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedValidators {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+static WRITE_SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+impl MetadataCache {
+    pub(super) fn new(cache_root: &Path, offline: bool) -> Self {
+        Self {
+            dir: cache_root.join(METADATA_CACHE_DIR),
+            offline,
+        }
+    }
+
+    pub(super) fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Fetches `url` through the cache. Returns `None` when the registry reports the
+    /// resource missing (HTTP 404/410).
+    pub(super) fn fetch(&self, client: &Client, url: &Url) -> anyhow::Result<Option<Vec<u8>>> {
+        let entry_path = self.entry_path(url);
+        let cached = read_entry(&entry_path, url);
+        if self.offline {
+            return match cached {
+                Some((_, body)) => Ok(Some(body)),
+                None => anyhow::bail!(
+                    "offline mode: no cached registry metadata for {url}; run the same command once with network access to cache it"
+                ),
+            };
+        }
+
+        let mut request = registry_get(client, url);
+        if let Some((validators, _)) = &cached {
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match request.send() {
+            Ok(response) => response,
+            Err(err) => {
+                let Some((_, body)) = cached else {
+                    return Err(err).with_context(|| format!("failed to fetch {}", url));
+                };
+                warn!(
+                    url = %url,
+                    error = %err,
+                    "registry unreachable; using cached metadata"
+                );
+                return Ok(Some(body));
+            }
+        };
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some((_, body)) = cached
+        {
+            debug!(url = %url, "registry metadata not modified; using cached copy");
+            return Ok(Some(body));
+        }
+        if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+            return Ok(None);
+        }
+
+        let response = response
+            .error_for_status()
+            .with_context(|| format!("request failed for {}", url))?;
+        let validators = CachedValidators {
+            url: url.to_string(),
+            etag: header_value(&response, ETAG),
+            last_modified: header_value(&response, LAST_MODIFIED),
+        };
+        let body = response
+            .bytes()
+            .with_context(|| format!("failed to read response body from {}", url))?
+            .to_vec();
+        if let Err(err) = write_entry(&entry_path, &validators, &body) {
+            warn!(
+                url = %url,
+                error = %format!("{err:#}"),
+                "failed to cache registry metadata"
+            );
+        }
+        Ok(Some(body))
+    }
+
+    fn entry_path(&self, url: &Url) -> PathBuf {
+        let digest = Sha256::digest(url.as_str().as_bytes());
+        let name = digest
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        self.dir.join(name)
+    }
+}
+
+fn header_value(response: &Response, name: HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn read_entry(path: &Path, url: &Url) -> Option<(CachedValidators, Vec<u8>)> {
+    let bytes = fs::read(path).ok()?;
+    let newline = bytes.iter().position(|byte| *byte == b'\n')?;
+    let validators: CachedValidators = serde_json::from_slice(&bytes[..newline]).ok()?;
+    if validators.url != url.as_str() {
+        return None;
+    }
+    Some((validators, bytes[newline + 1..].to_vec()))
+}
+
+// Written to a unique staging file and renamed into place, so concurrent pulls never
+// read a half-written entry.
+fn write_entry(path: &Path, validators: &CachedValidators, body: &[u8]) -> anyhow::Result<()> {
+    let dir = path
+        .parent()
+        .context("metadata cache entry has no parent directory")?;
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create metadata cache dir {}", dir.display()))?;
+    let staging_path = path.with_extension(format!(
+        "tmp-{}-{}",
+        std::process::id(),
+        WRITE_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut file = fs::File::create(&staging_path)
+        .with_context(|| format!("failed to create {}", staging_path.display()))?;
+    serde_json::to_writer(&mut file, validators)
+        .context("failed to serialize metadata cache validators")?;
+    file.write_all(b"\n")
+        .and_then(|()| file.write_all(body))
+        .with_context(|| format!("failed to write {}", staging_path.display()))?;
+    drop(file);
+
+    if let Err(err) = fs::rename(&staging_path, path) {
+        let _ = fs::remove_file(&staging_path);
+        return Err(err).with_context(|| {
+            format!(
+                "failed to move metadata cache entry into {}",
+                path.display()
+            )
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_lookups_serve_cached_entries_and_fail_clearly_otherwise() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let cache = MetadataCache::new(temp.path(), true);
+        let client = Client::new();
+        let cached_url = Url::parse("https://registry.example/left-pad").expect("url");
+        let missing_url = Url::parse("https://registry.example/right-pad").expect("url");
+
+        write_entry(
+            &cache.entry_path(&cached_url),
+            &CachedValidators {
+                url: cached_url.to_string(),
+                etag: Some(String::from("\"v1\"")),
+                last_modified: None,
+            },
+            b"{\"name\":\"left-pad\"}\n",
+        )
+        .expect("write entry");
+
+        assert_eq!(
+            cache.fetch(&client, &cached_url).expect("cached fetch"),
+            Some(b"{\"name\":\"left-pad\"}\n".to_vec())
+        );
+        let err = cache
+            .fetch(&client, &missing_url)
+            .expect_err("missing entry fails offline");
+        assert!(format!("{err:#}").contains("offline mode: no cached registry metadata"));
+    }
+
+    #[test]
+    fn entries_are_ignored_when_they_belong_to_another_url() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("entry");
+        let url = Url::parse("https://registry.example/a").expect("url");
+        write_entry(
+            &path,
+            &CachedValidators {
+                url: String::from("https://registry.example/b"),
+                etag: None,
+                last_modified: Some(String::from("Tue, 01 Sep 2026 00:00:00 GMT")),
+            },
+            b"body",
+        )
+        .expect("write entry");
+
+        assert!(read_entry(&path, &url).is_none());
+    }
+}
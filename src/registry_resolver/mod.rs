@@ -0,0 +1,1367 @@
+mod go;
+mod hex;
+mod maven;
+mod metadata_cache;
+mod nuget;
+mod packagist;
+mod pub_dev;
+mod registry_config;
+mod rubygems;
+mod version_range;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{LazyLock, OnceLock};
+
+use anyhow::Context;
+use flate2::read::GzDecoder;
+use reqwest::Url;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::archive::ArchiveFormat;
+use crate::config::Config;
+use crate::depspec::{DepSpec, Ecosystem, SourceKind, VersionRequest};
+use crate::source::{ArchivePullTarget, GitPullTarget, PullTarget, cache_root_for};
+
+use metadata_cache::MetadataCache;
+
+const DEFAULT_NPM_REGISTRY_BASE: &str = "https://registry.npmjs.org";
+const DEFAULT_PYPI_REGISTRY_BASE: &str = "https://pypi.org/pypi";
+const DEFAULT_CRATES_REGISTRY_BASE: &str = "https://crates.io/api/v1/crates";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestedRevisionSource {
+    ExactMetadata,
+    VersionDerived,
+    /// No git repository was usable; the target is the published artifact itself.
+    PublishedArchive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtifactKind {
+    NpmTarball,
+    PypiSdist,
+    PypiWheel,
+    Crate,
+    GoModuleZip,
+    Gem,
+    MavenSourcesJar,
+    ComposerDist,
+    Nupkg,
+    HexTarball,
+    PubArchive,
+}
+
+#[derive(Clone, Debug)]
+pub struct PublishedArtifact {
+    pub url: Url,
+    pub kind: ArtifactKind,
+    pub format: ArchiveFormat,
+    /// Leading path components wrapping the package files: none for wheels, which
+    /// unpack straight into site-packages, and one directory for most other artifacts.
+    pub strip_components: usize,
+}
+
+pub struct RegistryResolution {
+    pub target: PullTarget,
+    pub package_version: String,
+    pub requested_revision_source: RequestedRevisionSource,
+}
+
+pub fn resolve_registry_spec(
+    cwd: &Path,
+    config: &Config,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    match spec.source_kind {
+        SourceKind::Git { .. } => {
+            anyhow::bail!("resolve_registry_spec called with git source spec");
+        }
+        SourceKind::Registry => {}
+    }
+
+    let client = &RegistryClient::new(cwd, config)?;
+    match spec.ecosystem {
+        Ecosystem::Npm => resolve_npm(client, spec),
+        Ecosystem::Pypi => resolve_pypi(client, spec),
+        Ecosystem::Crates => resolve_crates(client, spec),
+        Ecosystem::Go => go::resolve_go(client, spec),
+        Ecosystem::Rubygems => rubygems::resolve_rubygems(client, spec),
+        Ecosystem::Maven => maven::resolve_maven(client, spec),
+        Ecosystem::Packagist => packagist::resolve_packagist(client, spec),
+        Ecosystem::Nuget => nuget::resolve_nuget(client, spec),
+        Ecosystem::Hex => hex::resolve_hex(client, spec),
+        Ecosystem::Pub => pub_dev::resolve_pub(client, spec),
+        Ecosystem::Swift => anyhow::bail!(
+            "swift package '{}' has no registry metadata; swift specs resolve from the Package.resolved pins of the project",
+            spec.locator
+        ),
+        other => anyhow::bail!(
+            "unsupported registry ecosystem '{}' for package-based pull; supported: {}",
+            other.as_str(),
+            crate::depspec::REGISTRY_SCHEMES.join(", ")
+        ),
+    }
+}
+
+fn resolve_npm(client: &RegistryClient, spec: DepSpec) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let metadata = fetch_npm_metadata(client, &package_name)?;
+
+    let dist_tag = |tag: &str| {
+        metadata
+            .dist_tags
+            .as_ref()
+            .and_then(|dist_tags| dist_tags.get(tag).cloned())
+            .ok_or_else(|| {
+                anyhow::anyhow!("npm package '{}' has no {} dist-tag", package_name, tag)
+            })
+    };
+    let selected_version = match spec.version_request() {
+        Some(VersionRequest::Exact(version)) => version,
+        Some(VersionRequest::DistTag(tag)) => dist_tag(&tag)?,
+        Some(VersionRequest::Range(range)) => {
+            let published = metadata
+                .versions
+                .iter()
+                .flat_map(|versions| versions.keys());
+            version_range::highest_npm_match(&range, published.map(String::as_str))?.ok_or_else(
+                || {
+                    anyhow::anyhow!(
+                        "npm package '{}' has no published version matching '{}'",
+                        package_name,
+                        range
+                    )
+                },
+            )?
+        }
+        None => dist_tag("latest")?,
+    };
+
+    let version_entry = metadata
+        .versions
+        .and_then(|versions| versions.get(&selected_version).cloned())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "npm package '{}' does not contain requested version '{}'",
+                package_name,
+                selected_version
+            )
+        })?;
+
+    let Some(repository_url) = repository_url_from_field(version_entry.repository.as_ref())
+        .or_else(|| repository_url_from_field(metadata.repository.as_ref()))
+    else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Npm,
+            package_name,
+            selected_version,
+            "metadata does not provide a repository URL",
+        ));
+    };
+    let Some(git_url) = normalize_git_repository_url(&repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Npm,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    let subdirectory = repository_directory_from_field(version_entry.repository.as_ref())
+        .or_else(|| repository_directory_from_field(metadata.repository.as_ref()));
+
+    let requested_revision_source = npm_requested_revision_source(&version_entry);
+    let requested_revision = version_entry
+        .git_head
+        .clone()
+        .or_else(|| {
+            version_entry
+                .dist
+                .as_ref()
+                .and_then(|dist| dist.git_head.clone())
+        })
+        .unwrap_or_else(|| selected_version.clone());
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Npm,
+            locator: package_name,
+            git_url: git_url.clone(),
+            requested_revision,
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+fn resolve_pypi(client: &RegistryClient, spec: DepSpec) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let endpoint = pypi_endpoint(&package_name)?;
+
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch pypi metadata from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "pypi metadata request failed for package '{}': not found",
+                package_name
+            )
+        })?;
+    let metadata: PypiPackageResponse = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse pypi metadata JSON for '{}'", package_name))?;
+
+    let selected_version = match spec.version_request() {
+        Some(VersionRequest::Range(specifiers)) => {
+            let published = metadata
+                .releases
+                .iter()
+                .filter(|(_, files)| files.iter().any(|file| !file.yanked))
+                .map(|(version, _)| version.as_str());
+            version_range::highest_pep440_match(&specifiers, published)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "pypi package '{}' has no published version matching '{}'",
+                    package_name,
+                    specifiers
+                )
+            })?
+        }
+        Some(VersionRequest::Exact(version) | VersionRequest::DistTag(version)) => version,
+        None => metadata.info.version.clone(),
+    };
+
+    let Some(repository_url) = pypi_repository_url(&metadata.info) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Pypi,
+            package_name,
+            selected_version,
+            "metadata does not provide a repository/source URL",
+        ));
+    };
+    let Some(git_url) = normalize_git_repository_url(&repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Pypi,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Pypi,
+            locator: package_name,
+            git_url: git_url.clone(),
+            requested_revision: selected_version.clone(),
+            subdirectory: None,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source: RequestedRevisionSource::VersionDerived,
+    })
+}
+
+fn resolve_crates(client: &RegistryClient, spec: DepSpec) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let endpoint = crates_endpoint(client, &package_name)?;
+
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch crates metadata from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "crates metadata request failed for package '{}': not found",
+                package_name
+            )
+        })?;
+    let metadata: CratesPackageResponse = serde_json::from_slice(&bytes).with_context(|| {
+        format!(
+            "failed to parse crates metadata JSON for '{}'",
+            package_name
+        )
+    })?;
+
+    let selected_version = match spec.version_request() {
+        Some(VersionRequest::Range(range)) => {
+            let published = metadata
+                .versions
+                .iter()
+                .filter(|version| !version.yanked)
+                .map(|version| version.num.as_str());
+            version_range::highest_cargo_match(&range, published)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "crates package '{}' has no published version matching '{}'",
+                    package_name,
+                    range
+                )
+            })?
+        }
+        Some(VersionRequest::Exact(version) | VersionRequest::DistTag(version)) => version,
+        None => metadata
+            .krate
+            .max_stable_version
+            .clone()
+            .filter(|version| !version.is_empty())
+            .or_else(|| {
+                metadata
+                    .krate
+                    .newest_version
+                    .clone()
+                    .filter(|version| !version.is_empty())
+            })
+            .unwrap_or_else(|| metadata.krate.max_version.clone()),
+    };
+
+    if !metadata
+        .versions
+        .iter()
+        .any(|version| version.num == selected_version)
+    {
+        anyhow::bail!(
+            "crates package '{}' does not contain requested version '{}'",
+            package_name,
+            selected_version
+        );
+    }
+
+    let Some(repository_url) = metadata
+        .krate
+        .repository
+        .clone()
+        .or_else(|| metadata.krate.homepage.clone())
+    else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Crates,
+            package_name,
+            selected_version,
+            "metadata does not provide a repository/source URL",
+        ));
+    };
+    let (repository_url, url_subdirectory) = split_web_tree_url(&repository_url);
+    let Some(git_url) = normalize_git_repository_url(&repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Crates,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    let vcs_info = match fetch_cargo_vcs_info(client, &package_name, &selected_version) {
+        Ok(vcs_info) => vcs_info,
+        Err(err) => {
+            warn!(
+                package = %package_name,
+                version = %selected_version,
+                error = %format!("{err:#}"),
+                "failed to read .cargo_vcs_info.json from published crate"
+            );
+            None
+        }
+    };
+    if vcs_info
+        .as_ref()
+        .and_then(|vcs_info| vcs_info.git.as_ref())
+        .is_some_and(|git| git.dirty)
+    {
+        warn!(
+            package = %package_name,
+            version = %selected_version,
+            "crate was published from a dirty working tree; its recorded commit does not match the published sources, so the version-derived revision is used instead"
+        );
+    }
+    let (requested_revision, requested_revision_source, subdirectory) =
+        crate_git_revision(&selected_version, vcs_info, url_subdirectory);
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Crates,
+            locator: package_name,
+            git_url: git_url.clone(),
+            requested_revision,
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+// `path_in_vcs` is what `cargo publish` saw, so it wins over a directory named in
+// the repository URL, which only fills in for crates published without it.
+fn crate_git_revision(
+    selected_version: &str,
+    vcs_info: Option<CargoVcsInfo>,
+    url_subdirectory: Option<String>,
+) -> (String, RequestedRevisionSource, Option<String>) {
+    let (published_commit, path_in_vcs) = match vcs_info {
+        Some(vcs_info) => (
+            vcs_info.clean_commit(),
+            vcs_info
+                .path_in_vcs
+                .and_then(|path_in_vcs| normalize_subdirectory(&path_in_vcs)),
+        ),
+        None => (None, None),
+    };
+    let subdirectory = path_in_vcs.or(url_subdirectory);
+    match published_commit {
+        Some(sha1) => (sha1, RequestedRevisionSource::ExactMetadata, subdirectory),
+        None => (
+            selected_version.to_string(),
+            RequestedRevisionSource::VersionDerived,
+            subdirectory,
+        ),
+    }
+}
+
+// Without a usable git repository the published artifact is the only source left.
+fn published_archive_resolution(
+    ecosystem: Ecosystem,
+    package_name: String,
+    package_version: String,
+    reason: &str,
+) -> RegistryResolution {
+    warn!(
+        ecosystem = ecosystem.as_str(),
+        package = %package_name,
+        version = %package_version,
+        reason = reason,
+        "no git source for package; using the published archive"
+    );
+    RegistryResolution {
+        target: PullTarget::Archive(ArchivePullTarget {
+            ecosystem,
+            package_name,
+            package_version: package_version.clone(),
+        }),
+        package_version,
+        requested_revision_source: RequestedRevisionSource::PublishedArchive,
+    }
+}
+
+/// Finds the artifact the registry serves for `package_name@version`: the npm
+/// `dist.tarball`, the PyPI sdist (or a wheel when no sdist exists), the `.crate`,
+/// the Go module zip, the `.gem`, the Maven `-sources.jar`, the Composer dist zip, the
+/// `.nupkg`, the Hex tarball, or the pub package archive.
+pub fn locate_published_artifact(
+    cwd: &Path,
+    config: &Config,
+    ecosystem: &Ecosystem,
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    let client = &RegistryClient::new(cwd, config)?;
+
+    match ecosystem {
+        Ecosystem::Npm => {
+            let metadata = fetch_npm_metadata(client, package_name)?;
+            let tarball = metadata
+                .versions
+                .and_then(|mut versions| versions.remove(version))
+                .and_then(|version_entry| version_entry.dist)
+                .and_then(|dist| dist.tarball)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "npm package '{}' does not publish a tarball for version '{}'",
+                        package_name,
+                        version
+                    )
+                })?;
+            let url = Url::parse(&tarball)
+                .with_context(|| format!("invalid npm tarball URL: {}", tarball))?;
+            Ok(PublishedArtifact {
+                url,
+                kind: ArtifactKind::NpmTarball,
+                format: ArchiveFormat::TarGz,
+                strip_components: 1,
+            })
+        }
+        Ecosystem::Pypi => {
+            let endpoint = pypi_release_endpoint(package_name, version)?;
+            let bytes = fetch_url_bytes(client, &endpoint)
+                .with_context(|| {
+                    format!("failed to fetch pypi release metadata from {}", endpoint)
+                })?
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "pypi release metadata request failed for '{}@{}': not found",
+                        package_name,
+                        version
+                    )
+                })?;
+            let release: PypiReleaseResponse =
+                serde_json::from_slice(&bytes).with_context(|| {
+                    format!(
+                        "failed to parse pypi release metadata JSON for '{}@{}'",
+                        package_name, version
+                    )
+                })?;
+            pypi_published_artifact(&release.urls).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "pypi package '{}' does not publish an sdist or wheel for version '{}'",
+                    package_name,
+                    version
+                )
+            })
+        }
+        Ecosystem::Crates => Ok(PublishedArtifact {
+            url: crates_download_endpoint(client, package_name, version)?,
+            kind: ArtifactKind::Crate,
+            format: ArchiveFormat::TarGz,
+            strip_components: 1,
+        }),
+        Ecosystem::Go => go::module_zip_artifact(package_name, version),
+        Ecosystem::Rubygems => rubygems::gem_artifact(package_name, version),
+        Ecosystem::Maven => maven::sources_jar_artifact(package_name, version),
+        Ecosystem::Packagist => packagist::dist_artifact(client, package_name, version),
+        Ecosystem::Nuget => nuget::nupkg_artifact(package_name, version),
+        Ecosystem::Hex => hex::tarball_artifact(package_name, version),
+        Ecosystem::Pub => pub_dev::archive_artifact(client, package_name, version),
+        Ecosystem::Swift => anyhow::bail!(
+            "swift package '{}' is pinned to source control and has no published artifact",
+            package_name
+        ),
+        other => anyhow::bail!(
+            "unsupported registry ecosystem '{}' for published artifacts; supported: {}",
+            other.as_str(),
+            crate::depspec::REGISTRY_SCHEMES.join(", ")
+        ),
+    }
+}
+
+pub fn download_published_artifact(
+    cwd: &Path,
+    config: &Config,
+    artifact: &PublishedArtifact,
+) -> anyhow::Result<Vec<u8>> {
+    let client = &RegistryClient::new(cwd, config)?;
+    fetch_archive_bytes(client, &artifact.url)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "published artifact download failed for {}: not found",
+            artifact.url
+        )
+    })
+}
+
+/// Fetches `url` over HTTP through the registry metadata cache or, for `file://`
+/// URLs, from the local filesystem, so a registry mirrored into a directory can
+/// stand in for the remote one.
+///
+/// Returns `None` when the resource does not exist (HTTP 404/410 or a missing file).
+fn fetch_url_bytes(client: &RegistryClient, url: &Url) -> anyhow::Result<Option<Vec<u8>>> {
+    if let Some(bytes) = read_file_url(url)? {
+        return Ok(bytes);
+    }
+
+    client.metadata_cache.fetch(&client.http, url)
+}
+
+/// Fetches a package archive around the metadata cache: archives are large and,
+/// once unpacked, live in the source cache, so keeping them would only grow
+/// `registry-metadata/` without bound. Offline, only `file://` URLs are readable.
+fn fetch_archive_bytes(client: &RegistryClient, url: &Url) -> anyhow::Result<Option<Vec<u8>>> {
+    if let Some(bytes) = read_file_url(url)? {
+        return Ok(bytes);
+    }
+    if client.metadata_cache.is_offline() {
+        anyhow::bail!("offline mode: cannot download published artifact {}", url);
+    }
+    fetch_remote_bytes(&client.http, url)
+}
+
+/// The contents of a `file://` URL (`Some(None)` when the file is missing), or
+/// `None` for any other scheme.
+fn read_file_url(url: &Url) -> anyhow::Result<Option<Option<Vec<u8>>>> {
+    if url.scheme() != "file" {
+        return Ok(None);
+    }
+    let path = url
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!("invalid file URL: {}", url))?;
+    match fs::read(&path) {
+        Ok(bytes) => Ok(Some(Some(bytes))),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Some(None)),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+fn fetch_remote_bytes(client: &Client, url: &Url) -> anyhow::Result<Option<Vec<u8>>> {
+    let response = registry_get(client, url)
+        .send()
+        .with_context(|| format!("failed to fetch {}", url))?;
+    if matches!(
+        response.status(),
+        reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+    ) {
+        return Ok(None);
+    }
+    let response = response
+        .error_for_status()
+        .with_context(|| format!("request failed for {}", url))?;
+    let bytes = response
+        .bytes()
+        .with_context(|| format!("failed to read response body from {}", url))?;
+    Ok(Some(bytes.to_vec()))
+}
+
+fn fetch_npm_metadata(
+    client: &RegistryClient,
+    package_name: &str,
+) -> anyhow::Result<NpmRegistryPackage> {
+    let endpoint = npm_endpoint(package_name)?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch npm metadata from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "npm metadata request failed for package '{}': not found",
+                package_name
+            )
+        })?;
+    serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse npm metadata JSON for '{}'", package_name))
+}
+
+fn pypi_published_artifact(files: &[PypiReleaseFile]) -> Option<PublishedArtifact> {
+    let sdist = files.iter().find_map(|file| {
+        if file.packagetype != "sdist" {
+            return None;
+        }
+        let format = if file.filename.ends_with(".tar.gz") {
+            ArchiveFormat::TarGz
+        } else if file.filename.ends_with(".zip") {
+            ArchiveFormat::Zip
+        } else {
+            return None;
+        };
+        Some((file, ArtifactKind::PypiSdist, format, 1))
+    });
+    let wheel = || {
+        files
+            .iter()
+            .find(|file| file.packagetype == "bdist_wheel")
+            .map(|file| (file, ArtifactKind::PypiWheel, ArchiveFormat::Zip, 0))
+    };
+
+    let (file, kind, format, strip_components) = sdist.or_else(wheel)?;
+    let url = Url::parse(&file.url).ok()?;
+    Some(PublishedArtifact {
+        url,
+        kind,
+        format,
+        strip_components,
+    })
+}
+
+static HTTP_CLIENT: LazyLock<reqwest::Result<Client>> =
+    LazyLock::new(|| Client::builder().user_agent("pkgrep").build());
+
+/// Registry access for one command: the HTTP client shared by every lookup in this
+/// process, and the metadata cache (and offline mode) taken from the `Config`.
+pub(super) struct RegistryClient {
+    http: Client,
+    metadata_cache: MetadataCache,
+}
+
+impl RegistryClient {
+    fn new(cwd: &Path, config: &Config) -> anyhow::Result<Self> {
+        Self::with_cache(MetadataCache::new(
+            &cache_root_for(cwd, &config.cache_dir),
+            config.offline,
+        ))
+    }
+
+    fn with_cache(metadata_cache: MetadataCache) -> anyhow::Result<Self> {
+        let http = HTTP_CLIENT
+            .as_ref()
+            .cloned()
+            .map_err(|err| anyhow::anyhow!("failed to initialize HTTP client: {err}"))?;
+        Ok(Self {
+            http,
+            metadata_cache,
+        })
+    }
+}
+
+/// A GET request carrying the credentials configured for `url` in `.npmrc`, pip's
+/// index URL, or the Cargo credentials file.
+pub(super) fn registry_get(client: &Client, url: &Url) -> RequestBuilder {
+    registry_config::settings().authorize(client.get(url.clone()), url)
+}
+
+// A scope registry from `.npmrc` is more specific than the pkgrep override, which in
+// turn beats the `.npmrc` default registry.
+fn npm_endpoint(package_name: &str) -> anyhow::Result<Url> {
+    let settings = registry_config::settings();
+    let base = settings
+        .npm_registry_for(package_name)
+        .map(str::to_string)
+        .or_else(|| std::env::var("PKGREP_NPM_REGISTRY_URL").ok())
+        .or_else(|| settings.npm_default_registry().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_NPM_REGISTRY_BASE.to_string());
+    let mut url =
+        Url::parse(&base).with_context(|| format!("invalid npm registry URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid npm registry URL path: {}", base))?
+        .pop_if_empty()
+        .push(package_name);
+    Ok(url)
+}
+
+fn pypi_endpoint(package_name: &str) -> anyhow::Result<Url> {
+    let base = std::env::var("PKGREP_PYPI_REGISTRY_URL")
+        .ok()
+        .or_else(|| registry_config::settings().pypi_json_base())
+        .unwrap_or_else(|| DEFAULT_PYPI_REGISTRY_BASE.to_string());
+    let mut url =
+        Url::parse(&base).with_context(|| format!("invalid pypi registry URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid pypi registry URL path: {}", base))?
+        .pop_if_empty()
+        .push(package_name)
+        .push("json");
+    Ok(url)
+}
+
+fn pypi_release_endpoint(package_name: &str, version: &str) -> anyhow::Result<Url> {
+    let mut url = pypi_endpoint(package_name)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid pypi registry URL for '{}'", package_name))?
+        .pop()
+        .push(version)
+        .push("json");
+    Ok(url)
+}
+
+fn crates_endpoint(client: &RegistryClient, package_name: &str) -> anyhow::Result<Url> {
+    let base = match std::env::var("PKGREP_CRATES_REGISTRY_URL") {
+        Ok(base) => base,
+        Err(_) => match registry_config::settings().cargo_registry() {
+            Some(registry) => cargo_registry_api_base(client, registry)?,
+            None => DEFAULT_CRATES_REGISTRY_BASE.to_string(),
+        },
+    };
+    let mut url =
+        Url::parse(&base).with_context(|| format!("invalid crates registry URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid crates registry URL path: {}", base))?
+        .pop_if_empty()
+        .push(package_name);
+    Ok(url)
+}
+
+fn crates_download_endpoint(
+    client: &RegistryClient,
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<Url> {
+    let mut url = crates_endpoint(client, package_name)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid crates registry URL for '{}'", package_name))?
+        .push(version)
+        .push("download");
+    Ok(url)
+}
+
+static CARGO_REGISTRY_API_BASE: OnceLock<String> = OnceLock::new();
+
+/// Web API base of a private Cargo registry, read from the `api` field of its
+/// sparse index `config.json`.
+fn cargo_registry_api_base(
+    client: &RegistryClient,
+    registry: &registry_config::CargoRegistry,
+) -> anyhow::Result<String> {
+    if let Some(base) = CARGO_REGISTRY_API_BASE.get() {
+        return Ok(base.clone());
+    }
+
+    if !registry.index.starts_with("http://") && !registry.index.starts_with("https://") {
+        anyhow::bail!(
+            "cargo registry '{}' does not use a sparse index ({}); set PKGREP_CRATES_REGISTRY_URL to its web API",
+            registry.name,
+            registry.index
+        );
+    }
+    let index = Url::parse(&format!("{}/", registry.index.trim_end_matches('/')))
+        .with_context(|| format!("invalid cargo registry index URL: {}", registry.index))?;
+    let config_url = index
+        .join("config.json")
+        .with_context(|| format!("invalid cargo registry index URL: {}", registry.index))?;
+    let bytes = fetch_url_bytes(client, &config_url)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "cargo registry '{}' has no index config at {}",
+            registry.name,
+            config_url
+        )
+    })?;
+    let config: CargoIndexConfig = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse cargo index config from {}", config_url))?;
+    let api = config.api.ok_or_else(|| {
+        anyhow::anyhow!(
+            "cargo registry '{}' does not advertise a web API in {}",
+            registry.name,
+            config_url
+        )
+    })?;
+    let base = format!("{}/api/v1/crates", api.trim_end_matches('/'));
+    Ok(CARGO_REGISTRY_API_BASE.get_or_init(|| base).clone())
+}
+
+// crates.io metadata does not expose the commit a crate was published from or where
+// it lives inside its repository; `cargo publish` records both in
+// `.cargo_vcs_info.json` inside the `.crate` archive.
+fn fetch_cargo_vcs_info(
+    client: &RegistryClient,
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<Option<CargoVcsInfo>> {
+    let endpoint = crates_download_endpoint(client, package_name, version)?;
+    let bytes = fetch_archive_bytes(client, &endpoint)
+        .with_context(|| format!("failed to download crate archive from {}", endpoint))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "crate archive download failed for '{}@{}': not found",
+                package_name,
+                version
+            )
+        })?;
+
+    read_cargo_vcs_info(&bytes)
+}
+
+fn read_cargo_vcs_info(crate_archive: &[u8]) -> anyhow::Result<Option<CargoVcsInfo>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(crate_archive));
+    for entry in archive
+        .entries()
+        .context("failed to read crate archive entries")?
+    {
+        let mut entry = entry.context("failed to read crate archive entry")?;
+        let path = entry
+            .path()
+            .context("crate archive entry has an invalid path")?
+            .into_owned();
+        let mut components = path.components();
+        let is_vcs_info = components.next().is_some()
+            && components.next().map(|component| component.as_os_str())
+                == Some(".cargo_vcs_info.json".as_ref())
+            && components.next().is_none();
+        if !is_vcs_info {
+            continue;
+        }
+
+        let mut raw = String::new();
+        entry
+            .read_to_string(&mut raw)
+            .context("failed to read .cargo_vcs_info.json from crate archive")?;
+        let vcs_info =
+            serde_json::from_str(&raw).context("failed to parse .cargo_vcs_info.json")?;
+        return Ok(Some(vcs_info));
+    }
+
+    Ok(None)
+}
+
+fn repository_url_from_field(field: Option<&RepositoryField>) -> Option<String> {
+    match field? {
+        RepositoryField::String(raw) => Some(raw.clone()),
+        RepositoryField::Object { url, .. } => url.clone(),
+    }
+}
+
+fn repository_directory_from_field(field: Option<&RepositoryField>) -> Option<String> {
+    match field? {
+        RepositoryField::String(_) => None,
+        RepositoryField::Object { directory, .. } => {
+            directory.as_deref().and_then(normalize_subdirectory)
+        }
+    }
+}
+
+/// Normalizes a repository-relative package directory, rejecting paths that
+/// would escape the checkout.
+pub fn normalize_subdirectory(raw: &str) -> Option<String> {
+    let mut components = Vec::new();
+    for component in raw.trim().split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => return None,
+            component => components.push(component),
+        }
+    }
+
+    if components.is_empty() {
+        None
+    } else {
+        Some(components.join("/"))
+    }
+}
+
+fn pypi_repository_url(info: &PypiInfo) -> Option<String> {
+    let project_urls = info.project_urls.as_ref()?;
+    let preferred_keys = ["Source", "Source Code", "Repository", "Code", "Homepage"];
+    for key in preferred_keys {
+        if let Some(url) = project_urls.get(key) {
+            return Some(url.clone());
+        }
+    }
+    project_urls
+        .values()
+        .next()
+        .cloned()
+        .or_else(|| info.home_page.clone())
+}
+
+fn normalize_git_repository_url(raw: &str) -> Option<String> {
+    let mut url = raw.trim().to_string();
+
+    if let Some(stripped) = url.strip_prefix("git+") {
+        url = stripped.to_string();
+    }
+    if let Some(stripped) = url.strip_prefix("github:") {
+        url = format!("https://github.com/{stripped}");
+    }
+    if let Some(stripped) = url.strip_prefix("git@github.com:") {
+        url = format!("https://github.com/{stripped}");
+    }
+    if let Some((prefix, _)) = url.split_once('#') {
+        url = prefix.to_string();
+    }
+    if let Some(stripped) = url.strip_prefix("git://") {
+        url = format!("https://{stripped}");
+    }
+
+    let supported_scheme = ["https://", "http://", "ssh://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme));
+    if !supported_scheme {
+        return None;
+    }
+
+    if !url.ends_with(".git") {
+        url.push_str(".git");
+    }
+    Some(url)
+}
+
+// Proxies and registries mirrored into a directory can record filesystem
+// repositories; keep those as-is.
+fn git_url_or_local_path(raw: &str) -> Option<String> {
+    if raw.starts_with("file://") || raw.starts_with('/') {
+        return Some(raw.to_string());
+    }
+    normalize_git_repository_url(raw)
+}
+
+/// Splits a forge web URL such as `https://github.com/rails/rails/tree/v7.1.3/activerecord`
+/// into the repository and the directory the package lives in. The ref is assumed to be a
+/// single path segment; the version-derived revision is what gets checked out.
+fn split_web_tree_url(raw: &str) -> (String, Option<String>) {
+    let raw = raw.trim().trim_end_matches('/');
+    if raw.starts_with("https://") || raw.starts_with("http://") {
+        for marker in ["/-/tree/", "/tree/", "/blob/"] {
+            if let Some((repository, rest)) = raw.split_once(marker) {
+                let subdirectory = rest
+                    .split_once('/')
+                    .and_then(|(_, path)| normalize_subdirectory(path));
+                return (repository.to_string(), subdirectory);
+            }
+        }
+    }
+    (raw.to_string(), None)
+}
+
+fn npm_requested_revision_source(version_entry: &NpmVersionEntry) -> RequestedRevisionSource {
+    if version_entry.git_head.is_some()
+        || version_entry
+            .dist
+            .as_ref()
+            .and_then(|dist| dist.git_head.as_ref())
+            .is_some()
+    {
+        RequestedRevisionSource::ExactMetadata
+    } else {
+        RequestedRevisionSource::VersionDerived
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmRegistryPackage {
+    #[serde(default, rename = "dist-tags")]
+    dist_tags: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    versions: Option<BTreeMap<String, NpmVersionEntry>>,
+    #[serde(default)]
+    repository: Option<RepositoryField>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct NpmVersionEntry {
+    #[serde(default)]
+    repository: Option<RepositoryField>,
+    #[serde(default, rename = "gitHead")]
+    git_head: Option<String>,
+    #[serde(default)]
+    dist: Option<NpmDistEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct NpmDistEntry {
+    #[serde(default, rename = "gitHead")]
+    git_head: Option<String>,
+    #[serde(default)]
+    tarball: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum RepositoryField {
+    String(String),
+    Object {
+        url: Option<String>,
+        #[serde(default)]
+        directory: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiPackageResponse {
+    info: PypiInfo,
+    #[serde(default)]
+    releases: BTreeMap<String, Vec<PypiReleaseFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiInfo {
+    version: String,
+    #[serde(default)]
+    project_urls: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    home_page: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiReleaseResponse {
+    #[serde(default)]
+    urls: Vec<PypiReleaseFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiReleaseFile {
+    filename: String,
+    packagetype: String,
+    url: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesPackageResponse {
+    #[serde(rename = "crate")]
+    krate: CratesCrate,
+    #[serde(default)]
+    versions: Vec<CratesVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesCrate {
+    max_version: String,
+    #[serde(default)]
+    newest_version: Option<String>,
+    #[serde(default)]
+    max_stable_version: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesVersion {
+    num: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoIndexConfig {
+    #[serde(default)]
+    api: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoVcsInfo {
+    #[serde(default)]
+    git: Option<CargoVcsGit>,
+    #[serde(default)]
+    path_in_vcs: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoVcsGit {
+    sha1: String,
+    /// Set by `cargo publish --allow-dirty` when uncommitted changes were packaged.
+    #[serde(default)]
+    dirty: bool,
+}
+
+impl CargoVcsInfo {
+    // A dirty publish packaged changes that `sha1` does not contain, so it is no pin.
+    fn clean_commit(&self) -> Option<String> {
+        self.git
+            .as_ref()
+            .filter(|git| !git.dirty)
+            .map(|git| git.sha1.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_clients_take_offline_mode_from_their_own_config() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let mut config = Config {
+            cache_dir: temp.path().join("cache"),
+            worker_pool_size: 1,
+            lock_timeout_secs: 5,
+            checkout_strategy: crate::config::CheckoutStrategy::Full,
+            tag_formats: BTreeMap::new(),
+            git_ssh_keys: Vec::new(),
+            git_url_rewrites: BTreeMap::new(),
+            offline: true,
+        };
+        let url = Url::parse("https://registry.example/left-pad").expect("url");
+
+        let offline = RegistryClient::new(temp.path(), &config).expect("offline client");
+        let err = fetch_url_bytes(&offline, &url).expect_err("uncached lookup fails offline");
+        assert!(format!("{err:#}").contains("offline mode: no cached registry metadata"));
+
+        config.offline = false;
+        let online = RegistryClient::new(temp.path(), &config).expect("online client");
+        assert!(!online.metadata_cache.is_offline());
+        assert!(offline.metadata_cache.is_offline());
+    }
+
+    #[test]
+    fn normalizes_github_shorthand_url() {
+        let raw = "github:colinhacks/zod";
+        let url = normalize_git_repository_url(raw).expect("normalized");
+        assert_eq!(url, "https://github.com/colinhacks/zod.git");
+    }
+
+    #[test]
+    fn normalizes_git_plus_url_and_fragment() {
+        let raw = "git+https://github.com/axios/axios.git#v1.7.0";
+        let url = normalize_git_repository_url(raw).expect("normalized");
+        assert_eq!(url, "https://github.com/axios/axios.git");
+    }
+
+    #[test]
+    fn prefers_pypi_source_project_url() {
+        let mut project_urls = BTreeMap::new();
+        project_urls.insert("Homepage".to_string(), "https://example.com".to_string());
+        project_urls.insert(
+            "Source".to_string(),
+            "https://github.com/psf/requests".to_string(),
+        );
+        let info = PypiInfo {
+            version: "2.32.3".to_string(),
+            project_urls: Some(project_urls),
+            home_page: Some("https://requests.readthedocs.io".to_string()),
+        };
+
+        let url = pypi_repository_url(&info).expect("source url");
+        assert_eq!(url, "https://github.com/psf/requests");
+    }
+
+    #[test]
+    fn npm_revision_source_is_exact_when_git_head_present() {
+        let entry = NpmVersionEntry {
+            repository: None,
+            git_head: Some(String::from("deadbeef")),
+            dist: None,
+        };
+
+        assert_eq!(
+            npm_requested_revision_source(&entry),
+            RequestedRevisionSource::ExactMetadata
+        );
+    }
+
+    #[test]
+    fn npm_revision_source_is_version_derived_without_git_head() {
+        let entry = NpmVersionEntry {
+            repository: None,
+            git_head: None,
+            dist: None,
+        };
+
+        assert_eq!(
+            npm_requested_revision_source(&entry),
+            RequestedRevisionSource::VersionDerived
+        );
+    }
+
+    #[test]
+    fn reads_npm_repository_directory() {
+        let field: RepositoryField = serde_json::from_str(
+            r#"{"type":"git","url":"https://github.com/babel/babel.git","directory":"packages/babel-core"}"#,
+        )
+        .expect("repository field");
+
+        assert_eq!(
+            repository_url_from_field(Some(&field)).as_deref(),
+            Some("https://github.com/babel/babel.git")
+        );
+        assert_eq!(
+            repository_directory_from_field(Some(&field)).as_deref(),
+            Some("packages/babel-core")
+        );
+    }
+
+    #[test]
+    fn normalizes_subdirectories_and_rejects_escapes() {
+        assert_eq!(
+            normalize_subdirectory("./packages//core/").as_deref(),
+            Some("packages/core")
+        );
+        assert_eq!(
+            normalize_subdirectory("crates\\cli").as_deref(),
+            Some("crates/cli")
+        );
+        assert_eq!(normalize_subdirectory("."), None);
+        assert_eq!(normalize_subdirectory(""), None);
+        assert_eq!(normalize_subdirectory("../outside"), None);
+        assert_eq!(normalize_subdirectory("packages/../../outside"), None);
+    }
+
+    fn crate_archive_with_vcs_info(vcs_info: &[u8]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, contents) in [
+            ("tokio-util-0.7.0/Cargo.toml", &b"[package]\n"[..]),
+            ("tokio-util-0.7.0/.cargo_vcs_info.json", vcs_info),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents)
+                .expect("append entry");
+        }
+        builder
+            .into_inner()
+            .expect("finish tar")
+            .finish()
+            .expect("finish gzip")
+    }
+
+    #[test]
+    fn reads_commit_and_path_in_vcs_from_crate_archive() {
+        let archive = crate_archive_with_vcs_info(
+            br#"{"git":{"sha1":"0123456789abcdef0123456789abcdef01234567"},"path_in_vcs":"tokio-util"}"#,
+        );
+
+        let vcs_info = read_cargo_vcs_info(&archive)
+            .expect("read archive")
+            .expect("vcs info present");
+        assert_eq!(vcs_info.path_in_vcs.as_deref(), Some("tokio-util"));
+        assert_eq!(
+            vcs_info.clean_commit().as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+    }
+
+    #[test]
+    fn dirty_crate_publish_does_not_pin_its_recorded_commit() {
+        let archive = crate_archive_with_vcs_info(
+            br#"{"git":{"sha1":"0123456789abcdef0123456789abcdef01234567","dirty":true},"path_in_vcs":"tokio-util"}"#,
+        );
+
+        let vcs_info = read_cargo_vcs_info(&archive)
+            .expect("read archive")
+            .expect("vcs info present");
+        assert!(vcs_info.git.as_ref().is_some_and(|git| git.dirty));
+        assert_eq!(vcs_info.clean_commit(), None);
+        assert_eq!(vcs_info.path_in_vcs.as_deref(), Some("tokio-util"));
+    }
+
+    #[test]
+    fn tree_url_crates_keep_the_commit_pin_from_their_crate_archive() {
+        let (repository_url, url_subdirectory) =
+            split_web_tree_url("https://github.com/tokio-rs/tokio/tree/master/tokio-util");
+        assert_eq!(repository_url, "https://github.com/tokio-rs/tokio");
+        let archive = crate_archive_with_vcs_info(
+            br#"{"git":{"sha1":"0123456789abcdef0123456789abcdef01234567"},"path_in_vcs":"tokio-util"}"#,
+        );
+        let vcs_info = read_cargo_vcs_info(&archive).expect("read archive");
+
+        assert_eq!(
+            crate_git_revision("0.7.0", vcs_info, url_subdirectory.clone()),
+            (
+                String::from("0123456789abcdef0123456789abcdef01234567"),
+                RequestedRevisionSource::ExactMetadata,
+                Some(String::from("tokio-util"))
+            )
+        );
+        assert_eq!(
+            crate_git_revision("0.7.0", None, url_subdirectory),
+            (
+                String::from("0.7.0"),
+                RequestedRevisionSource::VersionDerived,
+                Some(String::from("tokio-util"))
+            )
+        );
+    }
+
+    #[test]
+    fn pypi_artifact_prefers_sdist_over_wheels() {
+        let file = |filename: &str, packagetype: &str| PypiReleaseFile {
+            filename: filename.to_string(),
+            packagetype: packagetype.to_string(),
+            url: format!("https://files.example.test/{filename}"),
+            yanked: false,
+        };
+
+        let artifact = pypi_published_artifact(&[
+            file("demo-1.0-py3-none-any.whl", "bdist_wheel"),
+            file("demo-1.0.tar.gz", "sdist"),
+        ])
+        .expect("sdist");
+        assert_eq!(artifact.kind, ArtifactKind::PypiSdist);
+        assert_eq!(artifact.format, ArchiveFormat::TarGz);
+        assert_eq!(artifact.strip_components, 1);
+
+        let artifact = pypi_published_artifact(&[file("demo-1.0-py3-none-any.whl", "bdist_wheel")])
+            .expect("wheel");
+        assert_eq!(artifact.kind, ArtifactKind::PypiWheel);
+        assert_eq!(artifact.format, ArchiveFormat::Zip);
+        assert_eq!(artifact.strip_components, 0);
+    }
+
+    #[test]
+    fn splits_forge_tree_urls_into_repository_and_directory() {
+        assert_eq!(
+            split_web_tree_url("https://github.com/rails/rails/tree/v7.1.3/activerecord"),
+            (
+                String::from("https://github.com/rails/rails"),
+                Some(String::from("activerecord"))
+            )
+        );
+        assert_eq!(
+            split_web_tree_url("https://gitlab.com/group/gem/-/tree/main"),
+            (String::from("https://gitlab.com/group/gem"), None)
+        );
+        assert_eq!(
+            split_web_tree_url("https://github.com/rack/rack/"),
+            (String::from("https://github.com/rack/rack"), None)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::path::Path;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_archive_bytes, fetch_url_bytes, git_url_or_local_path, published_archive_resolution,
+};
+use crate::archive::{self, ArchiveFormat};
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_NUGET_FEED_BASE: &str = "https://api.nuget.org/v3-flatcontainer";
+
+/// `<id>/index.json` of the v3 flat container: every published version, oldest first.
+#[derive(Debug, Deserialize)]
+struct FlatContainerIndex {
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+/// The `<repository>` element of a `.nuspec`, written by SourceLink and
+/// `dotnet pack` from the repository the package was built from.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct NuspecRepository {
+    kind: Option<String>,
+    url: Option<String>,
+    commit: Option<String>,
+}
+
+pub(super) fn resolve_nuget(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let package_id = spec.locator.clone();
+
+    let selected_version = match spec.version {
+        Some(version) => version,
+        None => latest_version(client, &package_id)?,
+    };
+    let nupkg_url = nupkg_url(&package_id, &selected_version)?;
+    let nupkg = fetch_archive_bytes(client, &nupkg_url)
+        .with_context(|| format!("failed to fetch nuget package from {}", nupkg_url))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "nuget package '{}' does not contain requested version '{}'",
+                package_id,
+                selected_version
+            )
+        })?;
+    let repository = read_nuspec_repository(&nupkg)
+        .with_context(|| format!("failed to read the .nuspec from {}", nupkg_url))?;
+
+    let repository_url = repository
+        .url
+        .as_deref()
+        .filter(|_| repository.kind.as_deref().is_none_or(|kind| kind == "git"));
+    let Some(repository_url) = repository_url else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Nuget,
+            package_id,
+            selected_version,
+            ".nuspec does not provide a git <repository> URL",
+        ));
+    };
+    let Some(git_url) = git_url_or_local_path(repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Nuget,
+            package_id,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+    let (requested_revision, requested_revision_source) = match repository.commit {
+        Some(commit) => (commit, RequestedRevisionSource::ExactMetadata),
+        None => (
+            selected_version.clone(),
+            RequestedRevisionSource::VersionDerived,
+        ),
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Nuget,
+            locator: package_id,
+            git_url,
+            requested_revision,
+            subdirectory: None,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+pub(super) fn nupkg_artifact(package_id: &str, version: &str) -> anyhow::Result<PublishedArtifact> {
+    Ok(PublishedArtifact {
+        url: nupkg_url(package_id, version)?,
+        kind: ArtifactKind::Nupkg,
+        format: ArchiveFormat::Zip,
+        strip_components: 0,
+    })
+}
+
+/// Feed root from `PKGREP_NUGET_FEED_URL`: the v3 flat-container base as an HTTP(S)
+/// or `file://` URL, or a local directory in the same layout.
+fn nuget_feed_base() -> anyhow::Result<Url> {
+    let base = std::env::var("PKGREP_NUGET_FEED_URL")
+        .unwrap_or_else(|_| DEFAULT_NUGET_FEED_BASE.to_string());
+    if Path::new(&base).is_absolute() {
+        return Url::from_directory_path(&base)
+            .map_err(|_| anyhow::anyhow!("invalid nuget feed directory: {}", base));
+    }
+    Url::parse(&base).with_context(|| format!("invalid nuget feed URL: {}", base))
+}
+
+/// The flat container addresses packages by lowercased id and version.
+fn feed_url(segments: &[&str]) -> anyhow::Result<Url> {
+    let mut url = nuget_feed_base()?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid nuget feed URL path"))?
+        .pop_if_empty()
+        .extend(segments.iter().map(|segment| segment.to_ascii_lowercase()));
+    Ok(url)
+}
+
+fn nupkg_url(package_id: &str, version: &str) -> anyhow::Result<Url> {
+    feed_url(&[
+        package_id,
+        version,
+        &format!("{package_id}.{version}.nupkg"),
+    ])
+}
+
+fn latest_version(client: &RegistryClient, package_id: &str) -> anyhow::Result<String> {
+    let endpoint = feed_url(&[package_id, "index.json"])?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch nuget versions from {}", endpoint))?
+        .ok_or_else(|| anyhow::anyhow!("nuget package '{}' was not found", package_id))?;
+    let index: FlatContainerIndex = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse nuget versions JSON from {}", endpoint))?;
+    latest_stable_version(&index.versions)
+        .ok_or_else(|| anyhow::anyhow!("nuget package '{}' has no published version", package_id))
+}
+
+/// Picks the newest release without a pre-release label, else the newest version.
+fn latest_stable_version(versions: &[String]) -> Option<String> {
+    versions
+        .iter()
+        .rfind(|version| !version.contains('-'))
+        .or_else(|| versions.last())
+        .cloned()
+}
+
+fn read_nuspec_repository(nupkg: &[u8]) -> anyhow::Result<NuspecRepository> {
+    let files = archive::read_package_files(nupkg, ArchiveFormat::Zip, 0)?;
+    let nuspec = files
+        .iter()
+        .find(|(path, _)| !path.contains('/') && path.ends_with(".nuspec"))
+        .map(|(_, contents)| contents)
+        .ok_or_else(|| anyhow::anyhow!("package does not contain a .nuspec"))?;
+    let raw = std::str::from_utf8(nuspec).context(".nuspec is not UTF-8")?;
+    parse_nuspec_repository(raw)
+}
+
+fn parse_nuspec_repository(raw: &str) -> anyhow::Result<NuspecRepository> {
+    let document = roxmltree::Document::parse(raw)?;
+    let Some(repository) = document
+        .descendants()
+        .find(|node| node.has_tag_name("repository"))
+    else {
+        return Ok(NuspecRepository::default());
+    };
+    let attribute = |name: &str| {
+        repository
+            .attribute(name)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    Ok(NuspecRepository {
+        kind: attribute("type").map(|kind| kind.to_ascii_lowercase()),
+        url: attribute("url"),
+        commit: attribute("commit"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_repository_commit_from_nuspec() {
+        let nuspec = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd">
+  <metadata minClientVersion="2.12">
+    <id>Newtonsoft.Json</id>
+    <version>13.0.3</version>
+    <projectUrl>https://www.newtonsoft.com/json</projectUrl>
+    <repository type="git" url="https://github.com/JamesNK/Newtonsoft.Json" commit="0a2e291c0d9c0c7675d445703e51750363a549ef" />
+  </metadata>
+</package>"#;
+
+        assert_eq!(
+            parse_nuspec_repository(nuspec).expect("parse nuspec"),
+            NuspecRepository {
+                kind: Some(String::from("git")),
+                url: Some(String::from("https://github.com/JamesNK/Newtonsoft.Json")),
+                commit: Some(String::from("0a2e291c0d9c0c7675d445703e51750363a549ef")),
+            }
+        );
+        assert_eq!(
+            parse_nuspec_repository("<package><metadata><id>Demo</id></metadata></package>")
+                .expect("parse nuspec"),
+            NuspecRepository::default()
+        );
+    }
+
+    #[test]
+    fn prefers_the_newest_stable_version() {
+        let versions = ["1.0.0", "1.1.0", "2.0.0-preview.1"].map(String::from);
+        assert_eq!(latest_stable_version(&versions).as_deref(), Some("1.1.0"));
+        assert_eq!(
+            latest_stable_version(&[String::from("0.1.0-alpha")]).as_deref(),
+            Some("0.1.0-alpha")
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, published_archive_resolution,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_PACKAGIST_REPOSITORY_BASE: &str = "https://repo.packagist.org";
+// Composer 2 metadata only lists the fields that changed from the previous version.
+const MINIFIED_UNSET: &str = "__unset";
+
+#[derive(Debug, Deserialize)]
+struct PackagistMetadata {
+    #[serde(default)]
+    packages: BTreeMap<String, Vec<serde_json::Map<String, Value>>>,
+    #[serde(default)]
+    minified: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct PackagistVersion {
+    version: String,
+    #[serde(default)]
+    version_normalized: Option<String>,
+    #[serde(default)]
+    source: Option<PackagistReference>,
+    #[serde(default)]
+    dist: Option<PackagistReference>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct PackagistReference {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    reference: Option<String>,
+}
+
+pub(super) fn resolve_packagist(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let versions = fetch_versions(client, &package_name)?;
+
+    let selected = match spec.version.as_deref() {
+        Some(requested) => find_version(&versions, requested).ok_or_else(|| {
+            anyhow::anyhow!(
+                "packagist package '{}' does not contain requested version '{}'",
+                package_name,
+                requested
+            )
+        })?,
+        None => latest_stable_version(&versions).ok_or_else(|| {
+            anyhow::anyhow!("packagist package '{}' has no tagged release", package_name)
+        })?,
+    };
+    let selected_version = selected.version.clone();
+
+    let git_source = selected
+        .source
+        .as_ref()
+        .filter(|source| source.kind == "git");
+    let Some(source) = git_source else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Packagist,
+            package_name,
+            selected_version,
+            "metadata does not provide a git source",
+        ));
+    };
+    let Some(git_url) = git_url_or_local_path(&source.url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Packagist,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {}", source.url),
+        ));
+    };
+    let (requested_revision, requested_revision_source) = match source.reference.clone() {
+        Some(reference) => (reference, RequestedRevisionSource::ExactMetadata),
+        None => (
+            selected_version.clone(),
+            RequestedRevisionSource::VersionDerived,
+        ),
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Packagist,
+            locator: package_name,
+            git_url,
+            requested_revision,
+            subdirectory: None,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source,
+    })
+}
+
+/// Finds the `dist` zip Packagist records for `package_name@version`.
+pub(super) fn dist_artifact(
+    client: &RegistryClient,
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    let versions = fetch_versions(client, package_name)?;
+    let dist = find_version(&versions, version)
+        .and_then(|selected| selected.dist.clone())
+        .filter(|dist| dist.kind == "zip")
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "packagist package '{}' does not publish a zip dist for version '{}'",
+                package_name,
+                version
+            )
+        })?;
+    let url = Url::parse(&dist.url)
+        .with_context(|| format!("invalid packagist dist URL: {}", dist.url))?;
+    Ok(PublishedArtifact {
+        url,
+        kind: ArtifactKind::ComposerDist,
+        format: ArchiveFormat::Zip,
+        // GitHub zipballs wrap the files in `<owner>-<repo>-<short commit>/`.
+        strip_components: 1,
+    })
+}
+
+fn metadata_endpoint(package_name: &str) -> anyhow::Result<Url> {
+    let (vendor, name) = package_name
+        .split_once('/')
+        .filter(|(vendor, name)| !vendor.is_empty() && !name.is_empty() && !name.contains('/'))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "invalid packagist package name '{}': expected '<vendor>/<name>'",
+                package_name
+            )
+        })?;
+    let base = std::env::var("PKGREP_PACKAGIST_REPOSITORY_URL")
+        .unwrap_or_else(|_| DEFAULT_PACKAGIST_REPOSITORY_BASE.to_string());
+    let mut url =
+        Url::parse(&base).with_context(|| format!("invalid packagist repository URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid packagist repository URL path: {}", base))?
+        .pop_if_empty()
+        .push("p2")
+        .push(vendor)
+        .push(&format!("{name}.json"));
+    Ok(url)
+}
+
+fn fetch_versions(
+    client: &RegistryClient,
+    package_name: &str,
+) -> anyhow::Result<Vec<PackagistVersion>> {
+    let endpoint = metadata_endpoint(package_name)?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch packagist metadata from {}", endpoint))?
+        .ok_or_else(|| anyhow::anyhow!("packagist package '{}' was not found", package_name))?;
+    let metadata: PackagistMetadata = serde_json::from_slice(&bytes).with_context(|| {
+        format!(
+            "failed to parse packagist metadata JSON for '{}'",
+            package_name
+        )
+    })?;
+    versions_from_metadata(metadata, package_name)
+}
+
+fn versions_from_metadata(
+    mut metadata: PackagistMetadata,
+    package_name: &str,
+) -> anyhow::Result<Vec<PackagistVersion>> {
+    let entries = metadata.packages.remove(package_name).unwrap_or_default();
+    let entries = if metadata.minified.is_some() {
+        expand_minified(entries)
+    } else {
+        entries
+    };
+    entries
+        .into_iter()
+        .map(|entry| {
+            serde_json::from_value(Value::Object(entry)).with_context(|| {
+                format!(
+                    "failed to parse packagist version entry for '{}'",
+                    package_name
+                )
+            })
+        })
+        .collect()
+}
+
+/// Rebuilds full version entries from `"minified": "composer/2.0"` metadata, where
+/// each entry inherits every field of the one before it and `"__unset"` drops one.
+fn expand_minified(
+    entries: Vec<serde_json::Map<String, Value>>,
+) -> Vec<serde_json::Map<String, Value>> {
+    let mut expanded = Vec::with_capacity(entries.len());
+    let mut current = serde_json::Map::new();
+    for entry in entries {
+        for (key, value) in entry {
+            if value.as_str() == Some(MINIFIED_UNSET) {
+                current.remove(&key);
+            } else {
+                current.insert(key, value);
+            }
+        }
+        expanded.push(current.clone());
+    }
+    expanded
+}
+
+/// Matches `requested` against the version as published, ignoring a leading `v`.
+fn find_version<'a>(
+    versions: &'a [PackagistVersion],
+    requested: &str,
+) -> Option<&'a PackagistVersion> {
+    let requested = requested.strip_prefix('v').unwrap_or(requested);
+    versions.iter().find(|version| {
+        version
+            .version
+            .strip_prefix('v')
+            .unwrap_or(&version.version)
+            == requested
+            || version.version_normalized.as_deref() == Some(requested)
+    })
+}
+
+/// Packagist lists releases newest first; Composer marks pre-releases with a
+/// suffix on the normalized version (`1.0.0.0-RC1`).
+fn latest_stable_version(versions: &[PackagistVersion]) -> Option<&PackagistVersion> {
+    versions
+        .iter()
+        .find(|version| {
+            version
+                .version_normalized
+                .as_deref()
+                .is_some_and(|normalized| !normalized.contains('-'))
+        })
+        .or_else(|| versions.first())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_minified_metadata_and_picks_the_latest_stable_release() {
+        let metadata: PackagistMetadata = serde_json::from_str(
+            r#"{
+  "minified": "composer/2.0",
+  "packages": {
+    "monolog/monolog": [
+      {"name": "monolog/monolog", "version": "4.0.0-RC1", "version_normalized": "4.0.0.0-RC1",
+       "source": {"type": "git", "url": "https://github.com/Seldaek/monolog.git", "reference": "1111111111111111111111111111111111111111"},
+       "dist": {"type": "zip", "url": "https://api.github.com/repos/Seldaek/monolog/zipball/1111111"}},
+      {"version": "3.5.0", "version_normalized": "3.5.0.0",
+       "source": {"type": "git", "url": "https://github.com/Seldaek/monolog.git", "reference": "c915e2634718dbc8a4a15c61b0e62e7a44e14448"},
+       "dist": "__unset"}
+    ]
+  }
+}"#,
+        )
+        .expect("metadata");
+
+        let versions = versions_from_metadata(metadata, "monolog/monolog").expect("versions");
+        let latest = latest_stable_version(&versions).expect("stable release");
+        assert_eq!(latest.version, "3.5.0");
+        assert!(latest.dist.is_none());
+        assert_eq!(
+            latest
+                .source
+                .as_ref()
+                .and_then(|source| source.reference.as_deref()),
+            Some("c915e2634718dbc8a4a15c61b0e62e7a44e14448")
+        );
+        assert_eq!(
+            find_version(&versions, "v4.0.0-RC1").map(|version| version.version.as_str()),
+            Some("4.0.0-RC1")
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, published_archive_resolution, split_web_tree_url,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_PUB_HOSTED_BASE: &str = "https://pub.dev";
+
+/// The fields pkgrep reads from `/api/packages/<name>`.
+#[derive(Debug, Deserialize)]
+struct PubPackage {
+    #[serde(default)]
+    latest: Option<PubVersion>,
+    #[serde(default)]
+    versions: Vec<PubVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PubVersion {
+    version: String,
+    #[serde(default)]
+    pubspec: Pubspec,
+    #[serde(default)]
+    archive_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Pubspec {
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+}
+
+pub(super) fn resolve_pub(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let package_name = spec.locator.clone();
+    let package = fetch_pub_package(client, &package_name)?;
+
+    let release = match spec.version {
+        Some(version) => find_version(package, &version).ok_or_else(|| {
+            anyhow::anyhow!(
+                "pub package '{}' does not contain requested version '{}'",
+                package_name,
+                version
+            )
+        })?,
+        None => package.latest.ok_or_else(|| {
+            anyhow::anyhow!("pub package '{}' has no published version", package_name)
+        })?,
+    };
+    let selected_version = release.version.clone();
+
+    let Some(repository_url) = pubspec_repository_url(&release.pubspec) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Pub,
+            package_name,
+            selected_version,
+            "pubspec does not provide a repository or homepage",
+        ));
+    };
+    // Packages in a monorepo point `repository` at their directory in the forge UI.
+    let (repository_url, subdirectory) = split_web_tree_url(repository_url);
+    let Some(git_url) = git_url_or_local_path(&repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Pub,
+            package_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Pub,
+            locator: package_name,
+            git_url,
+            requested_revision: selected_version.clone(),
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source: RequestedRevisionSource::VersionDerived,
+    })
+}
+
+pub(super) fn archive_artifact(
+    client: &RegistryClient,
+    package_name: &str,
+    version: &str,
+) -> anyhow::Result<PublishedArtifact> {
+    let package = fetch_pub_package(client, package_name)?;
+    let archive_url = find_version(package, version)
+        .and_then(|release| release.archive_url)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "pub package '{}' does not publish an archive for version '{}'",
+                package_name,
+                version
+            )
+        })?;
+    Ok(PublishedArtifact {
+        url: Url::parse(&archive_url)
+            .with_context(|| format!("invalid pub archive URL: {}", archive_url))?,
+        kind: ArtifactKind::PubArchive,
+        format: ArchiveFormat::TarGz,
+        strip_components: 0,
+    })
+}
+
+fn fetch_pub_package(client: &RegistryClient, package_name: &str) -> anyhow::Result<PubPackage> {
+    let endpoint = pub_url(&["api", "packages", package_name])?;
+    let bytes = fetch_url_bytes(client, &endpoint)
+        .with_context(|| format!("failed to fetch pub metadata from {}", endpoint))?
+        .ok_or_else(|| anyhow::anyhow!("pub package '{}' was not found", package_name))?;
+    serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse pub metadata JSON for '{}'", package_name))
+}
+
+/// Hosted URL from `PKGREP_PUB_HOSTED_URL`, else the `PUB_HOSTED_URL` mirror `dart pub` uses.
+fn pub_url(segments: &[&str]) -> anyhow::Result<Url> {
+    let base = std::env::var("PKGREP_PUB_HOSTED_URL")
+        .or_else(|_| std::env::var("PUB_HOSTED_URL"))
+        .unwrap_or_else(|_| DEFAULT_PUB_HOSTED_BASE.to_string());
+    let mut url = Url::parse(&base).with_context(|| format!("invalid pub hosted URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid pub hosted URL path: {}", base))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+fn find_version(package: PubPackage, version: &str) -> Option<PubVersion> {
+    package
+        .versions
+        .into_iter()
+        .find(|release| release.version == version)
+}
+
+fn pubspec_repository_url(pubspec: &Pubspec) -> Option<&str> {
+    [pubspec.repository.as_deref(), pubspec.homepage.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|url| !url.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_repository_over_homepage() {
+        let release: PubVersion = serde_json::from_str(
+            r#"{"version":"1.2.2","pubspec":{"name":"http","homepage":"https://dart.dev","repository":"https://github.com/dart-lang/http/tree/master/pkgs/http"},"archive_url":"https://pub.dev/api/archives/http-1.2.2.tar.gz"}"#,
+        )
+        .expect("pub version");
+
+        let repository_url = pubspec_repository_url(&release.pubspec).expect("repository");
+        assert_eq!(
+            split_web_tree_url(repository_url),
+            (
+                String::from("https://github.com/dart-lang/http"),
+                Some(String::from("pkgs/http"))
+            )
+        );
+
+        let homepage_only = Pubspec {
+            repository: Some(String::from("  ")),
+            homepage: Some(String::from("https://github.com/acme/demo")),
+        };
+        assert_eq!(
+            pubspec_repository_url(&homepage_only),
+            Some("https://github.com/acme/demo")
+        );
+    }
+}
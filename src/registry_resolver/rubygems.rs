@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::Deserialize;
+
+use super::{
+    ArtifactKind, PublishedArtifact, RegistryClient, RegistryResolution, RequestedRevisionSource,
+    fetch_url_bytes, git_url_or_local_path, published_archive_resolution, split_web_tree_url,
+};
+use crate::archive::ArchiveFormat;
+use crate::depspec::{DepSpec, Ecosystem};
+use crate::source::{GitPullTarget, PullTarget};
+
+const DEFAULT_RUBYGEMS_REGISTRY_BASE: &str = "https://rubygems.org";
+
+/// The fields pkgrep reads from both `/api/v1/gems/<name>.json` (latest version) and
+/// `/api/v2/rubygems/<name>/versions/<version>.json`.
+#[derive(Debug, Deserialize)]
+struct GemInfo {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    source_code_uri: Option<String>,
+    #[serde(default)]
+    homepage_uri: Option<String>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+pub(super) fn resolve_rubygems(
+    client: &RegistryClient,
+    spec: DepSpec,
+) -> anyhow::Result<RegistryResolution> {
+    let gem_name = spec.locator.clone();
+
+    let (selected_version, info) = match spec.version {
+        Some(version) => {
+            let endpoint = rubygems_url(&[
+                "api",
+                "v2",
+                "rubygems",
+                &gem_name,
+                "versions",
+                &format!("{version}.json"),
+            ])?;
+            let info = fetch_gem_info(client, &endpoint)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "rubygems package '{}' does not contain requested version '{}'",
+                    gem_name,
+                    version
+                )
+            })?;
+            (version, info)
+        }
+        None => {
+            let endpoint = rubygems_url(&["api", "v1", "gems", &format!("{gem_name}.json")])?;
+            let info = fetch_gem_info(client, &endpoint)?
+                .ok_or_else(|| anyhow::anyhow!("rubygems package '{}' was not found", gem_name))?;
+            let version = info.version.clone().ok_or_else(|| {
+                anyhow::anyhow!("rubygems package '{}' has no published version", gem_name)
+            })?;
+            (version, info)
+        }
+    };
+
+    let Some(repository_url) = gem_repository_url(&info) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Rubygems,
+            gem_name,
+            selected_version,
+            "metadata does not provide a source_code_uri or homepage_uri",
+        ));
+    };
+    let (repository_url, subdirectory) = split_web_tree_url(&repository_url);
+    let Some(git_url) = git_url_or_local_path(&repository_url) else {
+        return Ok(published_archive_resolution(
+            Ecosystem::Rubygems,
+            gem_name,
+            selected_version,
+            &format!("repository URL is not a supported git URL: {repository_url}"),
+        ));
+    };
+
+    Ok(RegistryResolution {
+        target: PullTarget::Git(GitPullTarget {
+            ecosystem: Ecosystem::Rubygems,
+            locator: gem_name,
+            git_url,
+            requested_revision: selected_version.clone(),
+            subdirectory,
+            release_tag_package: None,
+        }),
+        package_version: selected_version,
+        requested_revision_source: RequestedRevisionSource::VersionDerived,
+    })
+}
+
+pub(super) fn gem_artifact(gem_name: &str, version: &str) -> anyhow::Result<PublishedArtifact> {
+    Ok(PublishedArtifact {
+        url: rubygems_url(&["downloads", &format!("{gem_name}-{version}.gem")])?,
+        kind: ArtifactKind::Gem,
+        format: ArchiveFormat::Gem,
+        strip_components: 0,
+    })
+}
+
+fn rubygems_url(segments: &[&str]) -> anyhow::Result<Url> {
+    let base = std::env::var("PKGREP_RUBYGEMS_REGISTRY_URL")
+        .unwrap_or_else(|_| DEFAULT_RUBYGEMS_REGISTRY_BASE.to_string());
+    let mut url =
+        Url::parse(&base).with_context(|| format!("invalid rubygems registry URL: {}", base))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid rubygems registry URL path: {}", base))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+fn fetch_gem_info(client: &RegistryClient, endpoint: &Url) -> anyhow::Result<Option<GemInfo>> {
+    let Some(bytes) = fetch_url_bytes(client, endpoint)
+        .with_context(|| format!("failed to fetch rubygems metadata from {}", endpoint))?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .with_context(|| format!("failed to parse rubygems metadata JSON from {}", endpoint))
+}
+
+fn gem_repository_url(info: &GemInfo) -> Option<String> {
+    [
+        info.source_code_uri.as_ref(),
+        info.metadata.get("source_code_uri"),
+        info.homepage_uri.as_ref(),
+        info.metadata.get("homepage_uri"),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|url| url.trim())
+    .find(|url| !url.is_empty())
+    .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_source_code_uri_over_homepage() {
+        let info: GemInfo = serde_json::from_str(
+            r#"{"version":"7.1.3","source_code_uri":null,"homepage_uri":"https://rubyonrails.org","metadata":{"source_code_uri":"https://github.com/rails/rails/tree/v7.1.3/activerecord"}}"#,
+        )
+        .expect("gem info");
+
+        assert_eq!(
+            gem_repository_url(&info).as_deref(),
+            Some("https://github.com/rails/rails/tree/v7.1.3/activerecord")
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use semver::{Version, VersionReq};
+
+/// Highest published version matching an npm range (`^18`, `18.x`, `>=1.2 <2`,
+/// `1.2.3 - 2.0.0`, `^1 || ^2`).
+pub(super) fn highest_npm_match<'a>(
+    range: &str,
+    versions: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<Option<String>> {
+    let alternatives = range
+        .split("||")
+        .map(npm_comparator_set)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow::anyhow!("invalid npm version range '{range}': {err}"))?;
+    Ok(highest_semver_match(versions, |version| {
+        alternatives.iter().any(|req| req.matches(version))
+    }))
+}
+
+/// Highest published version matching a Cargo version requirement (`~1.0`, `1.0`,
+/// `>=1.2, <1.5`).
+pub(super) fn highest_cargo_match<'a>(
+    range: &str,
+    versions: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<Option<String>> {
+    let req = VersionReq::parse(range)
+        .map_err(|err| anyhow::anyhow!("invalid crates version requirement '{range}': {err}"))?;
+    Ok(highest_semver_match(versions, |version| {
+        req.matches(version)
+    }))
+}
+
+fn highest_semver_match<'a>(
+    versions: impl IntoIterator<Item = &'a str>,
+    matches: impl Fn(&Version) -> bool,
+) -> Option<String> {
+    versions
+        .into_iter()
+        .filter_map(|raw| Some((Version::parse(raw).ok()?, raw)))
+        .filter(|(version, _)| matches(version))
+        .max_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs))
+        .map(|(_, raw)| raw.to_string())
+}
+
+// npm separates comparators with spaces and treats a bare version as exact, while
+// the semver crate uses commas and reads a bare version as a caret requirement.
+fn npm_comparator_set(alternative: &str) -> Result<VersionReq, semver::Error> {
+    let alternative = alternative.trim();
+    if let Some((lower, upper)) = alternative.split_once(" - ") {
+        return VersionReq::parse(&format!(
+            ">={}, <={}",
+            strip_v(lower.trim()),
+            strip_v(upper.trim())
+        ));
+    }
+
+    let mut comparators = Vec::new();
+    let mut pending_operator = String::new();
+    for token in alternative.split_whitespace() {
+        if token
+            .chars()
+            .all(|ch| matches!(ch, '<' | '>' | '=' | '^' | '~'))
+        {
+            pending_operator.push_str(token);
+            continue;
+        }
+        let token = format!("{}{token}", std::mem::take(&mut pending_operator));
+        let operator_len = token
+            .find(|ch: char| !matches!(ch, '<' | '>' | '=' | '^' | '~'))
+            .unwrap_or(token.len());
+        let (operator, version) = token.split_at(operator_len);
+        let version = strip_v(version);
+        let is_wildcard = version
+            .split('.')
+            .any(|part| matches!(part, "x" | "X" | "*"));
+        let operator = match operator {
+            "" if !is_wildcard => "=",
+            other => other,
+        };
+        comparators.push(format!("{operator}{version}"));
+    }
+
+    if comparators.is_empty() {
+        return VersionReq::parse("*");
+    }
+    VersionReq::parse(&comparators.join(", "))
+}
+
+fn strip_v(version: &str) -> &str {
+    version.strip_prefix('v').unwrap_or(version)
+}
+
+/// Highest published version matching a PEP 440 specifier set (`>=4.2,<5`,
+/// `~=2.31`, `==1.26.*`). Pre-releases only match when a specifier names one.
+pub(super) fn highest_pep440_match<'a>(
+    specifiers: &str,
+    versions: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<Option<String>> {
+    let specifiers = specifiers
+        .split(',')
+        .map(|specifier| {
+            Pep440Specifier::parse(specifier.trim())
+                .ok_or_else(|| anyhow::anyhow!("invalid PEP 440 specifier '{}'", specifier.trim()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let allow_prereleases = specifiers
+        .iter()
+        .any(|specifier| specifier.version.is_prerelease());
+
+    Ok(versions
+        .into_iter()
+        .filter_map(|raw| Some((Pep440Version::parse(raw)?, raw)))
+        .filter(|(version, _)| allow_prereleases || !version.is_prerelease())
+        .filter(|(version, raw)| {
+            specifiers
+                .iter()
+                .all(|specifier| specifier.matches(version, raw))
+        })
+        .max_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs))
+        .map(|(_, raw)| raw.to_string()))
+}
+
+static PEP440_VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^v?(?:(\d+)!)?(\d+(?:\.\d+)*)(?:[-_.]?(a|alpha|b|beta|c|rc|pre|preview)[-_.]?(\d+)?)?(?:-(\d+)|[-_.]?(post|rev|r)[-_.]?(\d+)?)?(?:[-_.]?(dev)[-_.]?(\d+)?)?(?:\+[a-z0-9.]+)?$",
+    )
+    .expect("valid PEP 440 version regex")
+});
+
+#[derive(Clone, Debug)]
+struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    /// Pre-release phase (0 = alpha, 1 = beta, 2 = release candidate) and number.
+    pre: Option<(u8, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+}
+
+impl Pep440Version {
+    fn parse(raw: &str) -> Option<Self> {
+        let lowered = raw.trim().to_ascii_lowercase();
+        let captures = PEP440_VERSION.captures(&lowered)?;
+        let number = |index: usize| {
+            captures
+                .get(index)
+                .and_then(|value| value.as_str().parse::<u64>().ok())
+        };
+
+        let release = captures
+            .get(2)?
+            .as_str()
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let pre = captures.get(3).map(|phase| {
+            let phase = match phase.as_str() {
+                "a" | "alpha" => 0,
+                "b" | "beta" => 1,
+                _ => 2,
+            };
+            (phase, number(4).unwrap_or(0))
+        });
+        let post = number(5).or_else(|| captures.get(6).map(|_| number(7).unwrap_or(0)));
+        let dev = captures.get(8).map(|_| number(9).unwrap_or(0));
+
+        Some(Self {
+            epoch: number(1).unwrap_or(0),
+            release,
+            pre,
+            post,
+            dev,
+        })
+    }
+
+    fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    // Orders `1.0.dev0 < 1.0a1 < 1.0 < 1.0.post1`, with dev releases before their
+    // pre or post release.
+    fn sort_key(&self) -> (u64, &[u64], (i64, i64), i64, i64) {
+        let pre = match (self.pre, self.post, self.dev) {
+            (Some((phase, number)), _, _) => (i64::from(phase), number as i64),
+            (None, None, Some(_)) => (-1, 0),
+            _ => (3, 0),
+        };
+        // Trailing zeros do not count: `1.0.0` equals `1.0`.
+        let mut release_len = self.release.len();
+        while release_len > 1 && self.release[release_len - 1] == 0 {
+            release_len -= 1;
+        }
+        (
+            self.epoch,
+            &self.release[..release_len],
+            pre,
+            self.post.map_or(-1, |post| post as i64),
+            self.dev.map_or(i64::MAX, |dev| dev as i64),
+        )
+    }
+
+    fn release_starts_with(&self, prefix: &[u64]) -> bool {
+        prefix
+            .iter()
+            .enumerate()
+            .all(|(index, part)| self.release.get(index).copied().unwrap_or(0) == *part)
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440Version {}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Pep440Specifier {
+    operator: &'static str,
+    raw_version: String,
+    version: Pep440Version,
+    /// Release prefix of a `==1.2.*` / `!=1.2.*` wildcard.
+    wildcard_prefix: Option<Vec<u64>>,
+}
+
+impl Pep440Specifier {
+    fn parse(specifier: &str) -> Option<Self> {
+        let operator = ["===", "~=", "==", "!=", "<=", ">=", "<", ">"]
+            .into_iter()
+            .find(|operator| specifier.starts_with(operator))?;
+        let raw_version = specifier[operator.len()..].trim().to_string();
+        let wildcard_prefix = match raw_version.strip_suffix(".*") {
+            Some(prefix) if matches!(operator, "==" | "!=") => Some(
+                prefix
+                    .trim_start_matches('v')
+                    .split('.')
+                    .map(|part| part.parse::<u64>().ok())
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            Some(_) => return None,
+            None => None,
+        };
+        let version = match &wildcard_prefix {
+            Some(prefix) => Pep440Version {
+                epoch: 0,
+                release: prefix.clone(),
+                pre: None,
+                post: None,
+                dev: None,
+            },
+            // Arbitrary equality compares strings, so the version need not be valid.
+            None if operator == "===" => {
+                Pep440Version::parse(&raw_version).unwrap_or(Pep440Version {
+                    epoch: 0,
+                    release: vec![0],
+                    pre: None,
+                    post: None,
+                    dev: None,
+                })
+            }
+            None => Pep440Version::parse(&raw_version)?,
+        };
+        if operator == "~=" && version.release.len() < 2 {
+            return None;
+        }
+
+        Some(Self {
+            operator,
+            raw_version,
+            version,
+            wildcard_prefix,
+        })
+    }
+
+    fn matches(&self, candidate: &Pep440Version, raw_candidate: &str) -> bool {
+        if let Some(prefix) = &self.wildcard_prefix {
+            let prefix_match = candidate.release_starts_with(prefix);
+            return if self.operator == "==" {
+                prefix_match
+            } else {
+                !prefix_match
+            };
+        }
+
+        match self.operator {
+            "===" => raw_candidate == self.raw_version,
+            "==" => candidate == &self.version,
+            "!=" => candidate != &self.version,
+            "<=" => candidate <= &self.version,
+            ">=" => candidate >= &self.version,
+            "<" => candidate < &self.version,
+            ">" => candidate > &self.version,
+            // `~=2.31.0` means `>=2.31.0, ==2.31.*`.
+            "~=" => {
+                let prefix = &self.version.release[..self.version.release.len() - 1];
+                candidate >= &self.version && candidate.release_starts_with(prefix)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NPM_VERSIONS: [&str; 8] = [
+        "17.0.2",
+        "18.0.0",
+        "18.2.0",
+        "18.3.1",
+        "19.0.0-rc.1",
+        "19.0.0",
+        "19.1.0",
+        "not-a-version",
+    ];
+
+    #[test]
+    fn npm_ranges_select_highest_match() {
+        let highest = |range: &str| highest_npm_match(range, NPM_VERSIONS).expect("valid range");
+
+        assert_eq!(highest("^18"), Some(String::from("18.3.1")));
+        assert_eq!(highest("18.x"), Some(String::from("18.3.1")));
+        assert_eq!(highest("~18.2"), Some(String::from("18.2.0")));
+        assert_eq!(highest(">=17 <18.3"), Some(String::from("18.2.0")));
+        assert_eq!(highest(">= 18.0.0 < 19"), Some(String::from("18.3.1")));
+        assert_eq!(highest("17.0.0 - 18.2"), Some(String::from("18.2.0")));
+        assert_eq!(highest("^17 || ^18"), Some(String::from("18.3.1")));
+        assert_eq!(highest("18.2.0"), Some(String::from("18.2.0")));
+        assert_eq!(highest("*"), Some(String::from("19.1.0")));
+        assert_eq!(highest("^20"), None);
+        assert!(highest_npm_match(">=banana", NPM_VERSIONS).is_err());
+    }
+
+    #[test]
+    fn cargo_requirements_select_highest_match() {
+        let versions = ["1.0.100", "1.0.228", "1.1.0", "2.0.0-alpha.1"];
+        let highest =
+            |range: &str| highest_cargo_match(range, versions).expect("valid requirement");
+
+        assert_eq!(highest("~1.0"), Some(String::from("1.0.228")));
+        assert_eq!(highest("1.0"), Some(String::from("1.1.0")));
+        assert_eq!(
+            highest(">=1.0.100, <1.0.200"),
+            Some(String::from("1.0.100"))
+        );
+        assert_eq!(highest("^2"), None);
+    }
+
+    #[test]
+    fn pep440_specifiers_select_highest_match() {
+        let versions = [
+            "4.1.13",
+            "4.2",
+            "4.2.16",
+            "5.0a1",
+            "5.0",
+            "5.1.1",
+            "5.1.1.post1",
+            "6.0.dev1",
+        ];
+        let highest =
+            |specifiers: &str| highest_pep440_match(specifiers, versions).expect("valid specifier");
+
+        assert_eq!(highest(">=4.2,<5"), Some(String::from("4.2.16")));
+        assert_eq!(highest("~=4.1"), Some(String::from("4.2.16")));
+        assert_eq!(highest("~=4.2.0"), Some(String::from("4.2.16")));
+        assert_eq!(highest("==4.2.*"), Some(String::from("4.2.16")));
+        assert_eq!(highest("==4.2"), Some(String::from("4.2")));
+        assert_eq!(highest("!=5.1.1.post1,<6"), Some(String::from("5.1.1")));
+        assert_eq!(highest(">5"), Some(String::from("5.1.1.post1")));
+        assert_eq!(highest(">=5.0a1,<5.0"), Some(String::from("5.0a1")));
+        assert_eq!(highest(">=7"), None);
+        assert!(highest_pep440_match("=>4", versions).is_err());
+    }
+
+    #[test]
+    fn pep440_versions_order_pre_dev_and_post_releases() {
+        let ordered = [
+            "1.0.dev1",
+            "1.0a1",
+            "1.0b2",
+            "1.0rc1",
+            "1.0",
+            "1.0.post1",
+            "1.1",
+        ]
+        .map(|raw| Pep440Version::parse(raw).expect("valid version"));
+        assert!(ordered.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Pep440Version::parse("1.0.0"), Pep440Version::parse("1.0"));
+    }
+}
@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use git2::{Direction, Remote, RemoteHead, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::git_auth::GitAccess;
+
+// Tried in order for each package name; package-specific tags come first so a
+// monorepo's `pkg@1.2.3` wins over a plain `v1.2.3` that belongs to another package.
+const TAG_CONVENTIONS: [&str; 8] = [
+    "{name}@{version}",
+    "{name}@v{version}",
+    "{name}-v{version}",
+    "{name}-{version}",
+    "{name}/v{version}",
+    "{name}/{version}",
+    "v{version}",
+    "{version}",
+];
+const REMOTE_TAGS_FILE: &str = "pkgrep-remote-tags.json";
+
+/// The release tag a version-derived revision was mapped to, and the naming
+/// convention (a `{name}`/`{version}` template) it matched.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseTag {
+    pub name: String,
+    pub convention: String,
+}
+
+/// A package version to find among a repository's tags.
+#[derive(Clone, Debug)]
+pub struct TagQuery {
+    pub package_name: String,
+    pub version: String,
+    /// Template from the `tag_formats` config, tried before the built-in conventions.
+    pub configured_format: Option<String>,
+}
+
+impl TagQuery {
+    /// Ranked `(tag, convention)` candidates for this package version.
+    fn candidates(&self) -> Vec<(String, String)> {
+        let version = self.version.strip_prefix('v').unwrap_or(&self.version);
+        let names = package_name_variants(&self.package_name);
+        let mut candidates: Vec<(String, String)> = Vec::new();
+        let conventions = self
+            .configured_format
+            .iter()
+            .map(String::as_str)
+            .chain(TAG_CONVENTIONS);
+        for convention in conventions {
+            for name in &names {
+                let tag = convention
+                    .replace("{name}", name)
+                    .replace("{version}", version);
+                if !candidates.iter().any(|(existing, _)| *existing == tag) {
+                    candidates.push((tag, convention.to_string()));
+                }
+            }
+        }
+        candidates
+    }
+
+    /// The best-ranked candidate present in `tags`; tag names compare case-insensitively.
+    pub fn best_match(&self, tags: &[String]) -> Option<ReleaseTag> {
+        self.candidates()
+            .into_iter()
+            .find_map(|(candidate, convention)| {
+                tags.iter()
+                    .find(|tag| tag.eq_ignore_ascii_case(&candidate))
+                    .map(|tag| ReleaseTag {
+                        name: tag.clone(),
+                        convention,
+                    })
+            })
+    }
+
+    /// Whether `tag` follows the first convention tried, so no better tag can exist.
+    fn is_top_ranked(&self, tag: &ReleaseTag) -> bool {
+        let top_convention = self
+            .configured_format
+            .as_deref()
+            .unwrap_or(TAG_CONVENTIONS[0]);
+        tag.convention == top_convention
+    }
+}
+
+/// The full package name, then the last segment of scoped or namespaced names
+/// (`@scope/pkg` -> `pkg`, `group:artifact` -> `artifact`, `vendor/name` -> `name`).
+fn package_name_variants(package_name: &str) -> Vec<String> {
+    let mut names = vec![package_name.to_string()];
+    if let Some(short_name) = package_name.rsplit(['/', ':']).next()
+        && !short_name.is_empty()
+        && short_name != package_name
+    {
+        names.push(short_name.to_string());
+    }
+    names
+}
+
+/// Tags advertised by the mirror's origin. The listing is saved in the mirror and
+/// rewritten whenever the mirror is fetched; a saved match is reused only when it
+/// follows the top-ranked convention, since a better tag may have been pushed since.
+pub fn find_release_tag(
+    access: &GitAccess,
+    repo: &Repository,
+    query: &TagQuery,
+) -> anyhow::Result<Option<ReleaseTag>> {
+    let listing_path = repo.path().join(REMOTE_TAGS_FILE);
+    let cached_match = read_cached_tags(&listing_path).and_then(|tags| query.best_match(&tags));
+    // Offline, the saved listing is all there is to go on.
+    if access.is_offline() {
+        return Ok(cached_match);
+    }
+    if let Some(tag) = cached_match
+        && query.is_top_ranked(&tag)
+    {
+        return Ok(Some(tag));
+    }
+
+    let remote_tags = list_remote_tags(access, repo)?;
+    write_cached_tags(&listing_path, &remote_tags)?;
+    Ok(query.best_match(&remote_tags))
+}
+
+/// Refreshes the saved listing from the tags `remote` advertised during a fetch,
+/// so a mirror fetch never leaves an older listing behind.
+pub fn save_advertised_tags(repo: &Repository, remote: &Remote<'_>) -> anyhow::Result<()> {
+    let heads = remote.list().context("failed to read advertised refs")?;
+    write_cached_tags(&repo.path().join(REMOTE_TAGS_FILE), &tag_names(heads))
+}
+
+fn read_cached_tags(path: &Path) -> Option<Vec<String>> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn write_cached_tags(path: &Path, tags: &[String]) -> anyhow::Result<()> {
+    let serialized = serde_json::to_vec(tags).context("failed to serialize tag listing")?;
+    fs::write(path, serialized)
+        .with_context(|| format!("failed to write tag listing {}", path.display()))
+}
+
+fn list_remote_tags(access: &GitAccess, repo: &Repository) -> anyhow::Result<Vec<String>> {
+    let mut remote = repo
+        .find_remote("origin")
+        .context("failed to find origin remote in mirror repo")?;
+    let remote_url = remote.url().unwrap_or("<unknown>").to_string();
+    let remote = remote
+        .connect_auth(Direction::Fetch, Some(access.remote_callbacks()), None)
+        .with_context(|| format!("failed to connect to remote {}", remote_url))?;
+    let heads = remote
+        .list()
+        .with_context(|| format!("failed to list remote tags for {}", remote_url))?;
+    Ok(tag_names(heads))
+}
+
+fn tag_names(heads: &[RemoteHead<'_>]) -> Vec<String> {
+    heads
+        .iter()
+        .filter_map(|head| head.name().strip_prefix("refs/tags/"))
+        .filter(|tag| !tag.ends_with("^{}"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(package_name: &str, version: &str) -> TagQuery {
+        TagQuery {
+            package_name: package_name.to_string(),
+            version: version.to_string(),
+            configured_format: None,
+        }
+    }
+
+    #[test]
+    fn prefers_package_specific_tags_over_plain_version_tags() {
+        let tags = ["v1.2.3", "@acme/other@1.2.3", "@acme/widgets@1.2.3"].map(String::from);
+        assert_eq!(
+            query("@acme/widgets", "1.2.3").best_match(&tags),
+            Some(ReleaseTag {
+                name: String::from("@acme/widgets@1.2.3"),
+                convention: String::from("{name}@{version}"),
+            })
+        );
+
+        let tags = ["v1.2.3", "widgets-v1.2.3"].map(String::from);
+        assert_eq!(
+            query("@acme/widgets", "1.2.3").best_match(&tags),
+            Some(ReleaseTag {
+                name: String::from("widgets-v1.2.3"),
+                convention: String::from("{name}-v{version}"),
+            })
+        );
+
+        let tags = ["Widgets/v1.2.3", "1.2.3"].map(String::from);
+        assert_eq!(
+            query("widgets", "v1.2.3")
+                .best_match(&tags)
+                .map(|tag| tag.name),
+            Some(String::from("Widgets/v1.2.3"))
+        );
+
+        let tags = ["v1.2.2", "1.2.3"].map(String::from);
+        assert_eq!(
+            query("widgets", "1.2.3").best_match(&tags),
+            Some(ReleaseTag {
+                name: String::from("1.2.3"),
+                convention: String::from("{version}"),
+            })
+        );
+        assert_eq!(query("widgets", "2.0.0").best_match(&tags), None);
+    }
+
+    #[test]
+    fn configured_format_is_tried_first() {
+        let tags = ["v1.2.3", "release/widgets/1.2.3"].map(String::from);
+        let mut configured = query("widgets", "1.2.3");
+        configured.configured_format = Some(String::from("release/{name}/{version}"));
+        assert_eq!(
+            configured.best_match(&tags),
+            Some(ReleaseTag {
+                name: String::from("release/widgets/1.2.3"),
+                convention: String::from("release/{name}/{version}"),
+            })
+        );
+    }
+
+    #[test]
+    fn only_the_first_convention_counts_as_top_ranked() {
+        let tags = ["v1.2.3", "widgets@1.2.3"].map(String::from);
+        let plain = query("widgets", "1.2.3");
+        let best = plain.best_match(&tags).expect("match");
+        assert!(plain.is_top_ranked(&best));
+        assert!(!plain.is_top_ranked(&plain.best_match(&tags[..1]).expect("match")));
+
+        let mut configured = query("widgets", "1.2.3");
+        configured.configured_format = Some(String::from("release/{name}/{version}"));
+        assert!(!configured.is_top_ranked(&best));
+    }
+}
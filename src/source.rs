@@ -1,13 +1,23 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use anyhow::Context;
 use git2::build::CheckoutBuilder;
-use git2::{AutotagOption, Direction, FetchOptions, Oid, RemoteCallbacks, Repository};
-use tracing::debug;
+use git2::{AutotagOption, Direction, FetchOptions, ObjectType, Oid, Repository};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
 
-use crate::config::Config;
+use crate::archive;
+use crate::cache_lock;
+use crate::config::{CheckoutStrategy, Config};
 use crate::depspec::{self, Ecosystem};
+use crate::error::PkgrepError;
+use crate::git_auth::GitAccess;
+use crate::registry_resolver;
+use crate::release_tags::{self, ReleaseTag, TagQuery};
+
+const ARCHIVE_FINGERPRINT_PREFIX: &str = "archive-sha256-";
 
 #[derive(Clone, Debug)]
 pub struct GitPullTarget {
@@ -15,6 +25,61 @@ pub struct GitPullTarget {
     pub locator: String,
     pub git_url: String,
     pub requested_revision: String,
+    /// Package directory inside a monorepo, relative to the repository root.
+    pub subdirectory: Option<String>,
+    /// Package to look for among the repository's release tags when the revision is a
+    /// version number rather than a git ref.
+    pub release_tag_package: Option<String>,
+}
+
+/// Registry package whose published artifact is unpacked instead of a git checkout.
+#[derive(Clone, Debug)]
+pub struct ArchivePullTarget {
+    pub ecosystem: Ecosystem,
+    pub package_name: String,
+    pub package_version: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum PullTarget {
+    Git(GitPullTarget),
+    Archive(ArchivePullTarget),
+}
+
+impl PullTarget {
+    pub fn ecosystem(&self) -> &Ecosystem {
+        match self {
+            Self::Git(target) => &target.ecosystem,
+            Self::Archive(target) => &target.ecosystem,
+        }
+    }
+
+    pub fn locator(&self) -> &str {
+        match self {
+            Self::Git(target) => &target.locator,
+            Self::Archive(target) => &target.package_name,
+        }
+    }
+
+    /// Git revision, or package version for archives; used in links and cache keys.
+    pub fn revision(&self) -> &str {
+        match self {
+            Self::Git(target) => &target.requested_revision,
+            Self::Archive(target) => &target.package_version,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Git(target) => format!("{}@{}", target.git_url, target.requested_revision),
+            Self::Archive(target) => format!(
+                "{}:{}@{} (published archive)",
+                target.ecosystem.as_str(),
+                target.package_name,
+                target.package_version
+            ),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -23,7 +88,13 @@ pub struct MaterializedSource {
     pub source_fingerprint: String,
     pub checkout_path: PathBuf,
     pub project_link_path: PathBuf,
+    /// Subdirectory the project link points at, when the target's subdirectory
+    /// exists in the checkout.
+    pub linked_subdirectory: Option<String>,
+    /// Release tag the requested version was mapped to by tag discovery.
+    pub release_tag: Option<ReleaseTag>,
     pub git_fetch_performed: bool,
+    pub archive_download_performed: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -40,33 +111,239 @@ pub fn materialize_git_source(
     let cache_root = cache_root_for(cwd, &config.cache_dir);
 
     let mirror_repo_path = mirror_repo_path(&cache_root, &target.ecosystem, &target.git_url);
-    let (mirror_repo, git_fetch_performed) = ensure_mirror_repo(
+    let _mirror_lock = cache_lock::acquire(
+        &cache_root,
+        &mirror_repo_path,
+        "git mirror",
+        config.lock_timeout(),
+    )?;
+    let tag_query = release_tag_query(config, target);
+    let (mirror_repo, release_tag, git_fetch_performed) = ensure_mirror_repo(
+        &GitAccess::from_config(config),
         &target.git_url,
         &mirror_repo_path,
         &target.requested_revision,
+        tag_query.as_ref(),
     )?;
-    let source_fingerprint = resolve_commit_fingerprint(&mirror_repo, &target.requested_revision)?;
+    let fingerprint_revision = match &release_tag {
+        Some(tag) => format!("refs/tags/{}", tag.name),
+        None => target.requested_revision.clone(),
+    };
+    let source_fingerprint = resolve_commit_fingerprint(&mirror_repo, &fingerprint_revision)?;
 
+    let sparse_subdirectory = match config.checkout_strategy {
+        CheckoutStrategy::Sparse => target.subdirectory.as_deref(),
+        CheckoutStrategy::Full => None,
+    };
     let cache_key = depspec::cache_key(
         &target.ecosystem,
         &target.locator,
         &target.requested_revision,
-        &source_fingerprint,
+        &checkout_key_component(&source_fingerprint, sparse_subdirectory),
     );
     let checkout_path = cache_root.join("sources").join(&cache_key);
-    ensure_checkout_exists(&mirror_repo_path, &checkout_path, &source_fingerprint)?;
+    let _checkout_lock = cache_lock::acquire(
+        &cache_root,
+        &checkout_path,
+        "cache checkout",
+        config.lock_timeout(),
+    )?;
+    let staging_path = checkout_staging_path(&cache_root, &cache_key);
+    ensure_checkout_exists(
+        &mirror_repo_path,
+        &checkout_path,
+        &staging_path,
+        &source_fingerprint,
+        sparse_subdirectory,
+    )?;
 
-    let project_link_path = link_checkout(cwd, target, &checkout_path)?;
+    let linked_subdirectory = linked_subdirectory(target, &checkout_path);
+    let link_target = match &linked_subdirectory {
+        Some(subdirectory) => checkout_path.join(subdirectory),
+        None => checkout_path.clone(),
+    };
+    let project_link_path = link_checkout(
+        cwd,
+        &target.ecosystem,
+        &target.locator,
+        &target.requested_revision,
+        &link_target,
+    )?;
 
     Ok(MaterializedSource {
         cache_key,
         source_fingerprint,
         checkout_path,
         project_link_path,
+        linked_subdirectory,
+        release_tag,
         git_fetch_performed,
+        archive_download_performed: false,
     })
 }
 
+/// Unpacks the registry's published artifact for `target` into the cache and links it.
+///
+/// Registries do not replace published files, so an artifact already unpacked for
+/// this package version is reused without downloading it again.
+pub fn materialize_archive_source(
+    cwd: &Path,
+    config: &Config,
+    target: &ArchivePullTarget,
+) -> anyhow::Result<MaterializedSource> {
+    let cache_root = cache_root_for(cwd, &config.cache_dir);
+    let version_dir = cache_root
+        .join("sources")
+        .join(target.ecosystem.as_str())
+        .join(depspec::normalize_locator(&target.package_name))
+        .join(&target.package_version);
+    let _archive_lock = cache_lock::acquire(
+        &cache_root,
+        &version_dir,
+        "published archive",
+        config.lock_timeout(),
+    )?;
+
+    let (source_fingerprint, archive_download_performed) =
+        match find_unpacked_archive(&version_dir)? {
+            Some(source_fingerprint) => (source_fingerprint, false),
+            None => (
+                unpack_published_archive(cwd, config, &cache_root, target)?,
+                true,
+            ),
+        };
+    let cache_key = depspec::cache_key(
+        &target.ecosystem,
+        &target.package_name,
+        &target.package_version,
+        &source_fingerprint,
+    );
+    let checkout_path = cache_root.join("sources").join(&cache_key);
+    let _checkout_lock = cache_lock::acquire(
+        &cache_root,
+        &checkout_path,
+        "cache checkout",
+        config.lock_timeout(),
+    )?;
+    let project_link_path = link_checkout(
+        cwd,
+        &target.ecosystem,
+        &target.package_name,
+        &target.package_version,
+        &checkout_path,
+    )?;
+
+    Ok(MaterializedSource {
+        cache_key,
+        source_fingerprint,
+        checkout_path,
+        project_link_path,
+        linked_subdirectory: None,
+        release_tag: None,
+        git_fetch_performed: false,
+        archive_download_performed,
+    })
+}
+
+/// Unpacked archives have no `.git`; their fingerprint directory marks them instead.
+pub fn is_archive_checkout(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(ARCHIVE_FINGERPRINT_PREFIX))
+}
+
+fn find_unpacked_archive(version_dir: &Path) -> anyhow::Result<Option<String>> {
+    let entries = match fs::read_dir(version_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| {
+                format!("failed to read cache directory {}", version_dir.display())
+            });
+        }
+    };
+
+    let mut fingerprints = Vec::new();
+    for entry in entries {
+        let entry = entry
+            .with_context(|| format!("failed to read cache directory {}", version_dir.display()))?;
+        let path = entry.path();
+        if path.is_dir() && is_archive_checkout(&path) {
+            fingerprints.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    fingerprints.sort();
+    Ok(fingerprints.into_iter().next())
+}
+
+fn unpack_published_archive(
+    cwd: &Path,
+    config: &Config,
+    cache_root: &Path,
+    target: &ArchivePullTarget,
+) -> anyhow::Result<String> {
+    let artifact = registry_resolver::locate_published_artifact(
+        cwd,
+        config,
+        &target.ecosystem,
+        &target.package_name,
+        &target.package_version,
+    )?;
+    debug!(
+        package = %target.package_name,
+        version = %target.package_version,
+        artifact_url = %artifact.url,
+        "downloading published archive"
+    );
+    let bytes = registry_resolver::download_published_artifact(cwd, config, &artifact)?;
+    let source_fingerprint = format!("{ARCHIVE_FINGERPRINT_PREFIX}{}", sha256_hex(&bytes));
+    let cache_key = depspec::cache_key(
+        &target.ecosystem,
+        &target.package_name,
+        &target.package_version,
+        &source_fingerprint,
+    );
+    let checkout_path = cache_root.join("sources").join(&cache_key);
+    let staging_path = checkout_staging_path(cache_root, &cache_key);
+
+    for dir in [checkout_path.parent(), staging_path.parent()]
+        .into_iter()
+        .flatten()
+    {
+        fs::create_dir_all(dir).with_context(|| {
+            format!(
+                "failed to create cache checkout parent directory {}",
+                dir.display()
+            )
+        })?;
+    }
+    if staging_path.exists() {
+        fs::remove_dir_all(&staging_path).with_context(|| {
+            format!(
+                "failed to remove stale checkout staging directory {}",
+                staging_path.display()
+            )
+        })?;
+    }
+
+    archive::unpack_package_files(
+        &bytes,
+        artifact.format,
+        artifact.strip_components,
+        &staging_path,
+    )
+    .with_context(|| format!("failed to unpack published archive {}", artifact.url))?;
+    fs::rename(&staging_path, &checkout_path).with_context(|| {
+        format!(
+            "failed to move unpacked archive {} into {}",
+            staging_path.display(),
+            checkout_path.display()
+        )
+    })?;
+
+    Ok(source_fingerprint)
+}
+
 pub fn cache_root_for(cwd: &Path, configured_cache_dir: &Path) -> PathBuf {
     if configured_cache_dir.is_absolute() {
         configured_cache_dir.to_path_buf()
@@ -75,10 +352,23 @@ pub fn cache_root_for(cwd: &Path, configured_cache_dir: &Path) -> PathBuf {
     }
 }
 
-pub fn resolve_default_remote_revision(git_url: &str) -> anyhow::Result<ResolvedRemoteRevision> {
+static REMOTE_RESOLVE_SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+pub fn resolve_default_remote_revision(
+    config: &Config,
+    git_url: &str,
+) -> anyhow::Result<ResolvedRemoteRevision> {
+    let access = GitAccess::from_config(config);
+    if access.is_offline() {
+        anyhow::bail!(
+            "offline mode: cannot resolve the default branch of {}; pin a revision instead (git:<url>@<rev>)",
+            git_url
+        );
+    }
     let temp_repo_path = std::env::temp_dir().join(format!(
-        "pkgrep-remote-resolve-{}-{}",
+        "pkgrep-remote-resolve-{}-{}-{}",
         std::process::id(),
+        REMOTE_RESOLVE_SEQUENCE.fetch_add(1, Ordering::Relaxed),
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .context("system clock is before UNIX_EPOCH")?
@@ -93,12 +383,12 @@ pub fn resolve_default_remote_revision(git_url: &str) -> anyhow::Result<Resolved
 
     let repo = Repository::init_bare(&temp_repo_path)
         .context("failed to initialize temporary bare repository")?;
-    let mut remote = repo
-        .remote_anonymous(git_url)
+    let mut anonymous_remote = repo
+        .remote_anonymous(&access.rewrite_url(git_url))
         .with_context(|| format!("failed to open anonymous remote for {}", git_url))?;
 
-    remote
-        .connect(Direction::Fetch)
+    let remote = anonymous_remote
+        .connect_auth(Direction::Fetch, Some(access.remote_callbacks()), None)
         .with_context(|| format!("failed to connect to remote {}", git_url))?;
 
     let default_branch_ref = remote
@@ -127,6 +417,7 @@ pub fn resolve_default_remote_revision(git_url: &str) -> anyhow::Result<Resolved
     };
 
     drop(remote);
+    drop(anonymous_remote);
     drop(repo);
     let _ = fs::remove_dir_all(&temp_repo_path);
 
@@ -136,20 +427,35 @@ pub fn resolve_default_remote_revision(git_url: &str) -> anyhow::Result<Resolved
     })
 }
 
-pub fn link_checkout(
+fn link_checkout(
     cwd: &Path,
-    target: &GitPullTarget,
-    checkout_path: &Path,
+    ecosystem: &Ecosystem,
+    locator: &str,
+    revision: &str,
+    link_target: &Path,
 ) -> anyhow::Result<PathBuf> {
-    let project_link_path = cwd.join(depspec::link_path(
-        &target.ecosystem,
-        &target.locator,
-        &target.requested_revision,
-    ));
-    ensure_symlink(checkout_path, &project_link_path)?;
+    let project_link_path = cwd.join(depspec::link_path(ecosystem, locator, revision));
+    ensure_symlink(link_target, &project_link_path)?;
     Ok(project_link_path)
 }
 
+// Registry metadata can name a directory that does not exist at the resolved
+// revision (for example when the package moved); link the repository root then.
+fn linked_subdirectory(target: &GitPullTarget, checkout_path: &Path) -> Option<String> {
+    let subdirectory = target.subdirectory.as_ref()?;
+    if checkout_path.join(subdirectory).is_dir() {
+        return Some(subdirectory.clone());
+    }
+
+    warn!(
+        git_url = %target.git_url,
+        requested_revision = %target.requested_revision,
+        subdirectory = %subdirectory,
+        "package subdirectory is missing from checkout; linking repository root"
+    );
+    None
+}
+
 fn mirror_repo_path(cache_root: &Path, ecosystem: &Ecosystem, git_url: &str) -> PathBuf {
     cache_root
         .join("repos")
@@ -157,11 +463,23 @@ fn mirror_repo_path(cache_root: &Path, ecosystem: &Ecosystem, git_url: &str) ->
         .join(format!("{}.git", depspec::normalize_locator(git_url)))
 }
 
+fn release_tag_query(config: &Config, target: &GitPullTarget) -> Option<TagQuery> {
+    let package_name = target.release_tag_package.as_ref()?;
+    let override_key = format!("{}:{}", target.ecosystem.as_str(), package_name);
+    Some(TagQuery {
+        package_name: package_name.clone(),
+        version: target.requested_revision.clone(),
+        configured_format: config.tag_formats.get(&override_key).cloned(),
+    })
+}
+
 fn ensure_mirror_repo(
+    access: &GitAccess,
     git_url: &str,
     mirror_repo_path: &Path,
     requested_revision: &str,
-) -> anyhow::Result<(Repository, bool)> {
+    tag_query: Option<&TagQuery>,
+) -> anyhow::Result<(Repository, Option<ReleaseTag>, bool)> {
     if let Some(parent) = mirror_repo_path.parent() {
         fs::create_dir_all(parent).with_context(|| {
             format!(
@@ -171,6 +489,9 @@ fn ensure_mirror_repo(
         })?;
     }
 
+    // Mirrors stay keyed by the URL the dependency names, so changing a rewrite rule
+    // repoints the existing mirror instead of cloning a new one.
+    let remote_url = access.rewrite_url(git_url);
     let repo = if mirror_repo_path.exists() {
         Repository::open_bare(mirror_repo_path).with_context(|| {
             format!(
@@ -190,16 +511,80 @@ fn ensure_mirror_repo(
                 mirror_repo_path.display()
             )
         })?;
-        repo.remote("origin", git_url)
+        repo.remote("origin", &remote_url)
             .with_context(|| format!("failed to configure origin remote for {}", git_url))?;
         repo
     };
+    update_origin_url(&repo, &remote_url)?;
 
-    let git_fetch_performed = ensure_revision_available(&repo, requested_revision)?;
-    Ok((repo, git_fetch_performed))
+    let release_tag = match tag_query {
+        Some(query) => discover_release_tag(access, &repo, query),
+        None => None,
+    };
+    let git_fetch_performed = match &release_tag {
+        Some(tag) => ensure_revision_available(access, &repo, &format!("refs/tags/{}", tag.name))?,
+        None => ensure_revision_available(access, &repo, requested_revision)?,
+    };
+    Ok((repo, release_tag, git_fetch_performed))
+}
+
+fn update_origin_url(repo: &Repository, remote_url: &str) -> anyhow::Result<()> {
+    let configured = repo
+        .config()
+        .context("failed to read mirror repo config")?
+        .get_string("remote.origin.url")
+        .ok();
+    if configured.as_deref() != Some(remote_url) {
+        debug!(remote_url = %remote_url, "updating mirror origin URL");
+        repo.remote_set_url("origin", remote_url)
+            .with_context(|| format!("failed to set origin remote URL to {}", remote_url))?;
+    }
+    Ok(())
+}
+
+// Discovery only refines the guess; when the listing fails, the plain
+// `<version>` / `v<version>` refspecs are still tried.
+fn discover_release_tag(
+    access: &GitAccess,
+    repo: &Repository,
+    query: &TagQuery,
+) -> Option<ReleaseTag> {
+    match release_tags::find_release_tag(access, repo, query) {
+        Ok(Some(tag)) => {
+            debug!(
+                package = %query.package_name,
+                version = %query.version,
+                tag = %tag.name,
+                convention = %tag.convention,
+                "mapped package version to release tag"
+            );
+            Some(tag)
+        }
+        Ok(None) => {
+            debug!(
+                package = %query.package_name,
+                version = %query.version,
+                "no release tag matches package version"
+            );
+            None
+        }
+        Err(err) => {
+            warn!(
+                package = %query.package_name,
+                version = %query.version,
+                error = %format!("{err:#}"),
+                "failed to list remote tags for release tag discovery"
+            );
+            None
+        }
+    }
 }
 
-fn ensure_revision_available(repo: &Repository, requested_revision: &str) -> anyhow::Result<bool> {
+fn ensure_revision_available(
+    access: &GitAccess,
+    repo: &Repository,
+    requested_revision: &str,
+) -> anyhow::Result<bool> {
     if try_resolve_commit_fingerprint_with_alternates(repo, requested_revision).is_some() {
         debug!(
             requested_revision = requested_revision,
@@ -208,25 +593,52 @@ fn ensure_revision_available(repo: &Repository, requested_revision: &str) -> any
         return Ok(false);
     }
 
-    fetch_targeted_revision(repo, requested_revision)?;
+    fetch_targeted_revision(access, repo, requested_revision)?;
 
     if try_resolve_commit_fingerprint_with_alternates(repo, requested_revision).is_none() {
-        anyhow::bail!(
-            "requested revision '{requested_revision}' is unavailable after targeted fetch"
-        );
+        return Err(PkgrepError::RevisionNotFound {
+            revision: requested_revision.to_string(),
+            remote_url: repo
+                .find_remote("origin")
+                .ok()
+                .and_then(|remote| remote.url().map(str::to_string))
+                .unwrap_or_else(|| "<unknown>".to_string()),
+            attempts: "requested revision is unavailable after targeted fetch".to_string(),
+        }
+        .into());
     }
 
     Ok(true)
 }
 
-fn fetch_targeted_revision(repo: &Repository, requested_revision: &str) -> anyhow::Result<()> {
+fn fetch_targeted_revision(
+    access: &GitAccess,
+    repo: &Repository,
+    requested_revision: &str,
+) -> anyhow::Result<()> {
     let mut remote = repo
         .find_remote("origin")
         .context("failed to find origin remote in mirror repo")?;
     let remote_url = remote.url().unwrap_or("<unknown>").to_string();
+    if access.is_offline() {
+        anyhow::bail!(
+            "offline mode: revision '{}' of {} is not in the local mirror",
+            requested_revision,
+            remote_url
+        );
+    }
+
+    if let Some(path) = local_remote_path(&remote_url)
+        && !path.exists()
+    {
+        return Err(PkgrepError::RepositoryNotFound { remote_url }.into());
+    }
 
     let refspecs = targeted_refspecs(requested_revision);
-    let shallow = supports_shallow_fetch(&remote_url);
+    // Servers only hand out commits by full id, so an abbreviated commit (Go
+    // pseudo-versions record 12 hex digits) has to be found in branch history.
+    let shallow =
+        supports_shallow_fetch(&remote_url) && !looks_like_abbreviated_commit(requested_revision);
     debug!(
         remote_url = %remote_url,
         requested_revision = requested_revision,
@@ -236,10 +648,19 @@ fn fetch_targeted_revision(repo: &Repository, requested_revision: &str) -> anyho
     );
 
     let mut errors = Vec::new();
+    // A fetch that completes without the revision means the server answered
+    // and simply does not have it; missing refs are not an error to libgit2.
+    let mut remote_answered = false;
+    let mut repository_missing = false;
     for refspec in &refspecs {
-        let mut fetch_options = fetch_options_with_progress("fetch_targeted", &remote_url, shallow);
+        let mut fetch_options =
+            fetch_options_with_progress(access, "fetch_targeted", &remote_url, shallow);
         match remote.fetch(&[refspec], Some(&mut fetch_options), None) {
             Ok(()) => {
+                remote_answered = true;
+                if let Err(err) = release_tags::save_advertised_tags(repo, &remote) {
+                    debug!(error = %format!("{err:#}"), "failed to refresh saved tag listing");
+                }
                 if try_resolve_commit_fingerprint_with_alternates(repo, requested_revision)
                     .is_some()
                 {
@@ -261,25 +682,53 @@ fn fetch_targeted_revision(repo: &Repository, requested_revision: &str) -> anyho
                     error = %err,
                     "targeted fetch attempt failed"
                 );
+                repository_missing |= is_missing_repository_error(&err);
                 errors.push(message);
             }
         }
     }
 
+    if repository_missing && !remote_answered {
+        return Err(PkgrepError::RepositoryNotFound { remote_url }.into());
+    }
+    let attempts = errors.join("; ");
+    if remote_answered {
+        return Err(PkgrepError::RevisionNotFound {
+            revision: requested_revision.to_string(),
+            remote_url,
+            attempts,
+        }
+        .into());
+    }
     anyhow::bail!(
         "failed to fetch requested revision '{}' from {} via targeted refspecs [{}]",
         requested_revision,
         remote_url,
-        errors.join("; ")
+        attempts
+    )
+}
+
+fn is_missing_repository_error(err: &git2::Error) -> bool {
+    err.code() == git2::ErrorCode::NotFound
+        || (err.class() == git2::ErrorClass::Http && err.message().contains("404"))
+}
+
+/// Whether an error means the revision or its repository does not exist, as opposed
+/// to the remote being unreachable.
+pub fn is_missing_source(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<PkgrepError>(),
+        Some(PkgrepError::RepositoryNotFound { .. } | PkgrepError::RevisionNotFound { .. })
     )
 }
 
 fn fetch_options_with_progress(
+    access: &GitAccess,
     operation: &'static str,
     git_url: &str,
     shallow: bool,
 ) -> FetchOptions<'static> {
-    let mut callbacks = RemoteCallbacks::new();
+    let mut callbacks = access.remote_callbacks();
     let git_url = git_url.to_string();
     let mut last_reported_percent = 0usize;
     callbacks.transfer_progress(move |stats| {
@@ -317,11 +766,17 @@ fn fetch_options_with_progress(
 }
 
 fn supports_shallow_fetch(remote_url: &str) -> bool {
+    local_remote_path(remote_url).is_none()
+}
+
+fn local_remote_path(remote_url: &str) -> Option<PathBuf> {
+    if let Some(path) = remote_url.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
     let is_local_path = remote_url.starts_with('/')
         || remote_url.starts_with("./")
-        || remote_url.starts_with("../")
-        || remote_url.starts_with("file://");
-    !is_local_path
+        || remote_url.starts_with("../");
+    is_local_path.then(|| PathBuf::from(remote_url))
 }
 
 fn targeted_refspecs(requested_revision: &str) -> Vec<String> {
@@ -423,6 +878,10 @@ fn looks_like_hex_revision(requested_revision: &str) -> bool {
     requested_revision.len() >= 7 && requested_revision.chars().all(|c| c.is_ascii_hexdigit())
 }
 
+fn looks_like_abbreviated_commit(requested_revision: &str) -> bool {
+    looks_like_hex_revision(requested_revision) && requested_revision.len() < 40
+}
+
 fn try_resolve_commit_fingerprint(repo: &Repository, requested_revision: &str) -> Option<String> {
     let object = repo.revparse_single(requested_revision).ok()?;
     let commit = object.peel_to_commit().ok()?;
@@ -458,10 +917,41 @@ fn revision_candidates(requested_revision: &str) -> Vec<String> {
     out
 }
 
+// Checkouts are built outside `sources/` and renamed into place, so an interrupted
+// pull never leaves a half-made checkout behind at its cache key.
+fn checkout_staging_path(cache_root: &Path, cache_key: &str) -> PathBuf {
+    cache_root
+        .join("tmp")
+        .join("checkouts")
+        .join(sha256_hex(cache_key.as_bytes()))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Sparse checkouts hold a different tree than full ones for the same commit, so
+// they get their own cache key. The commit fixes the root files, so only the package
+// directory varies between sparse path sets, and its hash keeps packages apart.
+fn checkout_key_component(source_fingerprint: &str, sparse_subdirectory: Option<&str>) -> String {
+    match sparse_subdirectory {
+        Some(subdirectory) => {
+            let hash = sha256_hex(subdirectory.as_bytes());
+            format!("{source_fingerprint}+sparse-{}", &hash[..12])
+        }
+        None => source_fingerprint.to_string(),
+    }
+}
+
 fn ensure_checkout_exists(
     mirror_repo_path: &Path,
     checkout_path: &Path,
+    staging_path: &Path,
     source_fingerprint: &str,
+    sparse_subdirectory: Option<&str>,
 ) -> anyhow::Result<()> {
     if checkout_path.exists() {
         if checkout_path.is_dir() {
@@ -473,21 +963,31 @@ fn ensure_checkout_exists(
         );
     }
 
-    if let Some(parent) = checkout_path.parent() {
-        fs::create_dir_all(parent).with_context(|| {
+    for dir in [checkout_path.parent(), staging_path.parent()]
+        .into_iter()
+        .flatten()
+    {
+        fs::create_dir_all(dir).with_context(|| {
             format!(
                 "failed to create cache checkout parent directory {}",
-                parent.display()
+                dir.display()
+            )
+        })?;
+    }
+    if staging_path.exists() {
+        fs::remove_dir_all(staging_path).with_context(|| {
+            format!(
+                "failed to remove stale checkout staging directory {}",
+                staging_path.display()
             )
         })?;
     }
 
-    let mirror_repo_url = mirror_repo_path.to_string_lossy().to_string();
-    let checkout_repo = Repository::clone(&mirror_repo_url, checkout_path).with_context(|| {
+    init_checkout_sharing_mirror_objects(mirror_repo_path, staging_path)?;
+    let checkout_repo = Repository::open(staging_path).with_context(|| {
         format!(
-            "failed to create cache checkout from {} at {}",
-            mirror_repo_path.display(),
-            checkout_path.display()
+            "failed to open staged cache checkout at {}",
+            staging_path.display()
         )
     })?;
 
@@ -498,8 +998,17 @@ fn ensure_checkout_exists(
     let object = checkout_repo
         .find_object(oid, None)
         .with_context(|| format!("failed to find OID {source_fingerprint} in checkout repo"))?;
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force();
+    if let Some(subdirectory) = sparse_subdirectory {
+        // Paths are taken literally so names containing `*`, `?`, or `[` are not globs.
+        checkout.disable_pathspec_match(true);
+        for path in sparse_checkout_paths(&object, subdirectory)? {
+            checkout.path(path);
+        }
+    }
     checkout_repo
-        .checkout_tree(&object, Some(CheckoutBuilder::new().force()))
+        .checkout_tree(&object, Some(&mut checkout))
         .with_context(|| {
             format!(
                 "failed to checkout OID {source_fingerprint} into {}",
@@ -509,10 +1018,84 @@ fn ensure_checkout_exists(
     checkout_repo
         .set_head_detached(oid)
         .with_context(|| format!("failed to detach HEAD at OID {source_fingerprint}"))?;
+    drop(object);
+    drop(checkout_repo);
+
+    fs::rename(staging_path, checkout_path).with_context(|| {
+        format!(
+            "failed to move staged checkout {} into {}",
+            staging_path.display(),
+            checkout_path.display()
+        )
+    })?;
 
     Ok(())
 }
 
+/// Alternates file a checkout uses to borrow objects from its mirror.
+pub fn checkout_alternates_path(checkout_path: &Path) -> PathBuf {
+    checkout_path
+        .join(".git")
+        .join("objects")
+        .join("info")
+        .join("alternates")
+}
+
+// Checkouts borrow objects from the bare mirror through `objects/info/alternates`
+// instead of copying history into every version; `cache prune` reads the same file
+// to keep mirrors that live checkouts still depend on.
+fn init_checkout_sharing_mirror_objects(
+    mirror_repo_path: &Path,
+    staging_path: &Path,
+) -> anyhow::Result<()> {
+    Repository::init(staging_path).with_context(|| {
+        format!(
+            "failed to initialize cache checkout repository at {}",
+            staging_path.display()
+        )
+    })?;
+
+    let alternates_path = checkout_alternates_path(staging_path);
+    if let Some(parent) = alternates_path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!(
+                "failed to create object info directory {}",
+                parent.display()
+            )
+        })?;
+    }
+    let mirror_objects_path = mirror_repo_path.join("objects");
+    fs::write(
+        &alternates_path,
+        format!("{}\n", mirror_objects_path.display()),
+    )
+    .with_context(|| {
+        format!(
+            "failed to write object alternates file {}",
+            alternates_path.display()
+        )
+    })?;
+    Ok(())
+}
+
+fn sparse_checkout_paths(
+    object: &git2::Object<'_>,
+    subdirectory: &str,
+) -> anyhow::Result<Vec<String>> {
+    let tree = object
+        .peel_to_tree()
+        .context("failed to read tree for sparse checkout")?;
+    let mut paths = vec![subdirectory.to_string()];
+    for entry in tree.iter() {
+        if entry.kind() == Some(ObjectType::Blob)
+            && let Some(name) = entry.name()
+        {
+            paths.push(name.to_string());
+        }
+    }
+    Ok(paths)
+}
+
 fn ensure_symlink(target: &Path, link: &Path) -> anyhow::Result<()> {
     if let Some(parent) = link.parent() {
         fs::create_dir_all(parent).with_context(|| {
@@ -575,6 +1158,8 @@ fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
 
     #[test]
@@ -645,6 +1230,15 @@ mod tests {
         assert!(!looks_like_hex_revision("HEAD"));
     }
 
+    #[test]
+    fn detects_abbreviated_commit() {
+        assert!(looks_like_abbreviated_commit("abcdef123456"));
+        assert!(!looks_like_abbreviated_commit(
+            "0123456789abcdef0123456789abcdef01234567"
+        ));
+        assert!(!looks_like_abbreviated_commit("v1.8.0"));
+    }
+
     #[test]
     fn shallow_fetch_support_detection() {
         assert!(supports_shallow_fetch(
@@ -658,4 +1252,310 @@ mod tests {
         assert!(!supports_shallow_fetch("../repo"));
         assert!(!supports_shallow_fetch("file:///tmp/repo"));
     }
+
+    fn init_monorepo_fixture(path: &Path) -> String {
+        let package_dir = path.join("packages").join("core");
+        fs::create_dir_all(&package_dir).expect("create package dir");
+        fs::write(path.join("README.md"), "monorepo\n").expect("write root file");
+        fs::write(package_dir.join("index.js"), "module.exports = 1;\n")
+            .expect("write package file");
+        let sibling_dir = path.join("packages").join("other");
+        fs::create_dir_all(&sibling_dir).expect("create sibling package dir");
+        fs::write(sibling_dir.join("index.js"), "module.exports = 2;\n")
+            .expect("write sibling package file");
+
+        let repo = Repository::init(path).expect("init repo");
+        let mut index = repo.index().expect("index");
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .expect("add files");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let sig =
+            git2::Signature::now("pkgrep-test", "pkgrep-test@example.com").expect("signature");
+        repo.commit(Some("HEAD"), &sig, &sig, "initial commit", &tree, &[])
+            .expect("commit")
+            .to_string()
+    }
+
+    fn monorepo_target(repo_path: &Path, revision: &str, subdirectory: &str) -> GitPullTarget {
+        GitPullTarget {
+            ecosystem: Ecosystem::Npm,
+            locator: String::from("@scope/core"),
+            git_url: repo_path.display().to_string(),
+            requested_revision: revision.to_string(),
+            subdirectory: Some(subdirectory.to_string()),
+            release_tag_package: None,
+        }
+    }
+
+    fn test_config(temp: &tempfile::TempDir) -> Config {
+        Config {
+            cache_dir: temp.path().join("cache"),
+            worker_pool_size: 1,
+            lock_timeout_secs: 5,
+            checkout_strategy: CheckoutStrategy::Full,
+            tag_formats: BTreeMap::new(),
+            git_ssh_keys: Vec::new(),
+            git_url_rewrites: BTreeMap::new(),
+            offline: false,
+        }
+    }
+
+    #[test]
+    fn reuses_unpacked_archive_for_package_version_without_downloading() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let config = test_config(&temp);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+        let target = ArchivePullTarget {
+            ecosystem: Ecosystem::Npm,
+            package_name: String::from("left-pad"),
+            package_version: String::from("1.3.0"),
+        };
+        let unpacked = config.cache_dir.join("sources").join(depspec::cache_key(
+            &target.ecosystem,
+            &target.package_name,
+            &target.package_version,
+            "archive-sha256-abc",
+        ));
+        fs::create_dir_all(&unpacked).expect("create unpacked archive");
+        fs::write(unpacked.join("index.js"), "module.exports = leftPad;\n").expect("write");
+
+        let materialized =
+            materialize_archive_source(&project, &config, &target).expect("materialize");
+
+        assert!(!materialized.archive_download_performed);
+        assert_eq!(materialized.source_fingerprint, "archive-sha256-abc");
+        assert_eq!(
+            materialized.project_link_path,
+            project.join(".pkgrep/deps/npm/left-pad@1.3.0")
+        );
+        assert!(materialized.project_link_path.join("index.js").is_file());
+        assert!(is_archive_checkout(&materialized.checkout_path));
+    }
+
+    #[test]
+    fn sparse_checkout_keeps_package_and_root_files_under_separate_cache_key() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        let revision = init_monorepo_fixture(&repo_path);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+        let target = monorepo_target(&repo_path, &revision, "packages/core");
+
+        let full = materialize_git_source(&project, &test_config(&temp), &target)
+            .expect("materialize full");
+        let sparse_config = Config {
+            checkout_strategy: CheckoutStrategy::Sparse,
+            ..test_config(&temp)
+        };
+        let sparse =
+            materialize_git_source(&project, &sparse_config, &target).expect("materialize sparse");
+
+        assert_ne!(full.cache_key, sparse.cache_key);
+        assert!(sparse.cache_key.contains(&format!("{revision}+sparse-")));
+        assert!(full.checkout_path.join("packages/other/index.js").exists());
+        assert!(sparse.checkout_path.join("README.md").exists());
+        assert!(sparse.checkout_path.join("packages/core/index.js").exists());
+        assert!(!sparse.checkout_path.join("packages/other").exists());
+        assert_eq!(
+            fs::read_link(&sparse.project_link_path).expect("read link"),
+            sparse.checkout_path.join("packages/core")
+        );
+    }
+
+    #[test]
+    fn sparse_checkouts_of_sibling_packages_do_not_share_a_cache_key() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        fs::create_dir_all(repo_path.join("packages").join("other")).expect("create package dir");
+        fs::write(repo_path.join("packages/other/notes.md"), "notes\n").expect("write notes");
+        fs::write(repo_path.join("*.md"), "glob-like root file\n").expect("write root file");
+        let revision = init_monorepo_fixture(&repo_path);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+        let config = Config {
+            checkout_strategy: CheckoutStrategy::Sparse,
+            ..test_config(&temp)
+        };
+
+        let core = materialize_git_source(
+            &project,
+            &config,
+            &monorepo_target(&repo_path, &revision, "packages/core"),
+        )
+        .expect("materialize core");
+        let other = materialize_git_source(
+            &project,
+            &config,
+            &monorepo_target(&repo_path, &revision, "packages/other"),
+        )
+        .expect("materialize other");
+
+        assert_ne!(core.cache_key, other.cache_key);
+        assert!(core.checkout_path.join("*.md").exists());
+        assert!(core.checkout_path.join("packages/core/index.js").exists());
+        assert!(!core.checkout_path.join("packages/other").exists());
+        assert!(other.checkout_path.join("packages/other/index.js").exists());
+        assert!(other.checkout_path.join("packages/other/notes.md").exists());
+        assert!(!other.checkout_path.join("packages/core").exists());
+    }
+
+    #[test]
+    fn maps_version_to_package_prefixed_release_tag() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        let first_commit = init_monorepo_fixture(&repo_path);
+        let repo = Repository::open(&repo_path).expect("open fixture");
+        let first = repo
+            .find_object(Oid::from_str(&first_commit).expect("oid"), None)
+            .expect("find first commit");
+        repo.tag_lightweight("v1.2.3", &first, false)
+            .expect("tag plain version");
+        let sig =
+            git2::Signature::now("pkgrep-test", "pkgrep-test@example.com").expect("signature");
+        let tree = repo
+            .find_commit(first.id())
+            .expect("first commit")
+            .tree()
+            .expect("tree");
+        let package_commit = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "release core",
+                &tree,
+                &[&repo.find_commit(first.id()).expect("parent")],
+            )
+            .expect("commit");
+        let package_object = repo.find_object(package_commit, None).expect("object");
+        repo.tag(
+            "@scope/core@1.2.3",
+            &package_object,
+            &sig,
+            "core 1.2.3",
+            false,
+        )
+        .expect("tag package version");
+
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+        let target = GitPullTarget {
+            release_tag_package: Some(String::from("@scope/core")),
+            ..monorepo_target(&repo_path, "1.2.3", "packages/core")
+        };
+        let materialized =
+            materialize_git_source(&project, &test_config(&temp), &target).expect("materialize");
+
+        assert_eq!(materialized.source_fingerprint, package_commit.to_string());
+        assert_eq!(
+            materialized.release_tag,
+            Some(ReleaseTag {
+                name: String::from("@scope/core@1.2.3"),
+                convention: String::from("{name}@{version}"),
+            })
+        );
+
+        let config = Config {
+            tag_formats: BTreeMap::from([(
+                String::from("npm:@scope/core"),
+                String::from("v{version}"),
+            )]),
+            ..test_config(&temp)
+        };
+        let overridden =
+            materialize_git_source(&project, &config, &target).expect("materialize override");
+        assert_eq!(overridden.source_fingerprint, first_commit);
+        assert_eq!(
+            overridden.release_tag.map(|tag| tag.convention),
+            Some(String::from("v{version}"))
+        );
+    }
+
+    #[test]
+    fn links_package_subdirectory_inside_monorepo_checkout() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        let revision = init_monorepo_fixture(&repo_path);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+
+        let target = monorepo_target(&repo_path, &revision, "packages/core");
+        let materialized =
+            materialize_git_source(&project, &test_config(&temp), &target).expect("materialize");
+
+        assert_eq!(
+            materialized.linked_subdirectory.as_deref(),
+            Some("packages/core")
+        );
+        assert_eq!(
+            fs::read_link(&materialized.project_link_path).expect("read link"),
+            materialized.checkout_path.join("packages/core")
+        );
+        assert!(materialized.project_link_path.join("index.js").exists());
+    }
+
+    #[test]
+    fn checkouts_borrow_objects_from_mirror_through_alternates() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        let revision = init_monorepo_fixture(&repo_path);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+
+        let target = monorepo_target(&repo_path, &revision, "packages/core");
+        let materialized =
+            materialize_git_source(&project, &test_config(&temp), &target).expect("materialize");
+
+        let alternates = fs::read_to_string(checkout_alternates_path(&materialized.checkout_path))
+            .expect("read alternates");
+        let mirror_objects =
+            mirror_repo_path(&temp.path().join("cache"), &Ecosystem::Npm, &target.git_url)
+                .join("objects");
+        assert_eq!(alternates.trim(), mirror_objects.display().to_string());
+
+        let checkout_objects = materialized.checkout_path.join(".git").join("objects");
+        let copied_objects = fs::read_dir(&checkout_objects)
+            .expect("read checkout objects")
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                name != "info" && name != "pack"
+            })
+            .count();
+        assert_eq!(copied_objects, 0, "checkout should not copy loose objects");
+
+        let checkout_repo = Repository::open(&materialized.checkout_path).expect("open checkout");
+        assert_eq!(
+            checkout_repo
+                .head()
+                .expect("head")
+                .target()
+                .map(|oid| oid.to_string()),
+            Some(revision)
+        );
+    }
+
+    #[test]
+    fn links_checkout_root_when_subdirectory_is_missing() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let repo_path = temp.path().join("monorepo");
+        let revision = init_monorepo_fixture(&repo_path);
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).expect("create project");
+
+        let target = monorepo_target(&repo_path, &revision, "packages/missing");
+        let materialized =
+            materialize_git_source(&project, &test_config(&temp), &target).expect("materialize");
+
+        assert_eq!(materialized.linked_subdirectory, None);
+        assert_eq!(
+            fs::read_link(&materialized.project_link_path).expect("read link"),
+            materialized.checkout_path
+        );
+    }
 }
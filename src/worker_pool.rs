@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError, mpsc};
+use std::thread;
+
+/// Runs `job` over `items` on at most `pool_size` scoped worker threads.
+///
+/// Results are handed to `on_complete` on the calling thread in completion order.
+/// Returning `false` from `on_complete` stops workers from picking up further items;
+/// items that are already in flight still complete and are reported.
+pub fn run_bounded<T, R>(
+    items: Vec<T>,
+    pool_size: usize,
+    job: impl Fn(T) -> R + Sync,
+    mut on_complete: impl FnMut(R) -> bool,
+) where
+    T: Send,
+    R: Send,
+{
+    if items.is_empty() {
+        return;
+    }
+
+    let worker_count = pool_size.clamp(1, items.len());
+    let queue = Mutex::new(VecDeque::from(items));
+    // A rendezvous channel keeps workers from running ahead of `on_complete`, so
+    // stopping early leaves at most one extra item in flight per worker.
+    let (sender, receiver) = mpsc::sync_channel(0);
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let sender = sender.clone();
+            let queue = &queue;
+            let job = &job;
+            scope.spawn(move || {
+                loop {
+                    let next = queue
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .pop_front();
+                    let Some(item) = next else {
+                        break;
+                    };
+                    if sender.send(job(item)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        for result in receiver {
+            if !on_complete(result) {
+                queue.lock().unwrap_or_else(PoisonError::into_inner).clear();
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn reports_every_item() {
+        let mut results = Vec::new();
+        run_bounded(
+            (0..32).collect(),
+            4,
+            |item: usize| item * 2,
+            |result| {
+                results.push(result);
+                true
+            },
+        );
+
+        results.sort_unstable();
+        assert_eq!(results, (0..32).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn never_exceeds_pool_size() {
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        run_bounded(
+            (0..16).collect(),
+            3,
+            |_item: usize| {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(10));
+                active.fetch_sub(1, Ordering::SeqCst);
+            },
+            |()| true,
+        );
+
+        let peak = peak.load(Ordering::SeqCst);
+        assert!(peak <= 3, "peak concurrency {peak} exceeded pool size");
+        assert!(peak >= 1);
+    }
+
+    #[test]
+    fn stops_handing_out_items_after_on_complete_returns_false() {
+        let mut completed = 0usize;
+        run_bounded(
+            (0..64).collect(),
+            1,
+            |item: usize| item,
+            |_result| {
+                completed += 1;
+                false
+            },
+        );
+
+        assert!(completed <= 2, "expected early stop, completed {completed}");
+    }
+}
@@ -125,6 +125,38 @@ fn init_local_git_repo(path: &Path) -> String {
     oid.to_string()
 }
 
+fn commit_fixture_file(path: &Path, file_name: &str, contents: &str) -> String {
+    let repo = Repository::open(path).expect("open repo");
+    std::fs::write(path.join(file_name), contents).expect("write fixture file");
+
+    let mut index = repo.index().expect("index");
+    index
+        .add_path(Path::new(file_name))
+        .expect("add path to index");
+    index.write().expect("write index");
+
+    let tree_id = index.write_tree().expect("write tree");
+    let tree = repo.find_tree(tree_id).expect("find tree");
+    let parent = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .expect("head commit");
+    let sig = git2::Signature::now("pkgrep-test", "pkgrep-test@example.com").expect("signature");
+
+    let oid = repo
+        .commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "fixture commit",
+            &tree,
+            &[&parent],
+        )
+        .expect("commit");
+
+    oid.to_string()
+}
+
 fn first_symlink_entry(path: &Path) -> PathBuf {
     let mut entries = Vec::new();
     collect_symlink_entries(path, &mut entries);
@@ -366,6 +398,48 @@ fn second_verbose_pull_uses_local_mirror_without_refetch() {
         .stdout(predicate::str::contains("fetching targeted revision from origin").not());
 }
 
+#[test]
+fn pull_fails_clearly_when_mirror_lock_is_held_by_another_process() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+    let dep_spec = format!("git:{}@{}", repo_path.display(), revision);
+
+    cmd_in_temp(&temp)
+        .args(["pull", &dep_spec])
+        .assert()
+        .success();
+
+    let mirror_locks_dir = configured_cache_dir(&temp)
+        .join("locks")
+        .join("repos")
+        .join("git");
+    let mirror_lock_path = std::fs::read_dir(&mirror_locks_dir)
+        .expect("read mirror locks dir")
+        .map(|entry| entry.expect("mirror lock entry").path())
+        .find(|path| path.to_string_lossy().ends_with(".git.lock"))
+        .expect("mirror lock file");
+    let held_lock = std::fs::File::open(&mirror_lock_path).expect("open mirror lock");
+    held_lock.lock().expect("hold mirror lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_LOCK_TIMEOUT_SECS", "1")
+        .args(["pull", &dep_spec])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "timed out after 1s waiting for the git mirror lock",
+        ))
+        .stderr(predicate::str::contains("PKGREP_LOCK_TIMEOUT_SECS"));
+
+    drop(held_lock);
+    cmd_in_temp(&temp)
+        .env("PKGREP_LOCK_TIMEOUT_SECS", "1")
+        .args(["pull", &dep_spec])
+        .assert()
+        .success();
+}
+
 #[test]
 fn invalid_dep_spec_fails_fast() {
     let temp = TempDir::new().expect("tempdir");
@@ -686,6 +760,96 @@ fn pull_without_specs_with_non_git_lockfile_entries_is_noop() {
         .stdout(predicate::str::contains("none had git source hints"));
 }
 
+#[test]
+fn pull_without_specs_resolve_registry_reports_failed_registry_entries() {
+    let temp = TempDir::new().expect("tempdir");
+    let package_lock = json!({
+        "name": "fixture-js-npm",
+        "version": "1.0.0",
+        "lockfileVersion": 3,
+        "packages": {
+            "": {
+                "name": "fixture-js-npm",
+                "version": "1.0.0",
+                "dependencies": {
+                    "react": "^18.3.1"
+                }
+            },
+            "node_modules/react": {
+                "version": "18.3.1",
+                "resolved": "https://registry.npmjs.org/react/-/react-18.3.1.tgz"
+            }
+        }
+    });
+    std::fs::write(
+        temp.path().join("package-lock.json"),
+        serde_json::to_vec_pretty(&package_lock).expect("serialize package lock"),
+    )
+    .expect("write package-lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_NPM_REGISTRY_URL", "not-a-url")
+        .args(["pull", "--resolve-registry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "resolving package metadata for npm:react@18.3.1",
+        ))
+        .stdout(predicate::str::contains("npm:react@^18.3.1").not())
+        .stdout(predicate::str::contains(
+            "failed npm:react@18.3.1: invalid npm registry URL",
+        ))
+        .stdout(predicate::str::contains(
+            "Registry resolution: resolved=0 fell_back=0 failed=1",
+        ))
+        .stdout(predicate::str::contains(
+            "none could be resolved to a git source",
+        ));
+}
+
+#[test]
+fn pull_without_specs_resolve_registry_still_pulls_git_hinted_entries() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+
+    let package_lock = json!({
+        "name": "fixture-js-npm",
+        "version": "1.0.0",
+        "lockfileVersion": 3,
+        "packages": {
+            "node_modules/demo-git-package": {
+                "version": "1.0.0",
+                "resolved": format!("git+{}#{}", repo_path.display(), revision),
+            },
+            "node_modules/react": {
+                "version": "18.3.1",
+                "resolved": "https://registry.npmjs.org/react/-/react-18.3.1.tgz"
+            }
+        }
+    });
+    std::fs::write(
+        temp.path().join("package-lock.json"),
+        serde_json::to_vec_pretty(&package_lock).expect("serialize lock"),
+    )
+    .expect("write package-lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_NPM_REGISTRY_URL", "not-a-url")
+        .args(["pull", "--resolve-registry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("failed npm:react@18.3.1"))
+        .stdout(predicate::str::contains(
+            "Registry resolution: resolved=0 fell_back=0 failed=1",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let npm_links = temp.path().join(".pkgrep").join("deps").join("npm");
+    let link = first_symlink_entry(&npm_links);
+    assert!(link.to_string_lossy().contains("source-repo@"));
+}
+
 #[test]
 fn pull_with_explicit_git_spec_materializes_and_links() {
     let temp = TempDir::new().expect("tempdir");
@@ -715,6 +879,34 @@ fn pull_with_explicit_git_spec_materializes_and_links() {
     assert!(target.join("README.md").exists());
 }
 
+#[test]
+fn pull_git_spec_fetches_through_configured_url_rewrite() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("internal").join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://gitlab.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    let dep_spec = format!("git:https://gitlab.internal.example/source-repo@{revision}");
+    cmd_in_temp(&temp)
+        .env_remove("PKGREP_GIT_TOKEN")
+        .args(["pull", &dep_spec])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("git"));
+    assert!(link.to_string_lossy().contains("gitlab.internal.example"));
+    let target = std::fs::read_link(&link).expect("read link");
+    assert!(target.join("README.md").exists());
+}
+
 #[test]
 fn pull_with_git_spec_without_revision_resolves_default_branch_head() {
     let temp = TempDir::new().expect("tempdir");
@@ -760,107 +952,1541 @@ fn pull_with_git_spec_without_revision_resolves_default_branch_head() {
 }
 
 #[test]
-fn path_returns_link_when_present() {
+fn pull_runs_targets_in_parallel_and_shares_mirrors_between_revisions() {
     let temp = TempDir::new().expect("tempdir");
-    let repo_path = temp.path().join("source-repo");
-    let revision = init_local_git_repo(&repo_path);
-    let dep_spec = format!("git:{}@{}", repo_path.display(), revision);
+    let first_repo_path = temp.path().join("first-repo");
+    let first_revision = init_local_git_repo(&first_repo_path);
+    let second_revision = commit_fixture_file(&first_repo_path, "CHANGES.md", "second\n");
+    let other_repo_path = temp.path().join("other-repo");
+    let other_revision = init_local_git_repo(&other_repo_path);
 
     cmd_in_temp(&temp)
-        .args(["pull", &dep_spec])
+        .env("PKGREP_WORKER_POOL_SIZE", "3")
+        .args([
+            "pull",
+            &format!("git:{}@{}", first_repo_path.display(), first_revision),
+            &format!("git:{}@{}", first_repo_path.display(), second_revision),
+            &format!("git:{}@{}", other_repo_path.display(), other_revision),
+        ])
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains("[3/3] pull"))
+        .stdout(predicate::str::contains("Pull completed: total=3"));
 
-    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("git"));
-    let link_display = link.display().to_string();
+    let cache_dir = configured_cache_dir(&temp);
+    assert_eq!(count_cached_mirrors(&cache_dir), 2);
+    assert_eq!(count_cached_checkouts(&cache_dir), 3);
 
-    cmd_in_temp(&temp)
-        .args(["path", &dep_spec])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains(&link_display));
+    let manifest = read_json(&temp.path().join(".pkgrep").join("manifest.json"));
+    let entries = manifest
+        .get("entries")
+        .and_then(Value::as_object)
+        .expect("manifest entries");
+    assert_eq!(entries.len(), 3);
 }
 
 #[test]
-fn path_returns_link_for_bare_git_spec_when_single_match() {
+fn pull_links_monorepo_packages_at_their_subdirectory() {
     let temp = TempDir::new().expect("tempdir");
-    let repo_path = temp.path().join("source-repo");
+    let repo_path = temp.path().join("internal").join("toolkit.git");
     init_local_git_repo(&repo_path);
-    let dep_spec = format!("git:{}", repo_path.display());
-
-    cmd_in_temp(&temp)
-        .args(["pull", &dep_spec])
+    let mut revision = String::new();
+    for package in ["core", "util"] {
+        std::fs::create_dir_all(repo_path.join("packages").join(package))
+            .expect("create package dir");
+        revision = commit_fixture_file(
+            &repo_path,
+            &format!("packages/{package}/index.js"),
+            &format!("export const {package} = true;\n"),
+        );
+    }
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    let registry_dir = temp.path().join("registry");
+    std::fs::create_dir_all(&registry_dir).expect("create registry dir");
+    for package in ["core", "util"] {
+        let metadata = json!({
+            "name": format!("toolkit-{package}"),
+            "dist-tags": {"latest": "1.0.0"},
+            "versions": {"1.0.0": {
+                "repository": {
+                    "type": "git",
+                    "url": "https://git.internal.example/toolkit.git",
+                    "directory": format!("packages/{package}")
+                },
+                "gitHead": revision
+            }}
+        });
+        std::fs::write(
+            registry_dir.join(format!("toolkit-{package}")),
+            serde_json::to_vec(&metadata).expect("serialize metadata"),
+        )
+        .expect("write registry metadata");
+    }
+    let pkgrep = |args: &[&str]| {
+        let mut cmd = cmd_in_temp(&temp);
+        cmd.env("PKGREP_NPM_REGISTRY_URL", file_url(&registry_dir))
+            .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+            .args(args);
+        cmd
+    };
+
+    pkgrep(&["pull", "npm:toolkit-core@1.0.0", "npm:toolkit-util@1.0.0"])
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=2"));
 
-    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("git"));
-    let expected = link.display().to_string();
-    cmd_in_temp(&temp)
-        .args(["path", &dep_spec])
+    let core_link = temp
+        .path()
+        .join(format!(".pkgrep/deps/npm/toolkit-core@{revision}"));
+    assert!(core_link.join("index.js").exists());
+    assert!(!core_link.join("README.md").exists());
+
+    let core_key =
+        format!("git:https://git.internal.example/toolkit.git@{revision}//packages/core");
+    let manifest = read_json(&temp.path().join(".pkgrep").join("manifest.json"));
+    assert_eq!(
+        manifest["entries"][&core_key]["subdirectory"],
+        json!("packages/core")
+    );
+
+    pkgrep(&["path", &core_key])
         .assert()
         .success()
-        .stdout(predicate::str::contains(expected));
+        .stdout(predicate::str::contains(format!("toolkit-core@{revision}")));
+    pkgrep(&["remove", &core_key, "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("removed=1"));
+    assert!(!core_link.exists());
+    assert!(
+        temp.path()
+            .join(format!(".pkgrep/deps/npm/toolkit-util@{revision}/index.js"))
+            .exists()
+    );
+
+    let git_spec = format!("git:{}@{revision}//packages/core", repo_path.display());
+    pkgrep(&["pull", &git_spec]).assert().success();
+    let output = pkgrep(&["path", &git_spec]).output().expect("run path");
+    assert!(output.status.success());
+    let git_link = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert!(git_link.ends_with(format!("toolkit.git/packages/core@{revision}")));
+    assert!(git_link.join("index.js").exists());
 }
 
 #[test]
-fn path_fails_when_dep_is_not_linked() {
+fn pull_falls_back_to_the_published_tarball_only_when_the_tag_is_missing() {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
     let temp = TempDir::new().expect("tempdir");
-    cmd_in_temp(&temp)
-        .args(["path", "git:https://github.com/facebook/react.git@v18.3.1"])
+    let repo_path = temp.path().join("internal").join("left-pad.git");
+    init_local_git_repo(&repo_path);
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    let source = b"module.exports = leftPad;\n";
+    let mut tarball = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(source.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tarball
+        .append_data(&mut header, "package/index.js", &source[..])
+        .expect("append tarball entry");
+    let tarball = tarball
+        .into_inner()
+        .expect("finish tarball")
+        .finish()
+        .expect("finish gzip");
+    let tarball_path = temp.path().join("left-pad-1.0.0.tgz");
+    std::fs::write(&tarball_path, tarball).expect("write tarball");
+
+    let registry_dir = temp.path().join("registry");
+    std::fs::create_dir_all(&registry_dir).expect("create registry dir");
+    let write_metadata = |repository_url: String| {
+        let metadata = json!({
+            "name": "left-pad",
+            "dist-tags": {"latest": "1.0.0"},
+            "versions": {"1.0.0": {
+                "repository": {"type": "git", "url": repository_url},
+                "dist": {"tarball": file_url(&tarball_path)}
+            }}
+        });
+        std::fs::write(
+            registry_dir.join("left-pad"),
+            serde_json::to_vec(&metadata).expect("serialize metadata"),
+        )
+        .expect("write registry metadata");
+    };
+    let pkgrep = |args: &[&str]| {
+        let mut cmd = cmd_in_temp(&temp);
+        cmd.env("PKGREP_NPM_REGISTRY_URL", file_url(&registry_dir))
+            .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+            .args(args);
+        cmd
+    };
+
+    // An unreachable remote is a transient failure, not a missing tag.
+    write_metadata("http://127.0.0.1:1/left-pad.git".to_string());
+    pkgrep(&["pull", "npm:left-pad@1.0.0"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "dependency is not linked in this project",
-        ));
+        .stdout(predicate::str::contains("published package archive").not());
+    assert!(!temp.path().join(".pkgrep/deps/npm/left-pad@1.0.0").exists());
+
+    // The repository exists but has no tag for 1.0.0.
+    write_metadata("https://git.internal.example/left-pad.git".to_string());
+    pkgrep(&["pull", "npm:left-pad@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "using the published package archive instead",
+        ))
+        .stdout(predicate::str::contains(
+            "archive npm:left-pad@1.0.0 -> npm:left-pad@1.0.0",
+        ))
+        .stdout(predicate::str::contains("from_archive=1"));
+
+    let link = temp.path().join(".pkgrep/deps/npm/left-pad@1.0.0");
+    assert_eq!(
+        std::fs::read_to_string(link.join("index.js")).expect("read unpacked file"),
+        "module.exports = leftPad;\n"
+    );
 }
 
 #[test]
-fn path_returns_registry_link_when_present() {
+fn pull_maps_versions_to_package_prefixed_tags_and_refreshes_the_tag_listing() {
     let temp = TempDir::new().expect("tempdir");
-    let link_relative = ".pkgrep/deps/npm/react@deadbeef";
-    let link_path = temp.path().join(link_relative);
-    std::fs::create_dir_all(&link_path).expect("create linked path");
-
-    let manifest_path = temp.path().join(".pkgrep").join("manifest.json");
-    std::fs::create_dir_all(
-        manifest_path
-            .parent()
-            .expect("manifest parent directory should exist"),
+    let repo_path = temp.path().join("internal").join("widgets.git");
+    init_local_git_repo(&repo_path);
+    let tag_head = |tag: &str| {
+        let repo = Repository::open(&repo_path).expect("open repo");
+        let head = repo.head().expect("head").peel_to_commit().expect("commit");
+        repo.tag_lightweight(tag, head.as_object(), false)
+            .expect("create tag");
+    };
+    commit_fixture_file(&repo_path, "index.js", "export const release = 'plain';\n");
+    tag_head("v1.1.0");
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
     )
-    .expect("create manifest parent");
-    let manifest = json!({
-        "schema_version": 1,
-        "entries": {
-            "git:https://github.com/facebook/react.git@deadbeef": {
-                "link_path": link_relative,
-                "cache_key": "npm/b64_cmVhY3Q/deadbeef/fingerprint",
-                "aliases": ["npm:react", "npm:react@18.3.1"],
-                "registry_refs": [{
-                    "ecosystem": "npm",
-                    "name": "react",
-                    "package_version": "18.3.1"
-                }]
-            }
-        }
+    .expect("write project config");
+
+    let registry_dir = temp.path().join("registry");
+    std::fs::create_dir_all(&registry_dir).expect("create registry dir");
+    let metadata = json!({
+        "name": "widgets",
+        "dist-tags": {"latest": "1.1.0"},
+        "versions": {"1.1.0": {
+            "repository": {"type": "git", "url": "https://git.internal.example/widgets.git"}
+        }}
     });
     std::fs::write(
-        &manifest_path,
-        serde_json::to_vec_pretty(&manifest).expect("serialize manifest"),
+        registry_dir.join("widgets"),
+        serde_json::to_vec(&metadata).expect("serialize metadata"),
     )
-    .expect("write manifest");
+    .expect("write registry metadata");
+    let pull = || {
+        let mut cmd = cmd_in_temp(&temp);
+        cmd.env("PKGREP_NPM_REGISTRY_URL", file_url(&registry_dir))
+            .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+            .args(["pull", "npm:widgets@1.1.0"]);
+        cmd
+    };
+    let recorded_tag = || {
+        let manifest = read_json(&temp.path().join(".pkgrep").join("manifest.json"));
+        let entries = manifest["entries"].as_object().expect("manifest entries");
+        assert_eq!(entries.len(), 1);
+        entries.values().next().expect("entry")["release_tag"]["name"].clone()
+    };
+
+    pull().assert().success();
+    assert_eq!(recorded_tag(), json!("v1.1.0"));
+
+    // The saved listing only holds the lower-ranked `v1.1.0`, so the package tag
+    // pushed afterwards is picked up instead of reusing it.
+    commit_fixture_file(
+        &repo_path,
+        "index.js",
+        "export const release = 'widgets';\n",
+    );
+    tag_head("widgets@1.1.0");
+    pull().assert().success();
+    assert_eq!(recorded_tag(), json!("widgets@1.1.0"));
+    let link = temp.path().join(".pkgrep/deps/npm/widgets@1.1.0");
+    assert_eq!(
+        std::fs::read_to_string(link.join("index.js")).expect("read linked file"),
+        "export const release = 'widgets';\n"
+    );
+}
+
+fn write_go_proxy_file(proxy_dir: &Path, module_path: &str, file_name: &str, contents: &[u8]) {
+    let version_dir = proxy_dir.join(module_path).join("@v");
+    std::fs::create_dir_all(&version_dir).expect("create go proxy dir");
+    std::fs::write(version_dir.join(file_name), contents).expect("write go proxy file");
+}
+
+fn file_url(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+#[test]
+fn pull_go_module_pins_the_proxy_origin_commit() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+    let proxy_dir = temp.path().join("goproxy");
+    let info = json!({
+        "Version": "v1.0.0",
+        "Time": "2024-01-01T00:00:00Z",
+        "Origin": {"VCS": "git", "URL": repo_path.display().to_string(), "Hash": commit}
+    });
+    write_go_proxy_file(
+        &proxy_dir,
+        "example.com/demo",
+        "v1.0.0.info",
+        &serde_json::to_vec(&info).expect("serialize info"),
+    );
+    std::fs::write(
+        temp.path().join("go.mod"),
+        "module example.com/app\n\nrequire example.com/demo v1.0.0\n",
+    )
+    .expect("write go.mod");
 
-    let expected = link_path.display().to_string();
     cmd_in_temp(&temp)
-        .args(["path", "npm:react@18.3.1"])
+        .env_remove("PKGREP_GOPROXY")
+        .env("GOPROXY", file_url(&proxy_dir))
+        .args(["pull", "go:example.com/demo"])
         .assert()
         .success()
-        .stdout(predicate::str::contains(expected));
+        .stdout(predicate::str::contains(
+            "detected installed go version for example.com/demo: v1.0.0 (from go.mod)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp
+        .path()
+        .join(".pkgrep/deps/go/example.com")
+        .join(format!("demo@{commit}"));
+    assert!(link.join("README.md").exists());
 }
 
 #[test]
-fn list_reports_linked_dependencies() {
+fn pull_go_module_falls_back_to_the_proxy_zip_when_git_is_unavailable() {
+    use std::io::Write;
+
     let temp = TempDir::new().expect("tempdir");
-    let link_relative = ".pkgrep/deps/npm/react@deadbeef";
+    let proxy_dir = temp.path().join("goproxy");
+    let info = json!({
+        "Version": "v1.0.0",
+        "Origin": {
+            "VCS": "git",
+            "URL": temp.path().join("missing-repo").display().to_string(),
+            "Hash": "0123456789abcdef0123456789abcdef01234567"
+        }
+    });
+    write_go_proxy_file(
+        &proxy_dir,
+        "example.com/demo",
+        "v1.0.0.info",
+        &serde_json::to_vec(&info).expect("serialize info"),
+    );
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .start_file(
+            "example.com/demo@v1.0.0/demo.go",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .expect("start zip entry");
+    writer
+        .write_all(b"package demo\n")
+        .expect("write zip entry");
+    let module_zip = writer.finish().expect("finish zip").into_inner();
+    write_go_proxy_file(&proxy_dir, "example.com/demo", "v1.0.0.zip", &module_zip);
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_GOPROXY", file_url(&proxy_dir))
+        .args(["pull", "go:example.com/demo@v1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/go/example.com/demo@v1.0.0");
+    assert_eq!(
+        std::fs::read_to_string(link.join("demo.go")).expect("read unpacked module file"),
+        "package demo\n"
+    );
+}
+
+#[test]
+fn pull_rubygems_shorthand_uses_the_gemfile_lock_version() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+    let repo = Repository::open(&repo_path).expect("open repo");
+    let target = repo
+        .find_object(git2::Oid::from_str(&commit).expect("oid"), None)
+        .expect("find commit");
+    repo.tag_lightweight("v1.0.0", &target, false)
+        .expect("tag release");
+
+    let registry_dir = temp.path().join("rubygems");
+    let versions_dir = registry_dir.join("api/v2/rubygems/demo/versions");
+    std::fs::create_dir_all(&versions_dir).expect("create rubygems registry dir");
+    let info = json!({
+        "name": "demo",
+        "version": "1.0.0",
+        "source_code_uri": file_url(&repo_path),
+        "homepage_uri": "https://example.com/demo"
+    });
+    std::fs::write(
+        versions_dir.join("1.0.0.json"),
+        serde_json::to_vec(&info).expect("serialize gem info"),
+    )
+    .expect("write gem info");
+    std::fs::write(
+        temp.path().join("Gemfile.lock"),
+        "GEM\n  remote: https://rubygems.org/\n  specs:\n    demo (1.0.0)\n\nDEPENDENCIES\n  demo\n",
+    )
+    .expect("write Gemfile.lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_RUBYGEMS_REGISTRY_URL", file_url(&registry_dir))
+        .args(["pull", "demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "inferred shorthand 'demo' as 'rubygems:demo'",
+        ))
+        .stdout(predicate::str::contains(
+            "detected installed rubygems version for demo: 1.0.0 (from Gemfile.lock)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/rubygems/demo@1.0.0");
+    assert!(link.join("README.md").exists());
+}
+
+fn write_maven_repository_file(
+    repository_dir: &Path,
+    group_id: &str,
+    artifact_id: &str,
+    version: &str,
+    file_name: &str,
+    contents: &[u8],
+) {
+    let version_dir = repository_dir
+        .join(group_id.replace('.', "/"))
+        .join(artifact_id)
+        .join(version);
+    std::fs::create_dir_all(&version_dir).expect("create maven repository dir");
+    std::fs::write(version_dir.join(file_name), contents).expect("write maven repository file");
+}
+
+#[test]
+fn pull_maven_artifact_follows_the_parent_pom_scm() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+    let repo = Repository::open(&repo_path).expect("open repo");
+    let target = repo
+        .find_object(git2::Oid::from_str(&commit).expect("oid"), None)
+        .expect("find commit");
+    repo.tag_lightweight("v1.0.0", &target, false)
+        .expect("tag release");
+
+    let repository_dir = temp.path().join("m2");
+    let parent_pom = format!(
+        "<project><groupId>com.example</groupId><artifactId>demo-parent</artifactId><version>1.0.0</version><scm><connection>scm:git:{}</connection></scm></project>",
+        file_url(&repo_path)
+    );
+    write_maven_repository_file(
+        &repository_dir,
+        "com.example",
+        "demo-parent",
+        "1.0.0",
+        "demo-parent-1.0.0.pom",
+        parent_pom.as_bytes(),
+    );
+    write_maven_repository_file(
+        &repository_dir,
+        "com.example",
+        "demo",
+        "1.0.0",
+        "demo-1.0.0.pom",
+        b"<project><parent><groupId>com.example</groupId><artifactId>demo-parent</artifactId><version>1.0.0</version></parent><artifactId>demo</artifactId></project>",
+    );
+    std::fs::write(
+        temp.path().join("gradle.lockfile"),
+        "com.example:demo:1.0.0=compileClasspath\nempty=annotationProcessor\n",
+    )
+    .expect("write gradle.lockfile");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_MAVEN_REPOSITORY_URL", &repository_dir)
+        .args(["pull", "maven:com.example:demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "detected installed maven version for com.example:demo: 1.0.0 (from gradle.lockfile)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp
+        .path()
+        .join(".pkgrep/deps/maven/com.example/demo@1.0.0");
+    assert!(link.join("README.md").exists());
+}
+
+#[test]
+fn pull_maven_artifact_falls_back_to_the_sources_jar() {
+    use std::io::Write;
+
+    let temp = TempDir::new().expect("tempdir");
+    let repository_dir = temp.path().join("m2");
+    write_maven_repository_file(
+        &repository_dir,
+        "com.example",
+        "demo",
+        "1.0.0",
+        "demo-1.0.0.pom",
+        b"<project><groupId>com.example</groupId><artifactId>demo</artifactId><version>1.0.0</version></project>",
+    );
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .start_file(
+            "com/example/Demo.java",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .expect("start jar entry");
+    writer
+        .write_all(b"package com.example;\n")
+        .expect("write jar entry");
+    let sources_jar = writer.finish().expect("finish jar").into_inner();
+    write_maven_repository_file(
+        &repository_dir,
+        "com.example",
+        "demo",
+        "1.0.0",
+        "demo-1.0.0-sources.jar",
+        &sources_jar,
+    );
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_MAVEN_REPOSITORY_URL", file_url(&repository_dir))
+        .args(["pull", "maven:com.example:demo@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp
+        .path()
+        .join(".pkgrep/deps/maven/com.example/demo@1.0.0");
+    assert_eq!(
+        std::fs::read_to_string(link.join("com/example/Demo.java")).expect("read unpacked source"),
+        "package com.example;\n"
+    );
+}
+
+#[test]
+fn pull_packagist_package_checks_out_the_locked_reference() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+
+    let repository_dir = temp.path().join("packagist");
+    let vendor_dir = repository_dir.join("p2/acme");
+    std::fs::create_dir_all(&vendor_dir).expect("create packagist metadata dir");
+    let metadata = json!({
+        "minified": "composer/2.0",
+        "packages": {
+            "acme/demo": [
+                {
+                    "name": "acme/demo",
+                    "version": "2.0.0",
+                    "version_normalized": "2.0.0.0",
+                    "source": {
+                        "type": "git",
+                        "url": file_url(&repo_path),
+                        "reference": "0000000000000000000000000000000000000000"
+                    }
+                },
+                {
+                    "version": "1.0.0",
+                    "version_normalized": "1.0.0.0",
+                    "source": {
+                        "type": "git",
+                        "url": file_url(&repo_path),
+                        "reference": commit
+                    }
+                }
+            ]
+        }
+    });
+    std::fs::write(
+        vendor_dir.join("demo.json"),
+        serde_json::to_vec(&metadata).expect("serialize packagist metadata"),
+    )
+    .expect("write packagist metadata");
+    let lock = json!({
+        "packages": [
+            {
+                "name": "acme/demo",
+                "version": "1.0.0",
+                "source": {"type": "git", "url": file_url(&repo_path), "reference": commit}
+            }
+        ],
+        "packages-dev": []
+    });
+    std::fs::write(
+        temp.path().join("composer.lock"),
+        serde_json::to_vec(&lock).expect("serialize composer.lock"),
+    )
+    .expect("write composer.lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_PACKAGIST_REPOSITORY_URL", file_url(&repository_dir))
+        .args(["pull", "packagist:acme/demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "detected installed packagist version for acme/demo: 1.0.0 (from composer.lock)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp
+        .path()
+        .join(format!(".pkgrep/deps/packagist/acme/demo@{commit}"));
+    assert!(link.join("README.md").exists());
+}
+
+#[test]
+fn pull_nuget_shorthand_checks_out_the_nuspec_commit() {
+    use std::io::Write;
+
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+
+    let nuspec = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd">
+  <metadata>
+    <id>Acme.Demo</id>
+    <version>1.0.0</version>
+    <repository type="git" url="{}" commit="{commit}" />
+  </metadata>
+</package>"#,
+        file_url(&repo_path)
+    );
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .start_file("Acme.Demo.nuspec", zip::write::SimpleFileOptions::default())
+        .expect("start nupkg entry");
+    writer
+        .write_all(nuspec.as_bytes())
+        .expect("write nupkg entry");
+    let nupkg = writer.finish().expect("finish nupkg").into_inner();
+    let feed_dir = temp.path().join("flat-container");
+    let version_dir = feed_dir.join("acme.demo/1.0.0");
+    std::fs::create_dir_all(&version_dir).expect("create nuget feed dir");
+    std::fs::write(version_dir.join("acme.demo.1.0.0.nupkg"), nupkg).expect("write nupkg");
+    std::fs::write(
+        temp.path().join("packages.lock.json"),
+        r#"{"version":1,"dependencies":{"net8.0":{"Acme.Demo":{"type":"Direct","requested":"[1.0.0, )","resolved":"1.0.0"}}}}"#,
+    )
+    .expect("write packages.lock.json");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_NUGET_FEED_URL", &feed_dir)
+        .args(["pull", "Acme.Demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "inferred shorthand 'Acme.Demo' as 'nuget:Acme.Demo'",
+        ))
+        .stdout(predicate::str::contains(
+            "detected installed nuget version for Acme.Demo: 1.0.0 (from packages.lock.json)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp
+        .path()
+        .join(format!(".pkgrep/deps/nuget/Acme.Demo@{commit}"));
+    assert!(link.join("README.md").exists());
+}
+
+fn write_hex_package_metadata(api_dir: &Path, package_name: &str, metadata: &Value) {
+    let packages_dir = api_dir.join("packages");
+    std::fs::create_dir_all(&packages_dir).expect("create hex api dir");
+    std::fs::write(
+        packages_dir.join(package_name),
+        serde_json::to_vec(metadata).expect("serialize hex metadata"),
+    )
+    .expect("write hex metadata");
+}
+
+#[test]
+fn pull_hex_shorthand_uses_the_mix_lock_version_and_source_link() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    let commit = init_local_git_repo(&repo_path);
+    let repo = Repository::open(&repo_path).expect("open repo");
+    let target = repo
+        .find_object(git2::Oid::from_str(&commit).expect("oid"), None)
+        .expect("find commit");
+    repo.tag_lightweight("v1.0.0", &target, false)
+        .expect("tag release");
+
+    let api_dir = temp.path().join("hex-api");
+    write_hex_package_metadata(
+        &api_dir,
+        "demo",
+        &json!({
+            "name": "demo",
+            "latest_stable_version": "1.1.0",
+            "releases": [{"version": "1.1.0"}, {"version": "1.0.0"}],
+            "meta": {"links": {"Docs": "https://hexdocs.pm/demo", "GitHub": file_url(&repo_path)}}
+        }),
+    );
+    std::fs::write(
+        temp.path().join("mix.lock"),
+        "%{\n  \"demo\": {:hex, :demo, \"1.0.0\", \"abc\", [:mix], [], \"hexpm\", \"def\"},\n}\n",
+    )
+    .expect("write mix.lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_HEX_API_URL", file_url(&api_dir))
+        .args(["pull", "demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "inferred shorthand 'demo' as 'hex:demo'",
+        ))
+        .stdout(predicate::str::contains(
+            "detected installed hex version for demo: 1.0.0 (from mix.lock)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/hex/demo@1.0.0");
+    assert!(link.join("README.md").exists());
+}
+
+#[test]
+fn pull_hex_package_without_source_link_unpacks_the_tarball() {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    let temp = TempDir::new().expect("tempdir");
+    let api_dir = temp.path().join("hex-api");
+    write_hex_package_metadata(
+        &api_dir,
+        "demo",
+        &json!({
+            "name": "demo",
+            "releases": [{"version": "1.0.0"}],
+            "meta": {"links": {"Docs": "https://hexdocs.pm/demo"}}
+        }),
+    );
+
+    let source = b"defmodule Demo do\nend\n";
+    let mut contents = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(source.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    contents
+        .append_data(&mut header, "lib/demo.ex", &source[..])
+        .expect("append contents entry");
+    let contents = contents
+        .into_inner()
+        .expect("finish contents tar")
+        .finish()
+        .expect("finish contents gzip");
+    let mut tarball = tar::Builder::new(Vec::new());
+    for (path, data) in [("VERSION", &b"3"[..]), ("contents.tar.gz", &contents[..])] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tarball
+            .append_data(&mut header, path, data)
+            .expect("append tarball entry");
+    }
+    let repository_dir = temp.path().join("hex-repo");
+    std::fs::create_dir_all(repository_dir.join("tarballs")).expect("create hex repo dir");
+    std::fs::write(
+        repository_dir.join("tarballs/demo-1.0.0.tar"),
+        tarball.into_inner().expect("finish tarball"),
+    )
+    .expect("write hex tarball");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_HEX_API_URL", file_url(&api_dir))
+        .env("PKGREP_HEX_REPOSITORY_URL", file_url(&repository_dir))
+        .args(["pull", "hex:demo@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/hex/demo@1.0.0");
+    assert_eq!(
+        std::fs::read(link.join("lib/demo.ex")).expect("read unpacked source"),
+        source
+    );
+}
+
+#[test]
+fn pull_pub_shorthand_uses_the_pubspec_lock_version_and_repository() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-repo");
+    init_local_git_repo(&repo_path);
+    let commit = commit_fixture_file(&repo_path, "pubspec.yaml", "name: demo\nversion: 1.0.0\n");
+    let repo = Repository::open(&repo_path).expect("open repo");
+    let target = repo
+        .find_object(git2::Oid::from_str(&commit).expect("oid"), None)
+        .expect("find commit");
+    repo.tag_lightweight("v1.0.0", &target, false)
+        .expect("tag release");
+
+    let hosted_dir = temp.path().join("pub-hosted");
+    let packages_dir = hosted_dir.join("api").join("packages");
+    std::fs::create_dir_all(&packages_dir).expect("create pub api dir");
+    let release = |version: &str| {
+        json!({
+            "version": version,
+            "pubspec": {"name": "demo", "version": version, "repository": file_url(&repo_path)},
+            "archive_url": format!("{}/api/archives/demo-{version}.tar.gz", file_url(&hosted_dir)),
+        })
+    };
+    std::fs::write(
+        packages_dir.join("demo"),
+        serde_json::to_vec(&json!({
+            "name": "demo",
+            "latest": release("1.1.0"),
+            "versions": [release("1.0.0"), release("1.1.0")],
+        }))
+        .expect("serialize pub metadata"),
+    )
+    .expect("write pub metadata");
+    std::fs::write(
+        temp.path().join("pubspec.lock"),
+        "packages:\n  demo:\n    dependency: \"direct main\"\n    description:\n      name: demo\n      url: \"https://pub.dev\"\n    source: hosted\n    version: \"1.0.0\"\nsdks:\n  dart: \">=3.3.0 <4.0.0\"\n",
+    )
+    .expect("write pubspec.lock");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_PUB_HOSTED_URL", file_url(&hosted_dir))
+        .args(["pull", "demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "inferred shorthand 'demo' as 'pub:demo'",
+        ))
+        .stdout(predicate::str::contains(
+            "detected installed pub version for demo: 1.0.0 (from pubspec.lock)",
+        ))
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/pub/demo@1.0.0");
+    assert!(link.join("pubspec.yaml").exists());
+}
+
+/// Serves the routes built by `routes` (path -> body) over plain HTTP on a loopback
+/// port, answering 401 to requests without `expected_authorization`. The builder
+/// receives the base URL so bodies can link back to the stand-in.
+fn serve_registry(
+    expected_authorization: &str,
+    routes: impl FnOnce(&str) -> Vec<(String, Vec<u8>)>,
+) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind registry stand-in");
+    let base_url = format!("http://{}", listener.local_addr().expect("local addr"));
+    let routes = routes(&base_url);
+    let expected_authorization = expected_authorization.to_string();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let mut authorized = false;
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':')
+                    && name.eq_ignore_ascii_case("authorization")
+                {
+                    authorized = value.trim() == expected_authorization;
+                }
+            }
+
+            let (status, body) = match routes
+                .iter()
+                .find(|(route, _)| route.eq_ignore_ascii_case(&path))
+            {
+                _ if !authorized => ("401 Unauthorized", Vec::new()),
+                Some((_, body)) => ("200 OK", body.clone()),
+                None => ("404 Not Found", Vec::new()),
+            };
+            let head = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(&body);
+        }
+    });
+    base_url
+}
+
+/// A loopback HTTP registry stand-in and the `<status> <path>` of every response
+/// it has sent.
+struct RegistryStandIn {
+    url: String,
+    responses: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl RegistryStandIn {
+    fn responses(&self) -> Vec<String> {
+        self.responses.lock().expect("responses lock").clone()
+    }
+}
+
+/// Like [`serve_registry`] without authorization, but bodies carry an `ETag`, a
+/// matching `If-None-Match` gets a 304, and every response is logged for the
+/// metadata cache tests.
+fn serve_revalidating_registry(
+    routes: impl FnOnce(&str) -> Vec<(String, Vec<u8>)>,
+) -> RegistryStandIn {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind registry stand-in");
+    let base_url = format!("http://{}", listener.local_addr().expect("local addr"));
+    let routes = routes(&base_url);
+    let responses = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let response_log = std::sync::Arc::clone(&responses);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let mut if_none_match = None;
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':')
+                    && name.eq_ignore_ascii_case("if-none-match")
+                {
+                    if_none_match = Some(value.trim().to_string());
+                }
+            }
+
+            let route = routes
+                .iter()
+                .position(|(route, _)| route.eq_ignore_ascii_case(&path));
+            let etag = route.map(|index| format!("\"route-{index}\""));
+            let (status, body) = match route {
+                Some(_) if if_none_match.is_some() && if_none_match == etag => {
+                    ("304 Not Modified", Vec::new())
+                }
+                Some(index) => ("200 OK", routes[index].1.clone()),
+                None => ("404 Not Found", Vec::new()),
+            };
+            response_log
+                .lock()
+                .expect("responses lock")
+                .push(format!("{} {path}", &status[..3]));
+            let etag_header = etag
+                .map(|etag| format!("ETag: {etag}\r\n"))
+                .unwrap_or_default();
+            let head = format!(
+                "HTTP/1.1 {status}\r\n{etag_header}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(&body);
+        }
+    });
+    RegistryStandIn {
+        url: base_url,
+        responses,
+    }
+}
+
+#[test]
+fn pull_scoped_npm_package_from_private_registry_with_npmrc_token() {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    let source = b"export const button = 1;\n";
+    let mut tarball = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(source.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tarball
+        .append_data(&mut header, "package/index.js", &source[..])
+        .expect("append tarball entry");
+    let tarball = tarball
+        .into_inner()
+        .expect("finish tarball")
+        .finish()
+        .expect("finish gzip");
+
+    let registry_url = serve_registry("Bearer secret", |base_url| {
+        let tarball_path = "/@ourco/ui/-/ui-1.0.0.tgz";
+        let metadata = json!({
+            "name": "@ourco/ui",
+            "dist-tags": {"latest": "1.0.0"},
+            "versions": {"1.0.0": {"dist": {"tarball": format!("{base_url}{tarball_path}")}}}
+        });
+        let metadata = serde_json::to_vec(&metadata).expect("serialize metadata");
+        vec![
+            (String::from("/@ourco%2fui"), metadata.clone()),
+            (String::from("/@ourco/ui"), metadata),
+            (String::from(tarball_path), tarball),
+        ]
+    });
+    let registry_host = registry_url.trim_start_matches("http://");
+
+    let temp = TempDir::new().expect("tempdir");
+    std::fs::write(
+        temp.path().join(".npmrc"),
+        format!("@ourco:registry={registry_url}/\n//{registry_host}/:_authToken=secret\n"),
+    )
+    .expect("write .npmrc");
+
+    cmd_in_temp(&temp)
+        .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+        .env_remove("PKGREP_NPM_REGISTRY_URL")
+        .args(["pull", "npm:@ourco/ui@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = temp.path().join(".pkgrep/deps/npm/ourco/ui@1.0.0");
+    assert_eq!(
+        std::fs::read_to_string(link.join("index.js")).expect("read linked file"),
+        "export const button = 1;\n"
+    );
+}
+
+#[test]
+fn offline_pull_resolves_from_cached_registry_metadata_and_manifest_aliases() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("internal").join("left-pad.git");
+    let revision = init_local_git_repo(&repo_path);
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    let metadata = json!({
+        "name": "left-pad",
+        "dist-tags": {"latest": "1.0.0"},
+        "versions": {"1.0.0": {
+            "repository": {"type": "git", "url": "https://git.internal.example/left-pad"},
+            "gitHead": revision
+        }}
+    });
+    let registry = serve_revalidating_registry(|_| {
+        vec![(
+            String::from("/left-pad"),
+            serde_json::to_vec(&metadata).expect("serialize metadata"),
+        )]
+    });
+    let pkgrep = |args: &[&str]| {
+        let mut cmd = cmd_in_temp(&temp);
+        cmd.env("PKGREP_NPM_REGISTRY_URL", &registry.url)
+            .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+            .env_remove("PKGREP_OFFLINE")
+            .args(args);
+        cmd
+    };
+
+    pkgrep(&["pull", "npm:left-pad@1.0.0"]).assert().success();
+    pkgrep(&["pull", "npm:left-pad@1.0.0"]).assert().success();
+    assert_eq!(
+        registry.responses(),
+        vec![String::from("200 /left-pad"), String::from("304 /left-pad")]
+    );
+
+    pkgrep(&["remove", "npm:left-pad", "--yes"])
+        .assert()
+        .success();
+    pkgrep(&["--offline", "pull", "npm:left-pad@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+    assert!(
+        temp.path()
+            .join(format!(".pkgrep/deps/npm/left-pad@{revision}/README.md"))
+            .exists()
+    );
+
+    pkgrep(&["pull", "--offline", "npm:left-pad@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "offline: npm:left-pad@1.0.0 already linked",
+        ));
+    pkgrep(&["pull", "--offline", "npm:right-pad@1.0.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "offline mode: cannot resolve npm:right-pad@1.0.0 from cached registry metadata",
+        ));
+    assert_eq!(registry.responses().len(), 2);
+}
+
+#[test]
+fn crate_archives_read_during_resolution_stay_out_of_the_metadata_cache() {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("internal").join("demo.git");
+    let revision = init_local_git_repo(&repo_path);
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    let vcs_info = format!(r#"{{"git":{{"sha1":"{revision}"}}}}"#);
+    let mut crate_archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(vcs_info.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    crate_archive
+        .append_data(
+            &mut header,
+            "demo-1.0.0/.cargo_vcs_info.json",
+            vcs_info.as_bytes(),
+        )
+        .expect("append crate entry");
+    let crate_archive = crate_archive
+        .into_inner()
+        .expect("finish crate archive")
+        .finish()
+        .expect("finish gzip");
+
+    let metadata = json!({
+        "crate": {"max_version": "1.0.0", "repository": "https://git.internal.example/demo"},
+        "versions": [{"num": "1.0.0"}]
+    });
+    let registry = serve_revalidating_registry(|_| {
+        vec![
+            (
+                String::from("/demo"),
+                serde_json::to_vec(&metadata).expect("serialize metadata"),
+            ),
+            (String::from("/demo/1.0.0/download"), crate_archive),
+        ]
+    });
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_CRATES_REGISTRY_URL", &registry.url)
+        .env_remove("PKGREP_OFFLINE")
+        .args(["pull", "crates:demo@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    assert!(
+        temp.path()
+            .join(format!(".pkgrep/deps/crates/demo@{revision}/README.md"))
+            .exists()
+    );
+    assert_eq!(
+        registry.responses(),
+        vec![
+            String::from("200 /demo"),
+            String::from("200 /demo/1.0.0/download")
+        ]
+    );
+    let cached_entries = std::fs::read_dir(configured_cache_dir(&temp).join("registry-metadata"))
+        .expect("read metadata cache")
+        .count();
+    assert_eq!(cached_entries, 1);
+}
+
+#[test]
+fn grep_searches_linked_checkouts_and_tags_matches_with_dep_spec() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+    let dep_spec = format!("git:{}@{}", repo_path.display(), revision);
+
+    cmd_in_temp(&temp)
+        .args(["pull", &dep_spec])
+        .assert()
+        .success();
+
+    let manifest_dep_spec = dep_spec.clone();
+    cmd_in_temp(&temp)
+        .args(["grep", "FIXTURE", "-i"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{manifest_dep_spec}:README.md:1:fixture repo"
+        )));
+
+    let output = cmd_in_temp(&temp)
+        .args(["grep", "fixture", &dep_spec, "--json"])
+        .output()
+        .expect("run grep --json");
+    assert!(output.status.success());
+    let payload: Value = serde_json::from_slice(&output.stdout).expect("grep json");
+    assert_eq!(
+        payload["matches"],
+        json!([{
+            "dep_spec": manifest_dep_spec,
+            "path": "README.md",
+            "line_number": 1,
+            "line": "fixture repo",
+        }])
+    );
+
+    cmd_in_temp(&temp)
+        .args(["grep", "fixture", "npm:not-linked"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "dependency is not linked in this project: npm:not-linked",
+        ));
+}
+
+#[test]
+fn pull_resolves_explicit_registry_specs_concurrently_in_spec_order() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    let temp = TempDir::new().expect("tempdir");
+    let mut routes = Vec::new();
+    for package in ["left-pad", "right-pad"] {
+        let revision =
+            init_local_git_repo(&temp.path().join("internal").join(format!("{package}.git")));
+        let metadata = json!({
+            "name": package,
+            "dist-tags": {"latest": "1.0.0"},
+            "versions": {"1.0.0": {
+                "repository": {"type": "git", "url": format!("https://git.internal.example/{package}")},
+                "gitHead": revision
+            }}
+        });
+        routes.push((
+            format!("/{package}"),
+            serde_json::to_vec(&metadata).expect("serialize metadata"),
+        ));
+    }
+    std::fs::write(
+        temp.path().join("pkgrep.toml"),
+        format!(
+            "[git_url_rewrites]\n\"https://git.internal.example/\" = \"{}/\"\n",
+            temp.path().join("internal").display()
+        ),
+    )
+    .expect("write project config");
+
+    // Metadata requests are held until both are in flight (or a deadline passes), so
+    // the number held shows whether the lookups overlapped.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind registry stand-in");
+    let registry_url = format!("http://{}", listener.local_addr().expect("local addr"));
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let held_requests = Arc::clone(&in_flight);
+    std::thread::spawn(move || {
+        let respond = |mut stream: std::net::TcpStream| {
+            stream.set_nonblocking(false).expect("blocking stream");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut request_line = String::new();
+            let _ = reader.read_line(&mut request_line);
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+                    break;
+                }
+            }
+            let (status, body) = match routes.iter().find(|(route, _)| *route == path) {
+                Some((_, body)) => ("200 OK", body.clone()),
+                None => ("404 Not Found", Vec::new()),
+            };
+            let head = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(&body);
+        };
+
+        listener
+            .set_nonblocking(true)
+            .expect("nonblocking listener");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut held = Vec::new();
+        while held.len() < routes.len() && Instant::now() < deadline {
+            match listener.accept() {
+                Ok((stream, _)) => held.push(stream),
+                Err(_) => std::thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        held_requests.store(held.len(), Ordering::SeqCst);
+        for stream in held {
+            respond(stream);
+        }
+        listener.set_nonblocking(false).expect("blocking listener");
+        for stream in listener.incoming().flatten() {
+            respond(stream);
+        }
+    });
+
+    let output = cmd_in_temp(&temp)
+        .env("PKGREP_NPM_REGISTRY_URL", &registry_url)
+        .env("NPM_CONFIG_USERCONFIG", temp.path().join("missing-npmrc"))
+        .env("PKGREP_WORKER_POOL_SIZE", "2")
+        .env_remove("PKGREP_OFFLINE")
+        .args(["pull", "npm:right-pad@1.0.0", "npm:left-pad@1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=2"))
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_eq!(in_flight.load(Ordering::SeqCst), 2);
+    let stdout = String::from_utf8(output).expect("utf8 stdout");
+    let right = stdout
+        .find("resolving package metadata for npm:right-pad@1.0.0")
+        .expect("right-pad resolution line");
+    let left = stdout
+        .find("resolving package metadata for npm:left-pad@1.0.0")
+        .expect("left-pad resolution line");
+    assert!(
+        right < left,
+        "resolution output follows spec order:\n{stdout}"
+    );
+}
+
+#[test]
+fn path_returns_link_when_present() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+    let dep_spec = format!("git:{}@{}", repo_path.display(), revision);
+
+    cmd_in_temp(&temp)
+        .args(["pull", &dep_spec])
+        .assert()
+        .success();
+
+    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("git"));
+    let link_display = link.display().to_string();
+
+    cmd_in_temp(&temp)
+        .args(["path", &dep_spec])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&link_display));
+}
+
+#[test]
+fn path_returns_link_for_bare_git_spec_when_single_match() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    init_local_git_repo(&repo_path);
+    let dep_spec = format!("git:{}", repo_path.display());
+
+    cmd_in_temp(&temp)
+        .args(["pull", &dep_spec])
+        .assert()
+        .success();
+
+    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("git"));
+    let expected = link.display().to_string();
+    cmd_in_temp(&temp)
+        .args(["path", &dep_spec])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(expected));
+}
+
+#[test]
+fn path_fails_when_dep_is_not_linked() {
+    let temp = TempDir::new().expect("tempdir");
+    cmd_in_temp(&temp)
+        .args(["path", "git:https://github.com/facebook/react.git@v18.3.1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "dependency is not linked in this project",
+        ));
+}
+
+#[test]
+fn path_returns_registry_link_when_present() {
+    let temp = TempDir::new().expect("tempdir");
+    let link_relative = ".pkgrep/deps/npm/react@deadbeef";
+    let link_path = temp.path().join(link_relative);
+    std::fs::create_dir_all(&link_path).expect("create linked path");
+
+    let manifest_path = temp.path().join(".pkgrep").join("manifest.json");
+    std::fs::create_dir_all(
+        manifest_path
+            .parent()
+            .expect("manifest parent directory should exist"),
+    )
+    .expect("create manifest parent");
+    let manifest = json!({
+        "schema_version": 1,
+        "entries": {
+            "git:https://github.com/facebook/react.git@deadbeef": {
+                "link_path": link_relative,
+                "cache_key": "npm/b64_cmVhY3Q/deadbeef/fingerprint",
+                "aliases": ["npm:react", "npm:react@18.3.1"],
+                "registry_refs": [{
+                    "ecosystem": "npm",
+                    "name": "react",
+                    "package_version": "18.3.1"
+                }]
+            }
+        }
+    });
+    std::fs::write(
+        &manifest_path,
+        serde_json::to_vec_pretty(&manifest).expect("serialize manifest"),
+    )
+    .expect("write manifest");
+
+    let expected = link_path.display().to_string();
+    cmd_in_temp(&temp)
+        .args(["path", "npm:react@18.3.1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(expected));
+}
+
+#[test]
+fn verify_rejects_git_specs() {
+    let temp = TempDir::new().expect("tempdir");
+    cmd_in_temp(&temp)
+        .args([
+            "verify",
+            "git:https://github.com/facebook/react.git@v18.3.1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "verify supports registry package specs only",
+        ));
+}
+
+#[test]
+fn verify_looks_up_the_linked_package_version_in_the_registry() {
+    let temp = TempDir::new().expect("tempdir");
+    cmd_in_temp(&temp)
+        .args(["verify", "npm:react"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "dependency is not linked in this project: npm:react",
+        ));
+
+    let link_relative = ".pkgrep/deps/npm/react@deadbeef";
+    std::fs::create_dir_all(temp.path().join(link_relative)).expect("create linked path");
+    let manifest_path = temp.path().join(".pkgrep").join("manifest.json");
+    let manifest = json!({
+        "schema_version": 1,
+        "entries": {
+            "git:https://github.com/facebook/react.git@deadbeef": {
+                "link_path": link_relative,
+                "cache_key": "npm/b64_cmVhY3Q/deadbeef/fingerprint",
+                "aliases": ["npm:react", "npm:react@18.3.1"],
+                "registry_refs": [{
+                    "ecosystem": "npm",
+                    "name": "react",
+                    "package_version": "18.3.1"
+                }]
+            }
+        }
+    });
+    std::fs::write(
+        &manifest_path,
+        serde_json::to_vec_pretty(&manifest).expect("serialize manifest"),
+    )
+    .expect("write manifest");
+
+    cmd_in_temp(&temp)
+        .env("PKGREP_NPM_REGISTRY_URL", "not-a-url")
+        .args(["verify", "npm:react"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid npm registry URL"));
+    let manifest = read_json(&manifest_path);
+    assert!(
+        manifest["entries"]["git:https://github.com/facebook/react.git@deadbeef"]
+            .get("verification")
+            .is_none()
+    );
+}
+
+#[test]
+fn list_reports_linked_dependencies() {
+    let temp = TempDir::new().expect("tempdir");
+    let link_relative = ".pkgrep/deps/npm/react@deadbeef";
     let link_path = temp.path().join(link_relative);
     std::fs::create_dir_all(&link_path).expect("create linked path");
 
@@ -1261,6 +2887,149 @@ fn pull_without_specs_uses_yarn_lock_git_hint() {
     assert!(metadata.file_type().is_symlink());
 }
 
+#[test]
+fn pull_without_specs_uses_yarn_berry_lock_git_hint() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+
+    let yarn_lock = format!(
+        "__metadata:\n  version: 8\n  cacheKey: 10c0\n\n\"demo-git-package@git+file://{}#main\":\n  version: 1.0.0\n  resolution: \"demo-git-package@git+file://{}#commit={}\"\n  languageName: node\n  linkType: hard\n",
+        repo_path.display(),
+        repo_path.display(),
+        revision,
+    );
+    std::fs::write(temp.path().join("yarn.lock"), yarn_lock).expect("write yarn lock");
+
+    cmd_in_temp(&temp)
+        .args(["pull"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let npm_links = temp.path().join(".pkgrep").join("deps").join("npm");
+    let link = first_symlink_entry(&npm_links);
+    assert!(link.join("README.md").exists());
+}
+
+#[test]
+fn pull_without_specs_uses_bun_lock_git_hint() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    let revision = init_local_git_repo(&repo_path);
+
+    let bun_lock = format!(
+        "{{\n  \"lockfileVersion\": 1,\n  \"packages\": {{\n    \"demo-git-package\": [\"demo-git-package@git+file://{}#{}\", {{}}, \"{}\"],\n  }}\n}}\n",
+        repo_path.display(),
+        revision,
+        revision,
+    );
+    std::fs::write(temp.path().join("bun.lock"), bun_lock).expect("write bun lock");
+
+    cmd_in_temp(&temp)
+        .args(["pull"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let npm_links = temp.path().join(".pkgrep").join("deps").join("npm");
+    let link = first_symlink_entry(&npm_links);
+    assert!(link.join("README.md").exists());
+}
+
+#[test]
+fn pull_without_specs_uses_pubspec_lock_git_hint_subdirectory() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("source-repo");
+    init_local_git_repo(&repo_path);
+    std::fs::create_dir_all(repo_path.join("packages/shared_widgets")).expect("create package dir");
+    let revision = commit_fixture_file(
+        &repo_path,
+        "packages/shared_widgets/pubspec.yaml",
+        "name: shared_widgets\n",
+    );
+
+    let pubspec_lock = format!(
+        "packages:\n  shared_widgets:\n    dependency: \"direct main\"\n    description:\n      path: \"packages/shared_widgets\"\n      ref: main\n      resolved-ref: \"{}\"\n      url: \"{}\"\n    source: git\n    version: \"0.3.0\"\n",
+        revision,
+        file_url(&repo_path),
+    );
+    std::fs::write(temp.path().join("pubspec.lock"), pubspec_lock).expect("write pubspec.lock");
+
+    cmd_in_temp(&temp)
+        .args(["pull"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let pub_links = temp.path().join(".pkgrep").join("deps").join("pub");
+    let link = first_symlink_entry(&pub_links);
+    assert!(
+        link.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("shared_widgets@"))
+    );
+    assert!(link.join("pubspec.yaml").exists());
+    assert!(!link.join("README.md").exists());
+}
+
+#[test]
+fn pull_mirrors_package_resolved_pins_and_resolves_swift_identities() {
+    let temp = TempDir::new().expect("tempdir");
+    let repo_path = temp.path().join("demo-kit");
+    let revision = init_local_git_repo(&repo_path);
+
+    let package_resolved = json!({
+        "pins": [{
+            "identity": "demo-kit",
+            "kind": "localSourceControl",
+            "location": repo_path.display().to_string(),
+            "state": {"revision": revision, "version": "1.2.0"}
+        }],
+        "version": 2
+    });
+    std::fs::write(
+        temp.path().join("Package.resolved"),
+        serde_json::to_vec_pretty(&package_resolved).expect("serialize Package.resolved"),
+    )
+    .expect("write Package.resolved");
+
+    cmd_in_temp(&temp)
+        .args(["pull"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pull completed: total=1"));
+
+    let link = first_symlink_entry(&temp.path().join(".pkgrep").join("deps").join("swift"));
+    assert!(link.join("README.md").exists());
+    cmd_in_temp(&temp)
+        .args(["path", "swift:demo-kit@1.2.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(link.display().to_string()));
+
+    cmd_in_temp(&temp)
+        .args(["pull", "Demo-Kit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "inferred shorthand 'Demo-Kit' as 'swift:Demo-Kit'",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "resolved swift:demo-kit from Package.resolved -> {} at {}",
+            repo_path.display(),
+            revision
+        )));
+
+    cmd_in_temp(&temp)
+        .args(["pull", "swift:demo-kit@2.0.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "swift package 'demo-kit' is pinned at 1.2.0 in Package.resolved, not 2.0.0",
+        ));
+}
+
 #[test]
 fn remove_with_yes_deletes_project_symlink() {
     let temp = TempDir::new().expect("tempdir");